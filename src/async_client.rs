@@ -0,0 +1,107 @@
+use crate::Error;
+use crate::Packet;
+use futures_core::Stream;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::ReadBuf;
+use tokio::net::UdpSocket;
+
+pub struct AsyncF1TelemetryClient {
+    socket: UdpSocket,
+    buf: [u8; 2048],
+    // The motion ex packet carried by the last legacy motion packet.
+    pending: Option<Packet>,
+}
+
+impl AsyncF1TelemetryClient {
+    pub async fn new(bind_address: &str) -> Result<Self, Error> {
+        let socket: UdpSocket = UdpSocket::bind(bind_address).await.map_err(Error::Bind)?;
+
+        Ok(AsyncF1TelemetryClient::from_socket(socket))
+    }
+
+    pub fn from_socket(socket: UdpSocket) -> Self {
+        let buf: [u8; 2048] = [0; 2048];
+
+        AsyncF1TelemetryClient {
+            socket,
+            buf,
+            pending: None,
+        }
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.socket.local_addr().map_err(Error::Io)
+    }
+}
+
+impl Stream for AsyncF1TelemetryClient {
+    type Item = Result<Packet, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this: &mut AsyncF1TelemetryClient = self.get_mut();
+        if let Some(packet) = this.pending.take() {
+            return Poll::Ready(Some(Ok(packet)));
+        }
+        let mut buf: ReadBuf<'_> = ReadBuf::new(&mut this.buf);
+        match this.socket.poll_recv(cx, &mut buf) {
+            Poll::Ready(Ok(())) => {
+                this.pending = Packet::decode_motion_ex(buf.filled());
+                Poll::Ready(Some(Packet::decode(buf.filled())))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(Error::Io(e)))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::PacketHeader;
+    use crate::packets::PacketLapData;
+    use std::future::poll_fn;
+
+    async fn next(client: &mut AsyncF1TelemetryClient) -> Option<Result<Packet, Error>> {
+        poll_fn(|cx| Pin::new(&mut *client).poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn test_async_client_yields_packets() {
+        let mut client: AsyncF1TelemetryClient =
+            AsyncF1TelemetryClient::new("127.0.0.1:0").await.unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let packet: Packet = Packet::Lap(PacketLapData {
+            header: PacketHeader {
+                packet_format: 2024,
+                packet_id: 2,
+                ..PacketHeader::default()
+            },
+            ..PacketLapData::default()
+        });
+        let addr: SocketAddr = client.local_addr().unwrap();
+        sender
+            .send_to(&packet.encode().unwrap(), addr)
+            .await
+            .unwrap();
+        sender.send_to(&[0u8; 10], addr).await.unwrap();
+
+        assert_eq!(next(&mut client).await.unwrap().unwrap(), packet);
+        assert!(matches!(
+            next(&mut client).await,
+            Some(Err(Error::TruncatedPacket {
+                expected: 29,
+                actual: 10
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_async_client_reports_bind_failure() {
+        assert!(matches!(
+            AsyncF1TelemetryClient::new("not an address").await,
+            Err(Error::Bind(_))
+        ));
+    }
+}
@@ -0,0 +1,527 @@
+use crate::index::{
+    CaptureIndex, CaptureIndexEntry, Indexer, INDEX_MAGIC, INDEX_MARKER, INDEX_TRAILER_SIZE,
+};
+use crate::{Compression, Error};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::fs::File;
+use std::io::{BufReader, Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// File header:
+//   magic: [u8; 4]          "F1TC"
+//   version: u16            little endian
+//   started_at: u64         nanoseconds since the Unix epoch, little endian
+//   compression: u8         since version 3: 0 for none, 1 for zstd, 2 for lz4
+// Followed by zero or more records:
+//   time: u64               nanoseconds since started_at (monotonic), little endian
+//   length: u32             little endian
+//   bytes: [u8; length]     the raw datagram
+// In compressed captures the records are grouped into blocks instead:
+//   compressed_length: u32  little endian
+//   length: u32             length of the records once decompressed, little endian
+//   bytes: [u8; compressed_length]
+// and a record is located by `block_offset << 16 | offset_in_block`, which is why no
+// record starts more than 64 KiB into its block.
+// Since version 2 captures may end with an index block, see `index.rs`.
+pub const CAPTURE_MAGIC: [u8; 4] = *b"F1TC";
+pub const CAPTURE_VERSION: u16 = 3;
+
+pub(crate) const BLOCK_SIZE: usize = 64 * 1024;
+
+// Lengths read from a capture are checked against these before anything is
+// allocated, so a corrupt length can't exhaust memory. A record holds a single
+// datagram, and a block is closed once it reaches `BLOCK_SIZE`.
+const MAX_RECORD_SIZE: usize = 2048;
+const MAX_BLOCK_SIZE: usize = 2 * BLOCK_SIZE;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureHeader {
+    pub version: u16,
+    pub started_at: SystemTime,
+    pub compression: Compression,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    pub time: Duration,
+    pub bytes: Vec<u8>,
+}
+
+impl CaptureHeader {
+    pub fn unserialize(reader: &mut impl Read) -> Result<Self, Error> {
+        let mut magic: [u8; 4] = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != CAPTURE_MAGIC {
+            return Err(Error::InvalidCaptureMagic(magic));
+        }
+        let version: u16 = reader.read_u16::<LittleEndian>()?;
+        if !(1..=CAPTURE_VERSION).contains(&version) {
+            return Err(Error::UnsupportedCaptureVersion(version));
+        }
+        let started_at: SystemTime =
+            UNIX_EPOCH + Duration::from_nanos(reader.read_u64::<LittleEndian>()?);
+        let compression: Compression = match version {
+            3.. => Compression::from_u8(reader.read_u8()?)?,
+            _ => Compression::None,
+        };
+
+        Ok(CaptureHeader {
+            version,
+            started_at,
+            compression,
+        })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(self.size() as usize);
+        bytes.write_all(&CAPTURE_MAGIC)?;
+        bytes.write_u16::<LittleEndian>(self.version)?;
+        bytes.write_u64::<LittleEndian>(nanos(
+            self.started_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+        ))?;
+        if self.version >= 3 {
+            bytes.write_u8(self.compression.to_u8())?;
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn size(&self) -> u64 {
+        match self.version {
+            3.. => 15,
+            _ => 14,
+        }
+    }
+}
+
+impl CaptureRecord {
+    pub fn unserialize(reader: &mut impl Read) -> Result<Self, std::io::Error> {
+        let time: Duration = Duration::from_nanos(reader.read_u64::<LittleEndian>()?);
+        let length: usize = reader.read_u32::<LittleEndian>()? as usize;
+        if length > MAX_RECORD_SIZE {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "record too large",
+            ));
+        }
+        let mut bytes: Vec<u8> = vec![0; length];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(CaptureRecord { time, bytes })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        if self.bytes.len() > MAX_RECORD_SIZE {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "record too large",
+            ));
+        }
+        let length: u32 = self.bytes.len() as u32;
+        let mut bytes: Vec<u8> = Vec::with_capacity(12 + self.bytes.len());
+        bytes.write_u64::<LittleEndian>(nanos(self.time))?;
+        bytes.write_u32::<LittleEndian>(length)?;
+        bytes.write_all(&self.bytes)?;
+
+        Ok(bytes)
+    }
+}
+
+pub struct CaptureReader<R: Read = BufReader<File>> {
+    reader: R,
+    header: CaptureHeader,
+    position: u64,
+    block_offset: u64,
+    block: Cursor<Vec<u8>>,
+    ended: bool,
+}
+
+impl CaptureReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        CaptureReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let header: CaptureHeader = CaptureHeader::unserialize(&mut reader)?;
+
+        Ok(CaptureReader {
+            reader,
+            header,
+            position: header.size(),
+            block_offset: 0,
+            block: Cursor::default(),
+            ended: false,
+        })
+    }
+
+    pub fn header(&self) -> CaptureHeader {
+        self.header
+    }
+
+    pub fn read_record(&mut self) -> Result<Option<CaptureRecord>, Error> {
+        if self.ended {
+            return Ok(None);
+        }
+        if self.header.compression != Compression::None {
+            return self.read_compressed_record();
+        }
+        let mut prefix: [u8; 12] = [0; 12];
+        let result: Result<Option<CaptureRecord>, std::io::Error> =
+            self.reader.read_exact(&mut prefix).and_then(|()| {
+                if prefix == INDEX_MARKER {
+                    return Ok(None);
+                }
+                CaptureRecord::unserialize(&mut prefix.as_slice().chain(&mut self.reader)).map(Some)
+            });
+        // A record cut short by a crash while it was being written marks the end of the capture.
+        match result {
+            Ok(Some(record)) => {
+                self.position += 12 + record.bytes.len() as u64;
+                Ok(Some(record))
+            }
+            Ok(None) => {
+                self.ended = true;
+                Ok(None)
+            }
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                self.ended = true;
+                Ok(None)
+            }
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    fn read_compressed_record(&mut self) -> Result<Option<CaptureRecord>, Error> {
+        while self.block.position() >= self.block.get_ref().len() as u64 {
+            if !self.read_block()? {
+                self.ended = true;
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(CaptureRecord::unserialize(&mut self.block)?))
+    }
+
+    fn read_block(&mut self) -> Result<bool, Error> {
+        let mut prefix: [u8; 8] = [0; 8];
+        let result: Result<Option<(Vec<u8>, usize)>, std::io::Error> =
+            self.reader.read_exact(&mut prefix).and_then(|()| {
+                if prefix == INDEX_MARKER[..8] {
+                    return Ok(None);
+                }
+                let compressed_length: usize = (&prefix[..4]).read_u32::<LittleEndian>()? as usize;
+                let length: usize = (&prefix[4..]).read_u32::<LittleEndian>()? as usize;
+                if compressed_length > MAX_BLOCK_SIZE || length > MAX_BLOCK_SIZE {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        "block too large",
+                    ));
+                }
+                let mut compressed: Vec<u8> = vec![0; compressed_length];
+                self.reader.read_exact(&mut compressed)?;
+                Ok(Some((compressed, length)))
+            });
+        // Like a truncated record, a truncated block marks the end of the capture.
+        match result {
+            Ok(Some((compressed, length))) => {
+                let block: Vec<u8> = self.header.compression.decompress(&compressed, length)?;
+                self.block_offset = self.position;
+                self.position += 8 + compressed.len() as u64;
+                self.block = Cursor::new(block);
+                Ok(true)
+            }
+            Ok(None) => Ok(false),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    // The offset of the next record, as stored in `CaptureIndexEntry::offset`.
+    fn offset(&self) -> u64 {
+        if self.header.compression == Compression::None {
+            self.position
+        } else if self.block.position() < self.block.get_ref().len() as u64 {
+            self.block_offset << 16 | self.block.position()
+        } else {
+            self.position << 16
+        }
+    }
+}
+
+impl<R: Read + Seek> CaptureReader<R> {
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        if self.header.compression == Compression::None {
+            self.seek_to_offset(self.header.size())
+        } else {
+            self.seek_to_offset(self.header.size() << 16)
+        }
+    }
+
+    pub fn seek_to(&mut self, entry: &CaptureIndexEntry) -> Result<(), Error> {
+        self.seek_to_offset(entry.offset)
+    }
+
+    pub fn index(&mut self) -> Result<CaptureIndex, Error> {
+        // Captures without an index block, such as those of a recorder that was never
+        // finished, are indexed by scanning every record.
+        let offset: u64 = self.offset();
+        let index: CaptureIndex = match self.read_index()? {
+            Some(index) => index,
+            None => self.build_index()?,
+        };
+        self.seek_to_offset(offset)?;
+
+        Ok(index)
+    }
+
+    fn read_index(&mut self) -> Result<Option<CaptureIndex>, Error> {
+        let end: u64 = self.reader.seek(SeekFrom::End(0))?;
+        if self.header.version < 2 || end < self.header.size() + INDEX_TRAILER_SIZE {
+            return Ok(None);
+        }
+        self.reader
+            .seek(SeekFrom::End(-(INDEX_TRAILER_SIZE as i64)))?;
+        let offset: u64 = self.reader.read_u64::<LittleEndian>()?;
+        let mut magic: [u8; 4] = [0; 4];
+        self.reader.read_exact(&mut magic)?;
+        if magic != INDEX_MAGIC || offset < self.header.size() || offset >= end {
+            return Ok(None);
+        }
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut marker: [u8; 12] = [0; 12];
+        self.reader.read_exact(&mut marker)?;
+        if marker != INDEX_MARKER {
+            return Ok(None);
+        }
+
+        Ok(Some(CaptureIndex::unserialize(&mut self.reader)?))
+    }
+
+    fn build_index(&mut self) -> Result<CaptureIndex, Error> {
+        self.rewind()?;
+        let mut indexer: Indexer = Indexer::default();
+        loop {
+            let offset: u64 = self.offset();
+            let Some(record) = self.read_record()? else {
+                break;
+            };
+            indexer.push(offset, &record.bytes);
+        }
+
+        Ok(indexer.into_index())
+    }
+
+    fn seek_to_offset(&mut self, offset: u64) -> Result<(), Error> {
+        self.block = Cursor::default();
+        self.ended = false;
+        if self.header.compression == Compression::None {
+            self.reader.seek(SeekFrom::Start(offset))?;
+            self.position = offset;
+        } else {
+            self.reader.seek(SeekFrom::Start(offset >> 16))?;
+            self.position = offset >> 16;
+            if offset & 0xffff != 0 && self.read_block()? {
+                self.block.set_position(offset & 0xffff);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CaptureRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_capture_header_serialization_deserialization() {
+        let original_header: CaptureHeader = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789),
+            compression: Compression::None,
+        };
+
+        let serialized_header: Vec<u8> = original_header.serialize().unwrap();
+        let deserialized_header: CaptureHeader =
+            CaptureHeader::unserialize(&mut serialized_header.as_slice()).unwrap();
+
+        assert_eq!(serialized_header.len(), 15);
+        assert_eq!(original_header, deserialized_header);
+    }
+
+    #[test]
+    fn test_capture_record_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_record: CaptureRecord = CaptureRecord {
+            time: Duration::from_nanos(rng.gen()),
+            bytes: (0..rng.gen_range(0..2048)).map(|_| rng.gen()).collect(),
+        };
+
+        let serialized_record: Vec<u8> = original_record.serialize().unwrap();
+        let deserialized_record: CaptureRecord =
+            CaptureRecord::unserialize(&mut serialized_record.as_slice()).unwrap();
+
+        assert_eq!(serialized_record.len(), 12 + original_record.bytes.len());
+        assert_eq!(original_record, deserialized_record);
+    }
+
+    #[test]
+    fn test_capture_reader_rejects_bad_magic() {
+        assert!(matches!(
+            CaptureReader::new(&b"PCAP\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00"[..]),
+            Err(Error::InvalidCaptureMagic(magic)) if magic == *b"PCAP"
+        ));
+    }
+
+    #[test]
+    fn test_capture_reader_rejects_unsupported_version() {
+        let mut bytes: Vec<u8> = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: UNIX_EPOCH,
+            compression: Compression::None,
+        }
+        .serialize()
+        .unwrap();
+        bytes[4] = 4;
+
+        assert!(matches!(
+            CaptureReader::new(bytes.as_slice()),
+            Err(Error::UnsupportedCaptureVersion(4))
+        ));
+    }
+
+    #[test]
+    fn test_capture_reader_rejects_unsupported_compression() {
+        let mut bytes: Vec<u8> = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: UNIX_EPOCH,
+            compression: Compression::None,
+        }
+        .serialize()
+        .unwrap();
+        bytes[14] = 9;
+
+        assert!(matches!(
+            CaptureReader::new(bytes.as_slice()),
+            Err(Error::UnsupportedCaptureCompression(9))
+        ));
+    }
+
+    #[test]
+    fn test_capture_reader_reads_version_1() {
+        let mut bytes: Vec<u8> = CaptureHeader {
+            version: 1,
+            started_at: UNIX_EPOCH,
+            compression: Compression::None,
+        }
+        .serialize()
+        .unwrap();
+        let record: CaptureRecord = CaptureRecord {
+            time: Duration::from_millis(5),
+            bytes: vec![1, 2, 3],
+        };
+        bytes.extend(record.serialize().unwrap());
+        let mut reader: CaptureReader<std::io::Cursor<Vec<u8>>> =
+            CaptureReader::new(std::io::Cursor::new(bytes)).unwrap();
+
+        assert_eq!(reader.header().version, 1);
+        assert_eq!(reader.index().unwrap(), CaptureIndex::default());
+        assert_eq!(reader.read_record().unwrap(), Some(record));
+        assert_eq!(reader.read_record().unwrap(), None);
+    }
+
+    #[test]
+    fn test_capture_reader_stops_at_truncated_record() {
+        let mut bytes: Vec<u8> = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: UNIX_EPOCH,
+            compression: Compression::None,
+        }
+        .serialize()
+        .unwrap();
+        let record: CaptureRecord = CaptureRecord {
+            time: Duration::from_millis(5),
+            bytes: vec![1, 2, 3],
+        };
+        bytes.extend(record.serialize().unwrap());
+        let complete: usize = bytes.len();
+        bytes.extend(record.serialize().unwrap());
+
+        for len in complete..bytes.len() {
+            let records: Vec<CaptureRecord> = CaptureReader::new(&bytes[..len])
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            assert_eq!(records, vec![record.clone()]);
+        }
+    }
+
+    fn oversized_capture(compression: Compression) -> Vec<u8> {
+        let mut bytes: Vec<u8> = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: UNIX_EPOCH,
+            compression,
+        }
+        .serialize()
+        .unwrap();
+        // Read as a record prefix this is a length of u32::MAX, and read as a
+        // block prefix a decompressed length of u32::MAX.
+        bytes.extend([0; 4]);
+        bytes.extend(u32::MAX.to_le_bytes());
+        bytes.extend(u32::MAX.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_capture_reader_rejects_oversized_record() {
+        let bytes: Vec<u8> = oversized_capture(Compression::None);
+        let mut reader: CaptureReader<&[u8]> = CaptureReader::new(bytes.as_slice()).unwrap();
+
+        assert!(matches!(
+            reader.read_record(),
+            Err(Error::Io(e)) if e.kind() == ErrorKind::InvalidData
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_capture_reader_rejects_oversized_block() {
+        let bytes: Vec<u8> = oversized_capture(Compression::Zstd);
+        let mut reader: CaptureReader<&[u8]> = CaptureReader::new(bytes.as_slice()).unwrap();
+
+        assert!(matches!(
+            reader.read_record(),
+            Err(Error::Io(e)) if e.kind() == ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_capture_record_rejects_oversized_datagram() {
+        let record: CaptureRecord = CaptureRecord {
+            time: Duration::ZERO,
+            bytes: vec![0; MAX_RECORD_SIZE + 1],
+        };
+
+        assert!(record.serialize().is_err());
+    }
+}
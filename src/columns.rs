@@ -0,0 +1,208 @@
+use crate::packets::{CarDamageData, CarMotionData, CarStatusData, CarTelemetryData, LapData};
+use std::fmt;
+
+// Wheel arrays are ordered rear left, rear right, front left, front right on the wire.
+pub(crate) const WHEELS: [(&str, usize); 4] = [("fl", 2), ("fr", 3), ("rl", 0), ("rr", 1)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Value {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+}
+
+macro_rules! value_from {
+    ($($type:ty => $variant:ident),*) => {
+        $(impl From<$type> for Value {
+            fn from(value: $type) -> Self {
+                Value::$variant(value)
+            }
+        })*
+    };
+}
+
+value_from!(u8 => U8, i8 => I8, u16 => U16, i16 => I16, u32 => U32, u64 => U64, f32 => F32);
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::U8(value) => write!(f, "{value}"),
+            Value::I8(value) => write!(f, "{value}"),
+            Value::U16(value) => write!(f, "{value}"),
+            Value::I16(value) => write!(f, "{value}"),
+            Value::U32(value) => write!(f, "{value}"),
+            Value::U64(value) => write!(f, "{value}"),
+            Value::F32(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+// Flattens a per-car struct into named columns, for the tabular exporters.
+pub(crate) trait Columns: Default {
+    fn names(names: &mut Vec<String>);
+    fn values(&self, values: &mut Vec<Value>);
+}
+
+// Fields in brackets are wheel arrays and expand to one column per wheel.
+macro_rules! columns {
+    ($type:ty { $($field:tt),* $(,)? }) => {
+        impl Columns for $type {
+            fn names(names: &mut Vec<String>) {
+                $(columns!(@name names, $field);)*
+            }
+
+            fn values(&self, values: &mut Vec<Value>) {
+                $(columns!(@value self, values, $field);)*
+            }
+        }
+    };
+    (@name $names:ident, [$field:ident]) => {
+        for (wheel, _) in WHEELS {
+            $names.push(format!("{}_{wheel}", stringify!($field)));
+        }
+    };
+    (@name $names:ident, $field:ident) => {
+        $names.push(stringify!($field).to_string());
+    };
+    (@value $self:ident, $values:ident, [$field:ident]) => {
+        let array = $self.$field;
+        for (_, index) in WHEELS {
+            $values.push(Value::from(array[index]));
+        }
+    };
+    (@value $self:ident, $values:ident, $field:ident) => {
+        $values.push(Value::from({ $self.$field }));
+    };
+}
+
+columns!(CarTelemetryData {
+    speed,
+    throttle,
+    steer,
+    brake,
+    clutch,
+    gear,
+    engine_rpm,
+    drs,
+    rev_lights_percent,
+    rev_lights_bit_value,
+    [brakes_temperature],
+    [tyres_surface_temperature],
+    [tyres_inner_temperature],
+    engine_temperature,
+    [tyres_pressure],
+    [surface_type],
+});
+
+columns!(CarStatusData {
+    traction_control,
+    anti_lock_brakes,
+    fuel_mix,
+    front_brake_bias,
+    pit_limiter_status,
+    fuel_in_tank,
+    fuel_capacity,
+    fuel_remaining_laps,
+    max_rpm,
+    idle_rpm,
+    max_gears,
+    drs_allowed,
+    drs_activation_distance,
+    actual_tyre_compound,
+    visual_tyre_compound,
+    tyres_age_laps,
+    vehicle_fia_flags,
+    engine_power_ice,
+    engine_power_mguk,
+    ers_store_energy,
+    ers_deploy_mode,
+    ers_harvested_this_lap_mguk,
+    ers_harvested_this_lap_mguh,
+    ers_deployed_this_lap,
+    network_paused,
+});
+
+columns!(CarMotionData {
+    world_position_x,
+    world_position_y,
+    world_position_z,
+    world_velocity_x,
+    world_velocity_y,
+    world_velocity_z,
+    world_forward_dir_x,
+    world_forward_dir_y,
+    world_forward_dir_z,
+    world_right_dir_x,
+    world_right_dir_y,
+    world_right_dir_z,
+    g_force_lateral,
+    g_force_longitudinal,
+    g_force_vertical,
+    yaw,
+    pitch,
+    roll,
+});
+
+columns!(LapData {
+    last_lap_time_in_ms,
+    current_lap_time_in_ms,
+    sector1_time_in_ms,
+    sector1_time_minutes,
+    sector2_time_in_ms,
+    sector2_time_minutes,
+    delta_to_car_in_front_in_ms,
+    delta_to_car_in_front_minutes,
+    delta_to_race_leader_in_ms,
+    delta_to_race_leader_minutes,
+    lap_distance,
+    total_distance,
+    safety_car_delta,
+    car_position,
+    current_lap_num,
+    pit_status,
+    num_pit_stops,
+    sector,
+    current_lap_invalid,
+    penalties,
+    total_warnings,
+    corner_cutting_warnings,
+    num_unserved_drive_through_pens,
+    num_unserved_stop_go_pens,
+    grid_position,
+    driver_status,
+    result_status,
+    pit_lane_timer_active,
+    pit_lane_time_in_lane_in_ms,
+    pit_stop_timer_in_ms,
+    pit_stop_should_serve_pen,
+    speed_trap_fastest_speed,
+    speed_trap_fastest_lap,
+});
+
+columns!(CarDamageData {
+    [tyres_wear],
+    [tyres_damage],
+    [brakes_damage],
+    front_left_wing_damage,
+    front_right_wing_damage,
+    rear_wing_damage,
+    floor_damage,
+    diffuser_damage,
+    sidepod_damage,
+    drs_fault,
+    ers_fault,
+    gearbox_damage,
+    engine_damage,
+    engine_mguh_wear,
+    engine_es_wear,
+    engine_ce_wear,
+    engine_ice_wear,
+    engine_mguk_wear,
+    engine_tc_wear,
+    engine_blown,
+    engine_seized,
+});
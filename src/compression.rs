@@ -0,0 +1,65 @@
+use crate::Error;
+use std::io::ErrorKind;
+
+// Each algorithm is only available when the cargo feature of the same name is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    #[cfg(feature = "zstd")]
+    Zstd,
+    #[cfg(feature = "lz4")]
+    Lz4,
+}
+
+impl Compression {
+    pub(crate) fn from_u8(value: u8) -> Result<Self, Error> {
+        match value {
+            0 => Ok(Compression::None),
+            #[cfg(feature = "zstd")]
+            1 => Ok(Compression::Zstd),
+            #[cfg(feature = "lz4")]
+            2 => Ok(Compression::Lz4),
+            _ => Err(Error::UnsupportedCaptureCompression(value)),
+        }
+    }
+
+    pub(crate) fn to_u8(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => 1,
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => 2,
+        }
+    }
+
+    pub(crate) fn compress(self, bytes: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+        match self {
+            Compression::None => Ok(bytes.to_vec()),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::bulk::compress(bytes, 0),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Ok(lz4_flex::block::compress(bytes)),
+        }
+    }
+
+    pub(crate) fn decompress(self, bytes: &[u8], length: usize) -> Result<Vec<u8>, std::io::Error> {
+        let decompressed: Vec<u8> = match self {
+            Compression::None => bytes.to_vec(),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::bulk::decompress(bytes, length)?,
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => lz4_flex::block::decompress(bytes, length)
+                .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?,
+        };
+        if decompressed.len() != length {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "compressed block has the wrong length",
+            ));
+        }
+
+        Ok(decompressed)
+    }
+}
@@ -0,0 +1,273 @@
+use crate::columns::{Columns, Value};
+use crate::packets::{
+    CarDamageData, CarMotionData, CarStatusData, CarTelemetryData, LapData, PacketHeader, NUM_CARS,
+};
+use crate::{Error, Packet, PacketSink};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Default)]
+struct Frame {
+    session_uid: u64,
+    session_time: f32,
+    frame_identifier: u32,
+    car_telemetry_data: Option<[CarTelemetryData; NUM_CARS]>,
+    car_status_data: Option<[CarStatusData; NUM_CARS]>,
+    car_motion_data: Option<[CarMotionData; NUM_CARS]>,
+    lap_data: Option<[LapData; NUM_CARS]>,
+    car_damage_data: Option<[CarDamageData; NUM_CARS]>,
+}
+
+// Writes one row per frame and car. Packet types that arrive less often than
+// every frame keep their last values until the session changes; columns of a
+// packet type that hasn't been seen yet in the session are left empty.
+pub struct CsvExporter<W: Write = BufWriter<File>> {
+    writers: Vec<W>,
+    frame: Option<Frame>,
+}
+
+impl CsvExporter {
+    pub fn create(directory: impl AsRef<Path>) -> Result<Self, Error> {
+        fs::create_dir_all(&directory)?;
+        let mut writers: Vec<BufWriter<File>> = Vec::with_capacity(NUM_CARS);
+        for car_index in 0..NUM_CARS {
+            let path = directory.as_ref().join(format!("car_{car_index:02}.csv"));
+            writers.push(BufWriter::new(File::create(path)?));
+        }
+        CsvExporter::new(writers)
+    }
+}
+
+impl<W: Write> CsvExporter<W> {
+    // One writer per car, by car index. Cars without a writer are skipped.
+    pub fn new(mut writers: Vec<W>) -> Result<Self, Error> {
+        writers.truncate(NUM_CARS);
+        let mut columns: Vec<String> = vec![
+            "session_uid".to_string(),
+            "session_time".to_string(),
+            "frame_identifier".to_string(),
+        ];
+        CarTelemetryData::names(&mut columns);
+        CarStatusData::names(&mut columns);
+        CarMotionData::names(&mut columns);
+        LapData::names(&mut columns);
+        CarDamageData::names(&mut columns);
+        let header: String = columns.join(",") + "\n";
+        for writer in &mut writers {
+            writer.write_all(header.as_bytes())?;
+        }
+
+        Ok(CsvExporter {
+            writers,
+            frame: None,
+        })
+    }
+
+    pub fn finish(mut self) -> Result<Vec<W>, Error> {
+        self.write_frame()?;
+        for writer in &mut self.writers {
+            writer.flush()?;
+        }
+
+        Ok(self.writers)
+    }
+
+    fn write_frame(&mut self) -> Result<(), Error> {
+        let Some(frame) = &self.frame else {
+            return Ok(());
+        };
+        for (car_index, writer) in self.writers.iter_mut().enumerate() {
+            let mut values: Vec<String> = vec![
+                frame.session_uid.to_string(),
+                frame.session_time.to_string(),
+                frame.frame_identifier.to_string(),
+            ];
+            values_or_empty(&mut values, frame.car_telemetry_data.as_ref(), car_index);
+            values_or_empty(&mut values, frame.car_status_data.as_ref(), car_index);
+            values_or_empty(&mut values, frame.car_motion_data.as_ref(), car_index);
+            values_or_empty(&mut values, frame.lap_data.as_ref(), car_index);
+            values_or_empty(&mut values, frame.car_damage_data.as_ref(), car_index);
+            writer.write_all((values.join(",") + "\n").as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> PacketSink for CsvExporter<W> {
+    fn push(&mut self, packet: &Packet) -> Result<(), Error> {
+        if !matches!(
+            packet,
+            Packet::CarTelemetry(_)
+                | Packet::CarStatus(_)
+                | Packet::Motion(_)
+                | Packet::Lap(_)
+                | Packet::CarDamage(_)
+        ) {
+            return Ok(());
+        }
+        let header: PacketHeader = packet.header();
+        match &self.frame {
+            Some(frame) if frame.session_uid != header.session_uid => {
+                self.write_frame()?;
+                self.frame = None;
+            }
+            Some(frame) if frame.frame_identifier != header.frame_identifier => {
+                self.write_frame()?;
+            }
+            _ => {}
+        }
+        let frame: &mut Frame = self.frame.get_or_insert_with(Frame::default);
+        frame.session_uid = header.session_uid;
+        frame.session_time = header.session_time;
+        frame.frame_identifier = header.frame_identifier;
+        match packet {
+            Packet::CarTelemetry(packet) => {
+                frame.car_telemetry_data = Some(packet.car_telemetry_data)
+            }
+            Packet::CarStatus(packet) => frame.car_status_data = Some(packet.car_status_data),
+            Packet::Motion(packet) => frame.car_motion_data = Some(packet.car_motion_data),
+            Packet::Lap(packet) => frame.lap_data = Some(packet.lap_data),
+            Packet::CarDamage(packet) => frame.car_damage_data = Some(packet.car_damage_data),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn values_or_empty<T: Columns>(
+    values: &mut Vec<String>,
+    cars: Option<&[T; NUM_CARS]>,
+    car_index: usize,
+) {
+    match cars {
+        Some(cars) => {
+            let mut row: Vec<Value> = Vec::new();
+            cars[car_index].values(&mut row);
+            values.extend(row.iter().map(Value::to_string));
+        }
+        None => {
+            let mut columns: Vec<String> = Vec::new();
+            T::names(&mut columns);
+            values.resize(values.len() + columns.len(), String::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{
+        PacketCarDamageData, PacketCarTelemetryData, PacketLapData, PacketSessionData,
+    };
+    use crate::test_support::header;
+    use crate::{CaptureReader, Recorder};
+
+    fn telemetry(session_uid: u64, frame_identifier: u32, speed: u16) -> Packet {
+        let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: header(6, session_uid, frame_identifier),
+            ..PacketCarTelemetryData::default()
+        };
+        packet.car_telemetry_data[1].speed = speed;
+        packet.car_telemetry_data[1].tyres_pressure = [21.0, 21.5, 23.0, 23.5];
+        Packet::CarTelemetry(packet)
+    }
+
+    fn lap(session_uid: u64, frame_identifier: u32, current_lap_num: u8) -> Packet {
+        let mut packet: PacketLapData = PacketLapData {
+            header: header(2, session_uid, frame_identifier),
+            ..PacketLapData::default()
+        };
+        packet.lap_data[1].current_lap_num = current_lap_num;
+        Packet::Lap(packet)
+    }
+
+    fn damage(session_uid: u64, frame_identifier: u32) -> Packet {
+        Packet::CarDamage(PacketCarDamageData {
+            header: header(10, session_uid, frame_identifier),
+            ..PacketCarDamageData::default()
+        })
+    }
+
+    fn rows(bytes: &[u8]) -> Vec<Vec<String>> {
+        String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| line.split(',').map(str::to_string).collect())
+            .collect()
+    }
+
+    fn column(rows: &[Vec<String>], name: &str) -> Vec<String> {
+        let index: usize = rows[0].iter().position(|column| column == name).unwrap();
+        rows[1..].iter().map(|row| row[index].clone()).collect()
+    }
+
+    #[test]
+    fn test_csv_exporter_writes_one_row_per_frame() {
+        let mut exporter: CsvExporter<Vec<u8>> =
+            CsvExporter::new(vec![Vec::new(), Vec::new()]).unwrap();
+        for packet in [
+            telemetry(7, 0, 100),
+            lap(7, 0, 1),
+            Packet::Session(PacketSessionData {
+                header: header(1, 7, 1),
+                ..PacketSessionData::default()
+            }),
+            telemetry(7, 1, 110),
+            damage(7, 1),
+            lap(7, 1, 2),
+            telemetry(8, 1, 50),
+        ] {
+            exporter.push(&packet).unwrap();
+        }
+        let writers: Vec<Vec<u8>> = exporter.finish().unwrap();
+
+        let car_rows: Vec<Vec<String>> = rows(&writers[1]);
+        assert_eq!(car_rows.len(), 4);
+        assert!(car_rows.iter().all(|row| row.len() == car_rows[0].len()));
+        assert_eq!(column(&car_rows, "session_uid"), vec!["7", "7", "8"]);
+        assert_eq!(column(&car_rows, "session_time"), vec!["0", "0.5", "0.5"]);
+        assert_eq!(column(&car_rows, "speed"), vec!["100", "110", "50"]);
+        assert_eq!(column(&car_rows, "current_lap_num"), vec!["1", "2", ""]);
+        assert_eq!(column(&car_rows, "engine_blown"), vec!["", "0", ""]);
+        assert_eq!(column(&car_rows, "fuel_in_tank"), vec!["", "", ""]);
+        assert_eq!(
+            column(&car_rows, "tyres_pressure_fl"),
+            vec!["23", "23", "23"]
+        );
+        assert_eq!(
+            column(&car_rows, "tyres_pressure_fr"),
+            vec!["23.5", "23.5", "23.5"]
+        );
+        assert_eq!(
+            column(&car_rows, "tyres_pressure_rl"),
+            vec!["21", "21", "21"]
+        );
+        assert_eq!(
+            column(&car_rows, "tyres_pressure_rr"),
+            vec!["21.5", "21.5", "21.5"]
+        );
+        assert_eq!(column(&rows(&writers[0]), "speed"), vec!["0", "0", "0"]);
+    }
+
+    #[test]
+    fn test_csv_exporter_reads_capture() {
+        let mut recorder: Recorder<Vec<u8>> = Recorder::new(Vec::new()).unwrap();
+        for packet in [telemetry(7, 0, 100), telemetry(7, 1, 110)] {
+            recorder.record(&packet.encode().unwrap()).unwrap();
+        }
+        recorder.record(&[0; 10]).unwrap();
+        let bytes: Vec<u8> = recorder.into_inner();
+        let mut exporter: CsvExporter<Vec<u8>> = CsvExporter::new(vec![Vec::new(); 22]).unwrap();
+
+        exporter
+            .push_capture(CaptureReader::new(bytes.as_slice()).unwrap())
+            .unwrap();
+        let writers: Vec<Vec<u8>> = exporter.finish().unwrap();
+
+        assert_eq!(writers.len(), 22);
+        assert_eq!(column(&rows(&writers[1]), "speed"), vec!["100", "110"]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Bind(std::io::Error),
+    Io(std::io::Error),
+    TruncatedPacket {
+        expected: usize,
+        actual: usize,
+    },
+    UnknownPacketId(u8),
+    UnsupportedPacketFormat(u16),
+    Decode {
+        packet_id: u8,
+        offset: usize,
+        source: std::io::Error,
+    },
+    InvalidCaptureMagic([u8; 4]),
+    UnsupportedCaptureVersion(u16),
+    UnsupportedCaptureCompression(u8),
+    InvalidPcapMagic([u8; 4]),
+    UnsupportedLinkType(u32),
+    InvalidReplaySpeed(f64),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::errors::ParquetError),
+    #[cfg(feature = "influxdb")]
+    Http(Box<ureq::Error>),
+    #[cfg(feature = "mqtt")]
+    Mqtt(Box<dyn std::error::Error + Send + Sync>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Bind(e) => write!(f, "couldn't bind to address: {e}"),
+            Error::Io(e) => write!(f, "I/O error: {e}"),
+            Error::TruncatedPacket { expected, actual } => {
+                write!(
+                    f,
+                    "truncated packet: expected {expected} bytes, got {actual}"
+                )
+            }
+            Error::UnknownPacketId(packet_id) => write!(f, "unknown packet id {packet_id}"),
+            Error::UnsupportedPacketFormat(packet_format) => {
+                write!(f, "unsupported packet format {packet_format}")
+            }
+            Error::Decode {
+                packet_id,
+                offset,
+                source,
+            } => write!(
+                f,
+                "failed to decode packet {packet_id} at byte offset {offset}: {source}"
+            ),
+            Error::InvalidCaptureMagic(magic) => {
+                write!(f, "not a capture file: bad magic {magic:?}")
+            }
+            Error::UnsupportedCaptureVersion(version) => {
+                write!(f, "unsupported capture version {version}")
+            }
+            Error::UnsupportedCaptureCompression(compression) => {
+                write!(f, "unsupported capture compression {compression}")
+            }
+            Error::InvalidPcapMagic(magic) => {
+                write!(f, "not a pcap or pcapng file: bad magic {magic:?}")
+            }
+            Error::UnsupportedLinkType(link_type) => {
+                write!(f, "unsupported pcap link type {link_type}")
+            }
+            Error::InvalidReplaySpeed(multiplier) => write!(f, "invalid replay speed {multiplier}"),
+            #[cfg(feature = "parquet")]
+            Error::Parquet(e) => write!(f, "parquet error: {e}"),
+            #[cfg(feature = "influxdb")]
+            Error::Http(e) => write!(f, "HTTP error: {e}"),
+            #[cfg(feature = "mqtt")]
+            Error::Mqtt(e) => write!(f, "MQTT error: {e}"),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(e) => write!(f, "SQLite error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Bind(e) | Error::Io(e) => Some(e),
+            Error::Decode { source, .. } => Some(source),
+            #[cfg(feature = "parquet")]
+            Error::Parquet(e) => Some(e),
+            #[cfg(feature = "influxdb")]
+            Error::Http(e) => Some(e.as_ref()),
+            #[cfg(feature = "mqtt")]
+            Error::Mqtt(e) => Some(e.as_ref()),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        Error::Parquet(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        Error::Parquet(e.into())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sqlite(e)
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{is_timeout, set_read_timeout, Error, ShutdownHandle, POLL_INTERVAL};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ForwardStats {
+    pub forwarded: u64,
+    // Datagrams left out by the target's packet id filter.
+    pub filtered: u64,
+    // Datagrams that couldn't be sent, e.g. because the socket's send buffer
+    // was full or the target isn't listening.
+    pub dropped: u64,
+}
+
+struct Target {
+    socket: UdpSocket,
+    address: SocketAddr,
+    packet_ids: Option<Vec<u8>>,
+    stats: ForwardStats,
+}
+
+// Receives datagrams and sends each one on, as is, to every target. Datagrams
+// aren't decoded; only the packet id is read from the header, for targets that
+// filter on it. Sends don't block, so a slow target only drops its own
+// datagrams.
+pub struct Forwarder {
+    socket: UdpSocket,
+    read_timeout: Option<Duration>,
+    buf: [u8; 2048],
+    targets: Vec<Target>,
+    shutdown_handle: ShutdownHandle,
+}
+
+impl Forwarder {
+    pub fn new(bind_address: &str) -> Result<Self, Error> {
+        let socket: UdpSocket = UdpSocket::bind(bind_address).map_err(Error::Bind)?;
+
+        Ok(Forwarder {
+            socket,
+            read_timeout: None,
+            buf: [0; 2048],
+            targets: Vec::new(),
+            shutdown_handle: ShutdownHandle::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    // Forwards datagrams to `address`, only those with one of `packet_ids` if
+    // given.
+    pub fn add_target(
+        &mut self,
+        address: impl ToSocketAddrs,
+        packet_ids: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let address: SocketAddr = address.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(ErrorKind::InvalidInput, "no address to forward to")
+        })?;
+        let bind_address: &str = if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket: UdpSocket = UdpSocket::bind(bind_address).map_err(Error::Bind)?;
+        socket.connect(address)?;
+        socket.set_nonblocking(true)?;
+        self.targets.push(Target {
+            socket,
+            address,
+            packet_ids: packet_ids.map(<[u8]>::to_vec),
+            stats: ForwardStats::default(),
+        });
+
+        Ok(())
+    }
+
+    pub fn stats(&self) -> Vec<(SocketAddr, ForwardStats)> {
+        self.targets
+            .iter()
+            .map(|target| (target.address, target.stats))
+            .collect()
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown_handle.clone()
+    }
+
+    pub fn run(&mut self) -> Result<(), Error> {
+        while !self.shutdown_handle.is_shutdown() {
+            self.poll_once(POLL_INTERVAL)?;
+        }
+
+        Ok(())
+    }
+
+    // Waits up to `timeout` for a datagram and forwards it. Returns whether a
+    // datagram was received.
+    pub fn poll_once(&mut self, timeout: Duration) -> Result<bool, Error> {
+        set_read_timeout(&self.socket, &mut self.read_timeout, timeout)?;
+        let received: usize = match self.socket.recv(&mut self.buf) {
+            Ok(received) => received,
+            Err(e) if is_timeout(&e) => return Ok(false),
+            Err(e) => return Err(Error::Io(e)),
+        };
+        let bytes: &[u8] = &self.buf[..received];
+        let packet_id: Option<u8> = packet_id(bytes);
+        for target in &mut self.targets {
+            if let Some(packet_ids) = &target.packet_ids {
+                if !packet_id.is_some_and(|packet_id| packet_ids.contains(&packet_id)) {
+                    target.stats.filtered += 1;
+                    continue;
+                }
+            }
+            match target.socket.send(bytes) {
+                Ok(_) => target.stats.forwarded += 1,
+                Err(_) => target.stats.dropped += 1,
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+// The packet id is the seventh byte of the header, or the sixth before the
+// game year was added to it in the 2023 format.
+fn packet_id(bytes: &[u8]) -> Option<u8> {
+    let packet_format: u16 = u16::from_le_bytes([*bytes.first()?, *bytes.get(1)?]);
+    match packet_format {
+        2021 | 2022 => bytes.get(5).copied(),
+        _ => bytes.get(6).copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{v2022, PacketHeader};
+    use std::thread;
+
+    fn datagram(packet_id: u8) -> Vec<u8> {
+        PacketHeader {
+            packet_format: 2024,
+            packet_id,
+            ..PacketHeader::default()
+        }
+        .serialize()
+        .unwrap()
+    }
+
+    fn target() -> UdpSocket {
+        let socket: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        socket
+    }
+
+    #[test]
+    fn test_packet_id() {
+        let legacy: Vec<u8> = v2022::PacketHeader {
+            packet_format: 2022,
+            packet_id: 7,
+            ..v2022::PacketHeader::default()
+        }
+        .serialize()
+        .unwrap();
+
+        assert_eq!(packet_id(&datagram(6)), Some(6));
+        assert_eq!(packet_id(&legacy), Some(7));
+        assert_eq!(packet_id(&[0xe8, 0x07]), None);
+    }
+
+    #[test]
+    fn test_forwarder_forwards_to_targets() {
+        let mut forwarder: Forwarder = Forwarder::new("127.0.0.1:0").unwrap();
+        let (all, telemetry): (UdpSocket, UdpSocket) = (target(), target());
+        forwarder
+            .add_target(all.local_addr().unwrap(), None)
+            .unwrap();
+        forwarder
+            .add_target(telemetry.local_addr().unwrap(), Some(&[6]))
+            .unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        for packet_id in [2, 6] {
+            sender
+                .send_to(&datagram(packet_id), forwarder.local_addr().unwrap())
+                .unwrap();
+            assert!(forwarder.poll_once(Duration::from_secs(5)).unwrap());
+        }
+        sender
+            .send_to(&[1, 2, 3], forwarder.local_addr().unwrap())
+            .unwrap();
+        assert!(forwarder.poll_once(Duration::from_secs(5)).unwrap());
+        assert!(!forwarder.poll_once(Duration::from_millis(10)).unwrap());
+
+        let mut buf: [u8; 64] = [0; 64];
+        for expected in [datagram(2), datagram(6), vec![1, 2, 3]] {
+            let received: usize = all.recv(&mut buf).unwrap();
+            assert_eq!(&buf[..received], expected);
+        }
+        let received: usize = telemetry.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..received], datagram(6));
+        assert_eq!(
+            forwarder.stats(),
+            vec![
+                (
+                    all.local_addr().unwrap(),
+                    ForwardStats {
+                        forwarded: 3,
+                        filtered: 0,
+                        dropped: 0
+                    }
+                ),
+                (
+                    telemetry.local_addr().unwrap(),
+                    ForwardStats {
+                        forwarded: 1,
+                        filtered: 2,
+                        dropped: 0
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forwarder_counts_drops_per_target() {
+        let mut forwarder: Forwarder = Forwarder::new("127.0.0.1:0").unwrap();
+        let listening: UdpSocket = target();
+        let closed: SocketAddr = UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        forwarder.add_target(closed, None).unwrap();
+        forwarder
+            .add_target(listening.local_addr().unwrap(), None)
+            .unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        // The target's refusal is reported by the send after the refused one.
+        for _ in 0..3 {
+            sender
+                .send_to(&datagram(6), forwarder.local_addr().unwrap())
+                .unwrap();
+            assert!(forwarder.poll_once(Duration::from_secs(5)).unwrap());
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        let stats: Vec<(SocketAddr, ForwardStats)> = forwarder.stats();
+        assert!(stats[0].1.dropped > 0);
+        assert_eq!(stats[0].1.forwarded + stats[0].1.dropped, 3);
+        assert_eq!(stats[1].1.forwarded, 3);
+        assert_eq!(stats[1].1.dropped, 0);
+    }
+}
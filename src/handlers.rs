@@ -0,0 +1,138 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+type Handler<T> = Box<dyn FnMut(&T)>;
+
+pub(crate) struct Handlers<T> {
+    handlers: Vec<(SubscriptionId, Handler<T>)>,
+}
+
+impl<T> Handlers<T> {
+    pub(crate) fn new() -> Self {
+        Handlers {
+            handlers: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, id: SubscriptionId, handler: Handler<T>) {
+        self.handlers.push((id, handler));
+    }
+
+    pub(crate) fn set(&mut self, id: SubscriptionId, handler: Handler<T>) {
+        self.handlers.clear();
+        self.handlers.push((id, handler));
+    }
+
+    pub(crate) fn remove(&mut self, id: SubscriptionId) -> bool {
+        let len: usize = self.handlers.len();
+        self.handlers.retain(|(handler_id, _)| *handler_id != id);
+        self.handlers.len() != len
+    }
+
+    pub(crate) fn call(&mut self, value: &T) {
+        for (_, handler) in self.handlers.iter_mut() {
+            handler(value);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SubscriptionIds {
+    next: u64,
+}
+
+impl SubscriptionIds {
+    pub(crate) fn next(&mut self) -> SubscriptionId {
+        let id: SubscriptionId = SubscriptionId(self.next);
+        self.next += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_handlers_call_in_registration_order() {
+        let calls: Arc<Mutex<Vec<(u8, u32)>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut ids: SubscriptionIds = SubscriptionIds::default();
+        let mut handlers: Handlers<u32> = Handlers::new();
+        for n in 0..3 {
+            let calls: Arc<Mutex<Vec<(u8, u32)>>> = Arc::clone(&calls);
+            handlers.add(
+                ids.next(),
+                Box::new(move |value: &u32| calls.lock().unwrap().push((n, *value))),
+            );
+        }
+
+        handlers.call(&7);
+
+        assert_eq!(*calls.lock().unwrap(), vec![(0, 7), (1, 7), (2, 7)]);
+    }
+
+    #[test]
+    fn test_handlers_keep_state_between_calls() {
+        let total: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+        let mut handlers: Handlers<u32> = Handlers::new();
+        let mut sum: u32 = 0;
+        let shared: Arc<Mutex<u32>> = Arc::clone(&total);
+        handlers.add(
+            SubscriptionIds::default().next(),
+            Box::new(move |value: &u32| {
+                sum += value;
+                *shared.lock().unwrap() = sum;
+            }),
+        );
+
+        handlers.call(&1);
+        handlers.call(&2);
+        handlers.call(&3);
+
+        assert_eq!(*total.lock().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_handlers_remove() {
+        let calls: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut ids: SubscriptionIds = SubscriptionIds::default();
+        let mut handlers: Handlers<u32> = Handlers::new();
+        let mut subscriptions: Vec<SubscriptionId> = Vec::new();
+        for n in 0..3 {
+            let calls: Arc<Mutex<Vec<u8>>> = Arc::clone(&calls);
+            let id: SubscriptionId = ids.next();
+            handlers.add(id, Box::new(move |_: &u32| calls.lock().unwrap().push(n)));
+            subscriptions.push(id);
+        }
+
+        assert!(handlers.remove(subscriptions[1]));
+        assert!(!handlers.remove(subscriptions[1]));
+        handlers.call(&0);
+
+        assert_eq!(*calls.lock().unwrap(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_handlers_set_replaces_subscribers() {
+        let calls: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut ids: SubscriptionIds = SubscriptionIds::default();
+        let mut handlers: Handlers<u32> = Handlers::new();
+        for n in 0..2 {
+            let calls: Arc<Mutex<Vec<u8>>> = Arc::clone(&calls);
+            handlers.add(
+                ids.next(),
+                Box::new(move |_: &u32| calls.lock().unwrap().push(n)),
+            );
+        }
+        let shared: Arc<Mutex<Vec<u8>>> = Arc::clone(&calls);
+        handlers.set(
+            ids.next(),
+            Box::new(move |_: &u32| shared.lock().unwrap().push(9)),
+        );
+
+        handlers.call(&0);
+
+        assert_eq!(*calls.lock().unwrap(), vec![9]);
+    }
+}
@@ -0,0 +1,378 @@
+use crate::packets::{followed_lap_data, v2022, PacketHeader};
+use crate::Packet;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+// Index block, appended after the last record when a recording is finished:
+//   marker: [u8; 12]        all 0xff, where the next record would start
+//   count: u64              number of entries, little endian
+//   entries: [entry; count] one per record, in file order
+//   offset: u64             file offset of the marker, little endian
+//   magic: [u8; 4]          "F1TX"
+pub(crate) const INDEX_MARKER: [u8; 12] = [0xff; 12];
+pub(crate) const INDEX_MAGIC: [u8; 4] = *b"F1TX";
+pub(crate) const INDEX_TRAILER_SIZE: u64 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureIndexEntry {
+    pub offset: u64,
+    pub session_uid: u64,
+    pub session_time: f32,
+    pub frame_identifier: u32,
+    pub packet_id: u8,
+    pub lap_num: u8,
+    pub sector: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptureIndex {
+    entries: Vec<CaptureIndexEntry>,
+}
+
+impl CaptureIndexEntry {
+    pub fn unserialize(reader: &mut impl Read) -> Result<Self, std::io::Error> {
+        Ok(CaptureIndexEntry {
+            offset: reader.read_u64::<LittleEndian>()?,
+            session_uid: reader.read_u64::<LittleEndian>()?,
+            session_time: reader.read_f32::<LittleEndian>()?,
+            frame_identifier: reader.read_u32::<LittleEndian>()?,
+            packet_id: reader.read_u8()?,
+            lap_num: reader.read_u8()?,
+            sector: reader.read_u8()?,
+        })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(27);
+        bytes.write_u64::<LittleEndian>(self.offset)?;
+        bytes.write_u64::<LittleEndian>(self.session_uid)?;
+        bytes.write_f32::<LittleEndian>(self.session_time)?;
+        bytes.write_u32::<LittleEndian>(self.frame_identifier)?;
+        bytes.write_u8(self.packet_id)?;
+        bytes.write_u8(self.lap_num)?;
+        bytes.write_u8(self.sector)?;
+
+        Ok(bytes)
+    }
+}
+
+impl CaptureIndex {
+    pub fn entries(&self) -> &[CaptureIndexEntry] {
+        &self.entries
+    }
+
+    pub fn session_uids(&self) -> Vec<u64> {
+        let mut session_uids: Vec<u64> = Vec::new();
+        for entry in &self.entries {
+            if !session_uids.contains(&entry.session_uid) {
+                session_uids.push(entry.session_uid);
+            }
+        }
+        session_uids
+    }
+
+    pub fn session(&self, session_uid: u64) -> CaptureIndex {
+        CaptureIndex {
+            entries: self
+                .entries
+                .iter()
+                .filter(|entry| entry.session_uid == session_uid)
+                .copied()
+                .collect(),
+        }
+    }
+
+    pub fn find_session_time(&self, session_time: f32) -> Option<&CaptureIndexEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.session_time >= session_time)
+    }
+
+    pub fn find_frame(&self, frame_identifier: u32) -> Option<&CaptureIndexEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.frame_identifier >= frame_identifier)
+    }
+
+    pub fn find_packet(&self, packet_id: u8) -> Option<&CaptureIndexEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.packet_id == packet_id)
+    }
+
+    // `sector` counts from zero, as in `LapData::sector`.
+    pub fn find_lap(&self, lap_num: u8, sector: u8) -> Option<&CaptureIndexEntry> {
+        self.entries
+            .iter()
+            .find(|entry| (entry.lap_num, entry.sector) >= (lap_num, sector))
+    }
+
+    pub(crate) fn unserialize(reader: &mut impl Read) -> Result<Self, std::io::Error> {
+        let count: u64 = reader.read_u64::<LittleEndian>()?;
+        let mut entries: Vec<CaptureIndexEntry> = Vec::new();
+        for _ in 0..count {
+            entries.push(CaptureIndexEntry::unserialize(reader)?);
+        }
+
+        Ok(CaptureIndex { entries })
+    }
+
+    pub(crate) fn serialize(&self, offset: u64) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(32 + 27 * self.entries.len());
+        bytes.write_all(&INDEX_MARKER)?;
+        bytes.write_u64::<LittleEndian>(self.entries.len() as u64)?;
+        for entry in &self.entries {
+            bytes.write_all(&entry.serialize()?)?;
+        }
+        bytes.write_u64::<LittleEndian>(offset)?;
+        bytes.write_all(&INDEX_MAGIC)?;
+
+        Ok(bytes)
+    }
+}
+
+// Builds index entries record by record, carrying the player's lap and sector,
+// or the race leader's when spectating, forward from the last lap packet of the
+// same session.
+#[derive(Debug, Default)]
+pub(crate) struct Indexer {
+    index: CaptureIndex,
+    session_uid: u64,
+    lap_num: u8,
+    sector: u8,
+}
+
+impl Indexer {
+    pub(crate) fn push(&mut self, offset: u64, bytes: &[u8]) {
+        let Ok(header) = PacketHeader::unserialize(bytes) else {
+            return;
+        };
+        let (session_uid, session_time, frame_identifier, packet_id) = match header.packet_format {
+            2021 | 2022 => match v2022::PacketHeader::unserialize(bytes) {
+                Ok(header) => (
+                    header.session_uid,
+                    header.session_time,
+                    header.frame_identifier,
+                    header.packet_id,
+                ),
+                Err(_) => return,
+            },
+            _ => (
+                header.session_uid,
+                header.session_time,
+                header.frame_identifier,
+                header.packet_id,
+            ),
+        };
+        if session_uid != self.session_uid {
+            self.session_uid = session_uid;
+            self.lap_num = 0;
+            self.sector = 0;
+        }
+        // Only lap packets carry the lap and sector, so the rest are not decoded.
+        if packet_id == 2 {
+            if let Ok(Packet::Lap(packet)) = Packet::decode(bytes) {
+                if let Some(lap_data) = followed_lap_data(&packet) {
+                    self.lap_num = lap_data.current_lap_num;
+                    self.sector = lap_data.sector;
+                }
+            }
+        }
+
+        self.index.entries.push(CaptureIndexEntry {
+            offset,
+            session_uid,
+            session_time,
+            frame_identifier,
+            packet_id,
+            lap_num: self.lap_num,
+            sector: self.sector,
+        });
+    }
+
+    pub(crate) fn into_index(self) -> CaptureIndex {
+        self.index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{LapData, PacketLapData, PacketSessionData};
+    use crate::test_support::header;
+    use crate::{CaptureReader, CaptureRecord, Compression, Recorder};
+    use rand::Rng;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn lap_packet(
+        session_uid: u64,
+        frame_identifier: u32,
+        current_lap_num: u8,
+        sector: u8,
+    ) -> Vec<u8> {
+        let mut packet: PacketLapData = PacketLapData {
+            header: header(2, session_uid, frame_identifier),
+            ..PacketLapData::default()
+        };
+        packet.lap_data[1] = LapData {
+            current_lap_num,
+            sector,
+            ..LapData::default()
+        };
+        Packet::Lap(packet).encode().unwrap()
+    }
+
+    fn session_packet(session_uid: u64, frame_identifier: u32) -> Vec<u8> {
+        Packet::Session(PacketSessionData {
+            header: header(1, session_uid, frame_identifier),
+            ..PacketSessionData::default()
+        })
+        .encode()
+        .unwrap()
+    }
+
+    fn recording(compression: Compression) -> Recorder<Vec<u8>> {
+        let mut recorder: Recorder<Vec<u8>> =
+            Recorder::with_compression(Vec::new(), compression).unwrap();
+        let packets: Vec<Vec<u8>> = vec![
+            lap_packet(7, 0, 1, 0),
+            session_packet(7, 0),
+            lap_packet(7, 1, 1, 1),
+            lap_packet(7, 2, 2, 0),
+            session_packet(7, 2),
+            lap_packet(7, 3, 2, 1),
+            session_packet(8, 0),
+            lap_packet(8, 1, 1, 0),
+        ];
+        for (time, bytes) in packets.iter().enumerate() {
+            recorder
+                .record_at(Duration::from_millis(time as u64), bytes)
+                .unwrap();
+        }
+        recorder
+    }
+
+    fn frames(reader: &mut CaptureReader<Cursor<Vec<u8>>>) -> Vec<(u64, u32)> {
+        reader
+            .map(|record| {
+                let header: PacketHeader =
+                    PacketHeader::unserialize(&record.unwrap().bytes).unwrap();
+                (header.session_uid, header.frame_identifier)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_capture_index_entry_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_entry: CaptureIndexEntry = CaptureIndexEntry {
+            offset: rng.gen(),
+            session_uid: rng.gen(),
+            session_time: rng.gen(),
+            frame_identifier: rng.gen(),
+            packet_id: rng.gen(),
+            lap_num: rng.gen(),
+            sector: rng.gen(),
+        };
+
+        let serialized_entry: Vec<u8> = original_entry.serialize().unwrap();
+        let deserialized_entry: CaptureIndexEntry =
+            CaptureIndexEntry::unserialize(&mut serialized_entry.as_slice()).unwrap();
+
+        assert_eq!(serialized_entry.len(), 27);
+        assert_eq!(original_entry, deserialized_entry);
+    }
+
+    fn assert_index_finds_laps_frames_and_packets(compression: Compression) {
+        let bytes: Vec<u8> = recording(compression).finish().unwrap();
+        let mut reader: CaptureReader<Cursor<Vec<u8>>> =
+            CaptureReader::new(Cursor::new(bytes)).unwrap();
+
+        let index: CaptureIndex = reader.index().unwrap();
+
+        assert_eq!(index.entries().len(), 8);
+        assert_eq!(index.session_uids(), vec![7, 8]);
+        let entry: CaptureIndexEntry = *index.session(7).find_lap(2, 1).unwrap();
+        assert_eq!((entry.frame_identifier, entry.packet_id), (3, 2));
+        reader.seek_to(&entry).unwrap();
+        assert_eq!(frames(&mut reader), vec![(7, 3), (8, 0), (8, 1)]);
+
+        let entry: CaptureIndexEntry = *index.session(7).find_frame(2).unwrap();
+        assert_eq!((entry.lap_num, entry.sector), (2, 0));
+        reader.seek_to(&entry).unwrap();
+        assert_eq!(
+            frames(&mut reader),
+            vec![(7, 2), (7, 2), (7, 3), (8, 0), (8, 1)]
+        );
+
+        let entry: CaptureIndexEntry = *index.session(8).find_packet(1).unwrap();
+        assert_eq!((entry.session_uid, entry.lap_num), (8, 0));
+        assert_eq!(index.find_session_time(0.75).unwrap().frame_identifier, 2);
+        assert!(index.session(8).find_lap(2, 0).is_none());
+    }
+
+    #[test]
+    fn test_capture_index_finds_laps_frames_and_packets() {
+        assert_index_finds_laps_frames_and_packets(Compression::None);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_capture_index_finds_laps_frames_and_packets_with_zstd() {
+        assert_index_finds_laps_frames_and_packets(Compression::Zstd);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_capture_index_finds_laps_frames_and_packets_with_lz4() {
+        assert_index_finds_laps_frames_and_packets(Compression::Lz4);
+    }
+
+    #[test]
+    fn test_capture_index_follows_race_leader_when_spectating() {
+        let mut indexer: Indexer = Indexer::default();
+        for (frame_identifier, current_lap_num) in [(0, 1), (1, 2)] {
+            let mut packet: PacketLapData = PacketLapData {
+                header: PacketHeader {
+                    player_car_index: 255,
+                    ..header(2, 7, frame_identifier)
+                },
+                ..PacketLapData::default()
+            };
+            packet.lap_data[4] = LapData {
+                car_position: 1,
+                current_lap_num,
+                sector: 2,
+                ..LapData::default()
+            };
+            indexer.push(
+                frame_identifier as u64,
+                &Packet::Lap(packet).encode().unwrap(),
+            );
+        }
+
+        let index: CaptureIndex = indexer.into_index();
+
+        assert_eq!(index.find_lap(2, 2).unwrap().frame_identifier, 1);
+    }
+
+    #[test]
+    fn test_capture_index_of_unfinished_recording_matches_index_block() {
+        let finished: Vec<u8> = recording(Compression::None).finish().unwrap();
+        let unfinished: Vec<u8> = recording(Compression::None).into_inner();
+        assert!(finished.len() > unfinished.len());
+
+        let mut finished: CaptureReader<Cursor<Vec<u8>>> =
+            CaptureReader::new(Cursor::new(finished)).unwrap();
+        let mut unfinished: CaptureReader<Cursor<Vec<u8>>> =
+            CaptureReader::new(Cursor::new(unfinished)).unwrap();
+        let first: Option<CaptureRecord> = unfinished.read_record().unwrap();
+
+        assert_eq!(finished.index().unwrap(), unfinished.index().unwrap());
+        assert_eq!(frames(&mut finished).len(), 8);
+        assert_eq!(frames(&mut unfinished).len(), 7);
+        assert!(first.is_some());
+    }
+}
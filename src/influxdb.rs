@@ -0,0 +1,482 @@
+use crate::columns::{Columns, Value};
+use crate::packets::{nul_terminated, PacketHeader, PacketParticipantsData, NUM_CARS};
+use crate::{Error, Packet};
+use std::fmt::Write as _;
+use std::io::ErrorKind;
+use std::mem;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_BATCH_SIZE: usize = 5000;
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+// Batches waiting to be written. Once the queue is full, `push` blocks until
+// the server catches up.
+const QUEUE_CAPACITY: usize = 16;
+const MAX_DATAGRAM_SIZE: usize = 1400;
+// A server that hangs would otherwise hold up the background thread for good,
+// and with it `push` once the queue is full.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+enum Transport {
+    Http {
+        agent: ureq::Agent,
+        url: String,
+        token: Option<String>,
+    },
+    Udp(UdpSocket),
+}
+
+impl Transport {
+    fn send(&self, batch: &str) -> Result<(), Error> {
+        match self {
+            Transport::Http { agent, url, token } => {
+                let mut request: ureq::Request = agent
+                    .post(url)
+                    .set("Content-Type", "text/plain; charset=utf-8");
+                if let Some(token) = token {
+                    request = request.set("Authorization", &format!("Token {token}"));
+                }
+                request
+                    .send_string(batch)
+                    .map_err(|e| Error::Http(Box::new(e)))?;
+            }
+            Transport::Udp(socket) => {
+                // Split on line boundaries so that every datagram parses on its own.
+                let (mut start, mut end): (usize, usize) = (0, 0);
+                for line in batch.split_inclusive('\n') {
+                    if end > start && end - start + line.len() > MAX_DATAGRAM_SIZE {
+                        socket.send(&batch.as_bytes()[start..end])?;
+                        start = end;
+                    }
+                    end += line.len();
+                }
+                if end > start {
+                    socket.send(&batch.as_bytes()[start..end])?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+}
+
+#[derive(Clone, Default)]
+struct Participant {
+    driver: Option<String>,
+    team_id: u8,
+}
+
+// Writes decoded packets to InfluxDB as line protocol, with one measurement per
+// packet type and one point per car. Points are tagged with the session UID,
+// the car index and, once a participants packet has been seen in the session,
+// the driver name and team id. Only the per-car telemetry, status, motion, lap
+// and damage packets are written; session, event and the other packets aren't.
+//
+// Lines are batched and written from a background thread. Errors are returned
+// by the next call to `push`, `flush` or `finish`.
+pub struct InfluxWriter {
+    sender: Option<SyncSender<String>>,
+    errors: Receiver<Error>,
+    thread: Option<JoinHandle<()>>,
+    batch: String,
+    lines: usize,
+    batch_size: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+    session_uid: u64,
+    num_active_cars: usize,
+    participants: Vec<Participant>,
+}
+
+impl InfluxWriter {
+    // The URL of the write endpoint, e.g.
+    // `http://localhost:8086/api/v2/write?org=f1&bucket=telemetry&precision=ns`
+    // for InfluxDB 2 or `http://localhost:8086/write?db=telemetry` for InfluxDB 1.
+    pub fn http(url: &str) -> Self {
+        InfluxWriter::new(Transport::Http {
+            agent: agent(),
+            url: url.to_string(),
+            token: None,
+        })
+    }
+
+    pub fn http_with_token(url: &str, token: &str) -> Self {
+        InfluxWriter::new(Transport::Http {
+            agent: agent(),
+            url: url.to_string(),
+            token: Some(token.to_string()),
+        })
+    }
+
+    pub fn udp(address: impl ToSocketAddrs) -> Result<Self, Error> {
+        let address: SocketAddr = address.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(ErrorKind::InvalidInput, "no address to write to")
+        })?;
+        let bind_address: &str = if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket: UdpSocket = UdpSocket::bind(bind_address).map_err(Error::Bind)?;
+        socket.connect(address)?;
+        Ok(InfluxWriter::new(Transport::Udp(socket)))
+    }
+
+    fn new(transport: Transport) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<String>(QUEUE_CAPACITY);
+        let (error_sender, errors) = mpsc::channel::<Error>();
+        let thread: JoinHandle<()> = thread::spawn(move || {
+            for batch in receiver {
+                if let Err(e) = transport.send(&batch) {
+                    let _ = error_sender.send(e);
+                }
+            }
+        });
+
+        InfluxWriter {
+            sender: Some(sender),
+            errors,
+            thread: Some(thread),
+            batch: String::new(),
+            lines: 0,
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            last_flush: Instant::now(),
+            session_uid: 0,
+            num_active_cars: NUM_CARS,
+            participants: vec![Participant::default(); NUM_CARS],
+        }
+    }
+
+    // The number of lines after which a batch is written.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.max(1);
+    }
+
+    // The time after which a batch is written, however few lines it holds.
+    pub fn set_flush_interval(&mut self, flush_interval: Duration) {
+        self.flush_interval = flush_interval;
+    }
+
+    pub fn push(&mut self, packet: &Packet) -> Result<(), Error> {
+        self.push_at(packet, SystemTime::now())
+    }
+
+    pub fn push_at(&mut self, packet: &Packet, time: SystemTime) -> Result<(), Error> {
+        let header: PacketHeader = packet.header();
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.num_active_cars = NUM_CARS;
+            self.participants = vec![Participant::default(); NUM_CARS];
+        }
+        let timestamp: u128 = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        match packet {
+            Packet::Participants(packet) => self.set_participants(packet),
+            Packet::CarTelemetry(packet) => self.write_lines(
+                "car_telemetry",
+                &header,
+                &packet.car_telemetry_data,
+                timestamp,
+            ),
+            Packet::CarStatus(packet) => {
+                self.write_lines("car_status", &header, &packet.car_status_data, timestamp)
+            }
+            Packet::Motion(packet) => {
+                self.write_lines("car_motion", &header, &packet.car_motion_data, timestamp)
+            }
+            Packet::Lap(packet) => self.write_lines("lap", &header, &packet.lap_data, timestamp),
+            Packet::CarDamage(packet) => {
+                self.write_lines("car_damage", &header, &packet.car_damage_data, timestamp)
+            }
+            _ => {}
+        }
+        if self.lines >= self.batch_size || self.last_flush.elapsed() >= self.flush_interval {
+            self.flush()?;
+        }
+
+        self.check_errors()
+    }
+
+    // Queues the pending lines, blocking while the queue is full.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.last_flush = Instant::now();
+        if self.lines > 0 {
+            self.lines = 0;
+            if let Some(sender) = &self.sender {
+                // The thread only ends once the sender is dropped.
+                let _ = sender.send(mem::take(&mut self.batch));
+            }
+        }
+
+        self.check_errors()
+    }
+
+    // Writes the pending lines and waits for every batch to be written.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.flush()?;
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+
+        self.check_errors()
+    }
+
+    fn check_errors(&self) -> Result<(), Error> {
+        match self.errors.try_recv() {
+            Ok(e) => Err(e),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn set_participants(&mut self, packet: &PacketParticipantsData) {
+        self.num_active_cars = (packet.num_active_cars as usize).min(NUM_CARS);
+        for (participant, participant_data) in self.participants.iter_mut().zip(packet.participants)
+        {
+            let name: String = nul_terminated(&participant_data.name);
+            participant.driver = (!name.is_empty()).then_some(name);
+            participant.team_id = participant_data.team_id;
+        }
+    }
+
+    fn write_lines<T: Columns>(
+        &mut self,
+        measurement: &str,
+        header: &PacketHeader,
+        cars: &[T],
+        timestamp: u128,
+    ) {
+        let mut names: Vec<String> = Vec::new();
+        T::names(&mut names);
+        for (car_index, car) in cars.iter().enumerate().take(self.num_active_cars) {
+            let batch: &mut String = &mut self.batch;
+            let _ = write!(
+                batch,
+                "{measurement},session_uid={},car_index={car_index}",
+                { header.session_uid }
+            );
+            let participant: &Participant = &self.participants[car_index];
+            if let Some(driver) = &participant.driver {
+                batch.push_str(",driver=");
+                escape_tag(batch, driver);
+                let _ = write!(batch, ",team_id={}", participant.team_id);
+            }
+            let _ = write!(
+                batch,
+                " session_time={},frame_identifier={}i",
+                { header.session_time },
+                { header.frame_identifier }
+            );
+            let mut values: Vec<Value> = Vec::new();
+            car.values(&mut values);
+            for (name, value) in names.iter().zip(values) {
+                match value {
+                    Value::F32(value) if value.is_finite() => {
+                        let _ = write!(batch, ",{name}={value}");
+                    }
+                    Value::F32(_) => {}
+                    value => {
+                        let _ = write!(batch, ",{name}={value}i");
+                    }
+                }
+            }
+            let _ = writeln!(batch, " {timestamp}");
+            self.lines += 1;
+        }
+    }
+}
+
+impl Drop for InfluxWriter {
+    // Batches already queued are still written by the background thread.
+    fn drop(&mut self) {
+        if self.lines > 0 {
+            let _ = self.flush();
+        }
+    }
+}
+
+fn escape_tag(line: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ' | '\\') {
+            line.push('\\');
+        }
+        line.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{PacketCarTelemetryData, PacketParticipantsData};
+    use crate::test_support::header;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    fn telemetry(frame_identifier: u32, speed: u16) -> Packet {
+        let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: header(6, 7, frame_identifier),
+            ..PacketCarTelemetryData::default()
+        };
+        packet.car_telemetry_data[1].speed = speed;
+        packet.car_telemetry_data[1].tyres_pressure = [21.0, 21.5, 23.0, 23.5];
+        Packet::CarTelemetry(packet)
+    }
+
+    fn participants() -> Packet {
+        let mut packet: PacketParticipantsData = PacketParticipantsData {
+            header: header(4, 7, 0),
+            num_active_cars: 2,
+            ..PacketParticipantsData::default()
+        };
+        packet.participants[1].name[..11].copy_from_slice(b"Max, Driver");
+        packet.participants[1].team_id = 2;
+        Packet::Participants(packet)
+    }
+
+    // Accepts `requests` requests, answers each with `status` and returns the
+    // request heads and bodies.
+    fn mock_server(status: &str, requests: usize) -> (String, JoinHandle<Vec<(String, String)>>) {
+        let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url: String = format!(
+            "http://{}/api/v2/write?bucket=f1",
+            listener.local_addr().unwrap()
+        );
+        let response: String =
+            format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        let server: JoinHandle<Vec<(String, String)>> = thread::spawn(move || {
+            let mut received: Vec<(String, String)> = Vec::new();
+            for _ in 0..requests {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader: BufReader<TcpStream> = BufReader::new(stream);
+                let mut head: String = String::new();
+                let mut content_length: usize = 0;
+                loop {
+                    let mut line: String = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let mut body: Vec<u8> = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                received.push((head, String::from_utf8(body).unwrap()));
+            }
+            received
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_influx_writer_http_batches() {
+        let (url, server) = mock_server("204 No Content", 2);
+        let mut writer: InfluxWriter = InfluxWriter::http_with_token(&url, "secret");
+        writer.set_batch_size(4);
+        writer.set_flush_interval(Duration::from_secs(60));
+        let time: SystemTime = UNIX_EPOCH + Duration::from_secs(1);
+
+        for packet in [
+            participants(),
+            telemetry(1, 300),
+            telemetry(2, 310),
+            telemetry(3, 320),
+        ] {
+            writer.push_at(&packet, time).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let received: Vec<(String, String)> = server.join().unwrap();
+        assert!(received[0]
+            .0
+            .starts_with("POST /api/v2/write?bucket=f1 HTTP/1.1"));
+        assert!(received[0].0.contains("Authorization: Token secret"));
+        let lines: Vec<&str> = received[0].1.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(
+            "car_telemetry,session_uid=7,car_index=0 session_time=0.5,frame_identifier=1i,speed=0i,"
+        ));
+        assert!(lines[1].starts_with(
+            "car_telemetry,session_uid=7,car_index=1,driver=Max\\,\\ Driver,team_id=2 session_time=0.5,"
+        ));
+        assert!(lines[1].contains(",speed=300i,"));
+        assert!(lines[1].contains(",tyres_pressure_fl=23,tyres_pressure_fr=23.5,"));
+        assert!(lines[1].ends_with(" 1000000000"));
+        assert_eq!(received[1].1.lines().count(), 2);
+        assert!(received[1].1.contains(",speed=320i,"));
+    }
+
+    #[test]
+    fn test_influx_writer_http_error() {
+        let (url, server) = mock_server("500 Internal Server Error", 1);
+        let mut writer: InfluxWriter = InfluxWriter::http(&url);
+
+        writer.push(&telemetry(1, 300)).unwrap();
+        assert!(matches!(writer.finish(), Err(Error::Http(_))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_influx_writer_udp_datagrams() {
+        let receiver: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut writer: InfluxWriter = InfluxWriter::udp(receiver.local_addr().unwrap()).unwrap();
+
+        writer.push(&telemetry(1, 300)).unwrap();
+        writer.finish().unwrap();
+
+        let mut lines: usize = 0;
+        let mut buf: [u8; 2048] = [0; 2048];
+        while lines < NUM_CARS {
+            let received: usize = receiver.recv(&mut buf).unwrap();
+            assert!(received <= MAX_DATAGRAM_SIZE);
+            let datagram: &str = std::str::from_utf8(&buf[..received]).unwrap();
+            assert!(datagram.ends_with('\n'));
+            assert!(datagram
+                .lines()
+                .all(|line| line.starts_with("car_telemetry,")));
+            lines += datagram.lines().count();
+        }
+        assert_eq!(lines, NUM_CARS);
+    }
+
+    #[test]
+    fn test_influx_writer_udp_ipv6() {
+        let Ok(receiver) = UdpSocket::bind("[::1]:0") else {
+            return;
+        };
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut writer: InfluxWriter = InfluxWriter::udp(receiver.local_addr().unwrap()).unwrap();
+
+        writer.push(&telemetry(1, 300)).unwrap();
+        writer.finish().unwrap();
+
+        let mut buf: [u8; 2048] = [0; 2048];
+        let received: usize = receiver.recv(&mut buf).unwrap();
+        assert!(std::str::from_utf8(&buf[..received])
+            .unwrap()
+            .starts_with("car_telemetry,"));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Error, F1TelemetryClient, Packet, POLL_INTERVAL};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedPacket {
+    pub packet: Packet,
+    pub bytes: Vec<u8>,
+    pub received_at: SystemTime,
+}
+
+pub struct Packets<'a> {
+    client: &'a mut F1TelemetryClient,
+    // The motion ex packet carried by the last legacy motion packet.
+    pending: Option<ReceivedPacket>,
+    // The failure to set the read timeout, yielded first.
+    error: Option<Error>,
+}
+
+impl<'a> Packets<'a> {
+    // Wakes up periodically so that a shutdown request ends the iteration.
+    pub(crate) fn new(client: &'a mut F1TelemetryClient) -> Self {
+        let error: Option<Error> = client.set_read_timeout(POLL_INTERVAL).err();
+
+        Packets {
+            client,
+            pending: None,
+            error,
+        }
+    }
+
+    fn receive(&mut self) -> Result<Option<ReceivedPacket>, Error> {
+        let Some(received) = self.client.recv()? else {
+            return Ok(None);
+        };
+        let received_at: SystemTime = SystemTime::now();
+        let bytes: Vec<u8> = self.client.buf[..received].to_vec();
+        let packet: Packet = Packet::decode(&bytes)?;
+        self.pending = Packet::decode_motion_ex(&bytes).map(|packet| ReceivedPacket {
+            packet,
+            bytes: bytes.clone(),
+            received_at,
+        });
+
+        Ok(Some(ReceivedPacket {
+            packet,
+            bytes,
+            received_at,
+        }))
+    }
+}
+
+impl Iterator for Packets<'_> {
+    type Item = Result<ReceivedPacket, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+        if let Some(received_packet) = self.pending.take() {
+            return Some(Ok(received_packet));
+        }
+        while !self.client.shutdown_handle.is_shutdown() {
+            match self.receive() {
+                Ok(Some(received_packet)) => return Some(Ok(received_packet)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{v2022, PacketHeader, PacketLapData};
+    use std::net::UdpSocket;
+
+    #[test]
+    fn test_packets_yields_decoded_packets_with_raw_bytes() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = client.socket.local_addr().unwrap();
+        let mut packets: Vec<Packet> = Vec::new();
+        for frame_identifier in 0..3 {
+            let packet: Packet = Packet::Lap(PacketLapData {
+                header: PacketHeader {
+                    packet_format: 2024,
+                    packet_id: 2,
+                    frame_identifier,
+                    ..PacketHeader::default()
+                },
+                ..PacketLapData::default()
+            });
+            sender.send_to(&packet.encode().unwrap(), addr).unwrap();
+            packets.push(packet);
+        }
+        sender.send_to(&[0u8; 10], addr).unwrap();
+        let started: SystemTime = SystemTime::now();
+
+        let received_packets: Vec<Result<ReceivedPacket, Error>> =
+            client.packets().take(4).collect();
+
+        for (received_packet, packet) in received_packets.iter().zip(&packets) {
+            let received_packet: &ReceivedPacket = received_packet.as_ref().unwrap();
+            assert_eq!(received_packet.packet, *packet);
+            assert_eq!(received_packet.bytes, packet.encode().unwrap());
+            assert!(received_packet.received_at >= started);
+        }
+        assert!(matches!(
+            received_packets[3],
+            Err(Error::TruncatedPacket {
+                expected: 29,
+                actual: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn test_packets_yields_motion_ex_from_legacy_motion() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let bytes: Vec<u8> = v2022::PacketMotionData {
+            header: v2022::PacketHeader {
+                packet_format: 2022,
+                packet_id: 0,
+                ..v2022::PacketHeader::default()
+            },
+            ..v2022::PacketMotionData::default()
+        }
+        .serialize()
+        .unwrap();
+        sender
+            .send_to(&bytes, client.socket.local_addr().unwrap())
+            .unwrap();
+
+        let received_packets: Vec<ReceivedPacket> = client
+            .packets()
+            .take(2)
+            .collect::<Result<_, Error>>()
+            .unwrap();
+
+        assert!(matches!(received_packets[0].packet, Packet::Motion(_)));
+        assert!(matches!(received_packets[1].packet, Packet::MotionEx(_)));
+        assert_eq!(received_packets[1].bytes, bytes);
+    }
+
+    #[test]
+    fn test_packets_ends_on_shutdown() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        client.shutdown_handle().shutdown();
+
+        assert!(client.packets().next().is_none());
+    }
+}
@@ -1,12 +1,74 @@
+#[cfg(feature = "tokio")]
+mod async_client;
+mod capture;
+mod columns;
+mod compression;
+mod csv;
+mod error;
+mod forwarder;
+mod handlers;
+mod index;
+#[cfg(feature = "influxdb")]
+mod influxdb;
+mod iter;
+#[cfg(feature = "prometheus")]
+mod metrics;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod packet;
 pub mod packets;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod pcap;
+mod recorder;
+mod replay;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod shutdown;
+mod sink;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(test)]
+mod test_support;
+#[cfg(feature = "websocket")]
+mod websocket;
 
+#[cfg(feature = "tokio")]
+pub use async_client::AsyncF1TelemetryClient;
+pub use capture::{CaptureHeader, CaptureReader, CaptureRecord, CAPTURE_MAGIC, CAPTURE_VERSION};
+pub use compression::Compression;
+pub use csv::CsvExporter;
+pub use error::Error;
+pub use forwarder::{ForwardStats, Forwarder};
+pub use handlers::SubscriptionId;
+pub use index::{CaptureIndex, CaptureIndexEntry};
+#[cfg(feature = "influxdb")]
+pub use influxdb::InfluxWriter;
+pub use iter::{Packets, ReceivedPacket};
+#[cfg(feature = "prometheus")]
+pub use metrics::MetricsServer;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttPublisher;
+pub use packet::Packet;
+#[cfg(feature = "parquet")]
+pub use self::parquet::ParquetExporter;
+pub use pcap::{PcapDatagram, PcapReader, PcapWriter};
+pub use recorder::Recorder;
+pub use replay::{ReplaySpeed, Replayer};
+pub use shutdown::ShutdownHandle;
+pub use sink::PacketSink;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+#[cfg(feature = "websocket")]
+pub use websocket::WebSocketServer;
+
+use handlers::{Handlers, SubscriptionIds};
 use packets::PacketCarDamageData;
 use packets::PacketCarSetupData;
 use packets::PacketCarStatusData;
 use packets::PacketCarTelemetryData;
 use packets::PacketEventData;
 use packets::PacketFinalClassificationData;
-use packets::PacketHeader;
 use packets::PacketLapData;
 use packets::PacketLobbyInfoData;
 use packets::PacketMotionData;
@@ -14,227 +76,754 @@ use packets::PacketMotionExData;
 use packets::PacketParticipantsData;
 use packets::PacketSessionData;
 use packets::PacketSessionHistoryData;
+use packets::PacketTimeTrialData;
 use packets::PacketTyreSetsData;
-use std::net::UdpSocket;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct F1TelemetryClient {
     socket: UdpSocket,
+    // The socket's read timeout, so that it is only set when it changes.
+    read_timeout: Option<Duration>,
     buf: [u8; 2048],
-    car_damage_data_handler: Box<dyn Fn(&PacketCarDamageData)>,
-    car_setup_data_handler: Box<dyn Fn(&PacketCarSetupData)>,
-    car_status_data_handler: Box<dyn Fn(&PacketCarStatusData)>,
-    car_telemetry_data_handler: Box<dyn Fn(&PacketCarTelemetryData)>,
-    event_data_handler: Box<dyn Fn(&PacketEventData)>,
-    final_classification_data_handler: Box<dyn Fn(&PacketFinalClassificationData)>,
-    lap_data_handler: Box<dyn Fn(&PacketLapData)>,
-    lobby_info_data_handler: Box<dyn Fn(&PacketLobbyInfoData)>,
-    motion_data_handler: Box<dyn Fn(&PacketMotionData)>,
-    motion_ex_data_handler: Box<dyn Fn(&PacketMotionExData)>,
-    participants_data_handler: Box<dyn Fn(&PacketParticipantsData)>,
-    session_data_handler: Box<dyn Fn(&PacketSessionData)>,
-    session_history_data_handler: Box<dyn Fn(&PacketSessionHistoryData)>,
-    tyre_sets_data_handler: Box<dyn Fn(&PacketTyreSetsData)>,
+    subscription_ids: SubscriptionIds,
+    shutdown_handle: ShutdownHandle,
+    recorder: Option<Recorder>,
+    pcap_writer: Option<PcapWriter>,
+    car_damage_data_handlers: Handlers<PacketCarDamageData>,
+    car_setup_data_handlers: Handlers<PacketCarSetupData>,
+    car_status_data_handlers: Handlers<PacketCarStatusData>,
+    car_telemetry_data_handlers: Handlers<PacketCarTelemetryData>,
+    event_data_handlers: Handlers<PacketEventData>,
+    final_classification_data_handlers: Handlers<PacketFinalClassificationData>,
+    lap_data_handlers: Handlers<PacketLapData>,
+    lobby_info_data_handlers: Handlers<PacketLobbyInfoData>,
+    motion_data_handlers: Handlers<PacketMotionData>,
+    motion_ex_data_handlers: Handlers<PacketMotionExData>,
+    participants_data_handlers: Handlers<PacketParticipantsData>,
+    session_data_handlers: Handlers<PacketSessionData>,
+    session_history_data_handlers: Handlers<PacketSessionHistoryData>,
+    time_trial_data_handlers: Handlers<PacketTimeTrialData>,
+    tyre_sets_data_handlers: Handlers<PacketTyreSetsData>,
+    error_handlers: Handlers<Error>,
 }
 
 impl F1TelemetryClient {
-    pub fn new(bind_address: &str) -> Self {
-        let socket: UdpSocket = UdpSocket::bind(bind_address).expect("Couldn't bind to address");
+    pub fn new(bind_address: &str) -> Result<Self, Error> {
+        let socket: UdpSocket = UdpSocket::bind(bind_address).map_err(Error::Bind)?;
+        let read_timeout: Option<Duration> = None;
         let buf: [u8; 2048] = [0; 2048];
-        let car_damage_data_handler = Box::new(|_: &PacketCarDamageData| {});
-        let car_setup_data_handler = Box::new(|_: &PacketCarSetupData| {});
-        let car_status_data_handler = Box::new(|_: &PacketCarStatusData| {});
-        let car_telemetry_data_handler = Box::new(|_: &PacketCarTelemetryData| {});
-        let event_data_handler = Box::new(|_: &PacketEventData| {});
-        let final_classification_data_handler = Box::new(|_: &PacketFinalClassificationData| {});
-        let lap_data_handler = Box::new(|_: &PacketLapData| {});
-        let lobby_info_data_handler = Box::new(|_: &PacketLobbyInfoData| {});
-        let motion_data_handler = Box::new(|_: &PacketMotionData| {});
-        let motion_ex_data_handler = Box::new(|_: &PacketMotionExData| {});
-        let participants_data_handler = Box::new(|_: &PacketParticipantsData| {});
-        let session_data_handler = Box::new(|_: &PacketSessionData| {});
-        let session_history_data_handler = Box::new(|_: &PacketSessionHistoryData| {});
-        let tyre_sets_data_handler = Box::new(|_: &PacketTyreSetsData| {});
-
-        F1TelemetryClient {
+        let subscription_ids: SubscriptionIds = SubscriptionIds::default();
+        let shutdown_handle: ShutdownHandle = ShutdownHandle::default();
+        let recorder: Option<Recorder> = None;
+        let pcap_writer: Option<PcapWriter> = None;
+        let car_damage_data_handlers = Handlers::new();
+        let car_setup_data_handlers = Handlers::new();
+        let car_status_data_handlers = Handlers::new();
+        let car_telemetry_data_handlers = Handlers::new();
+        let event_data_handlers = Handlers::new();
+        let final_classification_data_handlers = Handlers::new();
+        let lap_data_handlers = Handlers::new();
+        let lobby_info_data_handlers = Handlers::new();
+        let motion_data_handlers = Handlers::new();
+        let motion_ex_data_handlers = Handlers::new();
+        let participants_data_handlers = Handlers::new();
+        let session_data_handlers = Handlers::new();
+        let session_history_data_handlers = Handlers::new();
+        let time_trial_data_handlers = Handlers::new();
+        let tyre_sets_data_handlers = Handlers::new();
+        let error_handlers = Handlers::new();
+
+        Ok(F1TelemetryClient {
             socket,
+            read_timeout,
             buf,
-            car_damage_data_handler,
-            car_setup_data_handler,
-            car_status_data_handler,
-            car_telemetry_data_handler,
-            event_data_handler,
-            final_classification_data_handler,
-            lap_data_handler,
-            lobby_info_data_handler,
-            motion_data_handler,
-            motion_ex_data_handler,
-            participants_data_handler,
-            session_data_handler,
-            session_history_data_handler,
-            tyre_sets_data_handler,
-        }
+            subscription_ids,
+            shutdown_handle,
+            recorder,
+            pcap_writer,
+            car_damage_data_handlers,
+            car_setup_data_handlers,
+            car_status_data_handlers,
+            car_telemetry_data_handlers,
+            event_data_handlers,
+            final_classification_data_handlers,
+            lap_data_handlers,
+            lobby_info_data_handlers,
+            motion_data_handlers,
+            motion_ex_data_handlers,
+            participants_data_handlers,
+            session_data_handlers,
+            session_history_data_handlers,
+            time_trial_data_handlers,
+            tyre_sets_data_handlers,
+            error_handlers,
+        })
+    }
+
+    pub fn add_packet_car_damage_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarDamageData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_damage_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_car_damage_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarDamageData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_damage_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_car_setup_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarSetupData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_setup_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_car_setup_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarSetupData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_setup_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_car_status_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarStatusData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_status_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_car_status_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarStatusData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_status_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_car_damage_data_handler(&mut self, handler: Box<dyn Fn(&PacketCarDamageData)>) {
-        self.car_damage_data_handler = handler;
+    pub fn add_packet_car_telemetry_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarTelemetryData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_telemetry_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_car_setup_data_handler(&mut self, handler: Box<dyn Fn(&PacketCarSetupData)>) {
-        self.car_setup_data_handler = handler;
+    pub fn set_packet_car_telemetry_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketCarTelemetryData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.car_telemetry_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_car_status_data_handler(&mut self, handler: Box<dyn Fn(&PacketCarStatusData)>) {
-        self.car_status_data_handler = handler;
+    pub fn add_packet_event_data_handler(&mut self, handler: Box<dyn FnMut(&PacketEventData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.event_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_car_telemetry_data_handler(&mut self, handler: Box<dyn Fn(&PacketCarTelemetryData)>) {
-        self.car_telemetry_data_handler = handler;
+    pub fn set_packet_event_data_handler(&mut self, handler: Box<dyn FnMut(&PacketEventData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.event_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_event_data_handler(&mut self, handler: Box<dyn Fn(&PacketEventData)>) {
-        self.event_data_handler = handler;
+    pub fn add_packet_final_classification_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketFinalClassificationData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.final_classification_data_handlers.add(id, handler);
+        id
     }
 
     pub fn set_packet_final_classification_data_handler(
         &mut self,
-        handler: Box<dyn Fn(&PacketFinalClassificationData)>,
-    ) {
-        self.final_classification_data_handler = handler;
+        handler: Box<dyn FnMut(&PacketFinalClassificationData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.final_classification_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_lap_data_handler(&mut self, handler: Box<dyn FnMut(&PacketLapData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.lap_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_lap_data_handler(&mut self, handler: Box<dyn FnMut(&PacketLapData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.lap_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_lobby_info_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketLobbyInfoData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.lobby_info_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_lobby_info_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketLobbyInfoData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.lobby_info_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_lap_data_handler(&mut self, handler: Box<dyn Fn(&PacketLapData)>) {
-        self.lap_data_handler = handler;
+    pub fn add_packet_motion_data_handler(&mut self, handler: Box<dyn FnMut(&PacketMotionData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.motion_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_lobby_info_data_handler(&mut self, handler: Box<dyn Fn(&PacketLobbyInfoData)>) {
-        self.lobby_info_data_handler = handler;
+    pub fn set_packet_motion_data_handler(&mut self, handler: Box<dyn FnMut(&PacketMotionData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.motion_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_motion_data_handler(&mut self, handler: Box<dyn Fn(&PacketMotionData)>) {
-        self.motion_data_handler = handler;
+    pub fn add_packet_motion_ex_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketMotionExData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.motion_ex_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_motion_ex_data_handler(&mut self, handler: Box<dyn Fn(&PacketMotionExData)>) {
-        self.motion_ex_data_handler = handler;
+    pub fn set_packet_motion_ex_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketMotionExData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.motion_ex_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_participants_data_handler(&mut self, handler: Box<dyn Fn(&PacketParticipantsData)>) {
-        self.participants_data_handler = handler;
+    pub fn add_packet_participants_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketParticipantsData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.participants_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_session_data_handler(&mut self, handler: Box<dyn Fn(&PacketSessionData)>) {
-        self.session_data_handler = handler;
+    pub fn set_packet_participants_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketParticipantsData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.participants_data_handlers.set(id, handler);
+        id
     }
 
-    pub fn set_packet_session_history_data_handler(&mut self, handler: Box<dyn Fn(&PacketSessionHistoryData)>) {
-        self.session_history_data_handler = handler;
+    pub fn add_packet_session_data_handler(&mut self, handler: Box<dyn FnMut(&PacketSessionData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.session_data_handlers.add(id, handler);
+        id
     }
 
-    pub fn set_packet_tyre_sets_data_handler(&mut self, handler: Box<dyn Fn(&PacketTyreSetsData)>) {
-        self.tyre_sets_data_handler = handler;
+    pub fn set_packet_session_data_handler(&mut self, handler: Box<dyn FnMut(&PacketSessionData)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.session_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_session_history_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketSessionHistoryData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.session_history_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_session_history_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketSessionHistoryData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.session_history_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_time_trial_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketTimeTrialData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.time_trial_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_time_trial_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketTimeTrialData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.time_trial_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_packet_tyre_sets_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketTyreSetsData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.tyre_sets_data_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_packet_tyre_sets_data_handler(
+        &mut self,
+        handler: Box<dyn FnMut(&PacketTyreSetsData)>,
+    ) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.tyre_sets_data_handlers.set(id, handler);
+        id
+    }
+
+    pub fn add_error_handler(&mut self, handler: Box<dyn FnMut(&Error)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.error_handlers.add(id, handler);
+        id
+    }
+
+    pub fn set_error_handler(&mut self, handler: Box<dyn FnMut(&Error)>) -> SubscriptionId {
+        let id: SubscriptionId = self.subscription_ids.next();
+        self.error_handlers.set(id, handler);
+        id
+    }
+
+    pub fn remove_handler(&mut self, id: SubscriptionId) -> bool {
+        let mut removed: bool = false;
+        removed |= self.car_damage_data_handlers.remove(id);
+        removed |= self.car_setup_data_handlers.remove(id);
+        removed |= self.car_status_data_handlers.remove(id);
+        removed |= self.car_telemetry_data_handlers.remove(id);
+        removed |= self.event_data_handlers.remove(id);
+        removed |= self.final_classification_data_handlers.remove(id);
+        removed |= self.lap_data_handlers.remove(id);
+        removed |= self.lobby_info_data_handlers.remove(id);
+        removed |= self.motion_data_handlers.remove(id);
+        removed |= self.motion_ex_data_handlers.remove(id);
+        removed |= self.participants_data_handlers.remove(id);
+        removed |= self.session_data_handlers.remove(id);
+        removed |= self.session_history_data_handlers.remove(id);
+        removed |= self.time_trial_data_handlers.remove(id);
+        removed |= self.tyre_sets_data_handlers.remove(id);
+        removed |= self.error_handlers.remove(id);
+        removed
+    }
+
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    pub fn take_recorder(&mut self) -> Option<Recorder> {
+        self.recorder.take()
+    }
+
+    pub fn set_pcap_writer(&mut self, pcap_writer: PcapWriter) {
+        self.pcap_writer = Some(pcap_writer);
+    }
+
+    pub fn take_pcap_writer(&mut self) -> Option<PcapWriter> {
+        self.pcap_writer.take()
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown_handle.clone()
     }
 
     pub fn run(&mut self) {
-        loop {
-            self.receive_packet();
+        while !self.shutdown_handle.is_shutdown() {
+            self.run_once(POLL_INTERVAL);
+        }
+    }
+
+    pub fn run_for(&mut self, duration: Duration) {
+        let deadline: Instant = Instant::now() + duration;
+        while !self.shutdown_handle.is_shutdown() {
+            let remaining: Duration = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            self.run_once(remaining.min(POLL_INTERVAL));
+        }
+    }
+
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&Packet) -> bool) {
+        while !self.shutdown_handle.is_shutdown() {
+            if let Some(packet) = self.run_once(POLL_INTERVAL) {
+                if predicate(&packet) {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn poll_once(&mut self, timeout: Duration) -> Result<Option<Packet>, Error> {
+        self.set_read_timeout(timeout)?;
+        self.receive_packet()
+    }
+
+    pub fn process_datagram(&mut self, bytes: &[u8]) -> Result<Packet, Error> {
+        let packet: Packet = Packet::decode(bytes)?;
+        self.dispatch(&packet);
+        if let Some(packet) = Packet::decode_motion_ex(bytes) {
+            self.dispatch(&packet);
+        }
+
+        Ok(packet)
+    }
+
+    pub fn packets(&mut self) -> Packets<'_> {
+        Packets::new(self)
+    }
+
+    fn run_once(&mut self, timeout: Duration) -> Option<Packet> {
+        match self.poll_once(timeout) {
+            Ok(packet) => packet,
+            Err(e) => {
+                self.error_handlers.call(&e);
+                None
+            }
         }
     }
 
-    fn receive_packet(&mut self) {
-        match self.socket.recv(&mut self.buf) {
-            Ok(received) => {
-                match PacketHeader::unserialize(&self.buf[..received]) {
-                    Ok(header) => match header.packet_id {
-                        0 => {
-                            match PacketMotionData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.motion_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        1 => {
-                            match PacketSessionData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.session_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        2 => {
-                            match PacketLapData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.lap_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        3 => {
-                            match PacketEventData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.event_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        4 => {
-                            match PacketParticipantsData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.participants_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        5 => {
-                            match PacketCarSetupData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.car_setup_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        6 => {
-                            match PacketCarTelemetryData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.car_telemetry_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        7 => {
-                            match PacketCarStatusData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.car_status_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        8 => {
-                            match PacketFinalClassificationData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.final_classification_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        9 => {
-                            match PacketLobbyInfoData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.lobby_info_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        10 => {
-                            match PacketCarDamageData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.car_damage_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        11 => {
-                            match PacketSessionHistoryData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.session_history_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        12 => {
-                            match PacketTyreSetsData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.tyre_sets_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        13 => {
-                            match PacketMotionExData::unserialize(&self.buf[..received]) {
-                                Ok(packet) => (self.motion_ex_data_handler)(&packet),
-                                Err(e) => eprintln!("{e:?}"),
-                            };
-                        }
-                        _ => {}
-                    },
-                    Err(e) => eprintln!("{e:?}"),
-                };
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        set_read_timeout(&self.socket, &mut self.read_timeout, timeout)
+    }
+
+    fn recv(&mut self) -> Result<Option<usize>, Error> {
+        match self.socket.recv_from(&mut self.buf) {
+            Ok((received, source)) => {
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.record(&self.buf[..received])?;
+                }
+                if let Some(pcap_writer) = &mut self.pcap_writer {
+                    let destination: SocketAddr = self.socket.local_addr()?;
+                    pcap_writer.write_datagram(SystemTime::now(), source, destination, &self.buf[..received])?;
+                }
+                Ok(Some(received))
             }
-            Err(e) => eprintln!("{e:?}"),
+            Err(e) if is_timeout(&e) => Ok(None),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    fn receive_packet(&mut self) -> Result<Option<Packet>, Error> {
+        let Some(received) = self.recv()? else {
+            return Ok(None);
+        };
+        let packet: Packet = Packet::decode(&self.buf[..received])?;
+        self.dispatch(&packet);
+        if let Some(packet) = Packet::decode_motion_ex(&self.buf[..received]) {
+            self.dispatch(&packet);
+        }
+
+        Ok(Some(packet))
+    }
+
+    fn dispatch(&mut self, packet: &Packet) {
+        match *packet {
+            Packet::Motion(packet) => self.motion_data_handlers.call(&packet),
+            Packet::Session(packet) => self.session_data_handlers.call(&packet),
+            Packet::Lap(packet) => self.lap_data_handlers.call(&packet),
+            Packet::Event(packet) => self.event_data_handlers.call(&packet),
+            Packet::Participants(packet) => self.participants_data_handlers.call(&packet),
+            Packet::CarSetup(packet) => self.car_setup_data_handlers.call(&packet),
+            Packet::CarTelemetry(packet) => self.car_telemetry_data_handlers.call(&packet),
+            Packet::CarStatus(packet) => self.car_status_data_handlers.call(&packet),
+            Packet::FinalClassification(packet) => self.final_classification_data_handlers.call(&packet),
+            Packet::LobbyInfo(packet) => self.lobby_info_data_handlers.call(&packet),
+            Packet::CarDamage(packet) => self.car_damage_data_handlers.call(&packet),
+            Packet::SessionHistory(packet) => self.session_history_data_handlers.call(&packet),
+            Packet::TyreSets(packet) => self.tyre_sets_data_handlers.call(&packet),
+            Packet::MotionEx(packet) => self.motion_ex_data_handlers.call(&packet),
+            Packet::TimeTrial(packet) => self.time_trial_data_handlers.call(&packet),
+        }
+    }
+}
+
+// Sets the socket's read timeout, unless `read_timeout`, the one set last, is
+// the same.
+fn set_read_timeout(socket: &UdpSocket, read_timeout: &mut Option<Duration>, timeout: Duration) -> Result<(), Error> {
+    // A zero read timeout is rejected by the socket, so wait at least a millisecond.
+    let timeout: Duration = timeout.max(Duration::from_millis(1));
+    if *read_timeout != Some(timeout) {
+        socket.set_read_timeout(Some(timeout)).map_err(Error::Io)?;
+        *read_timeout = Some(timeout);
+    }
+
+    Ok(())
+}
+
+// Whether a receive with a timeout ended without a datagram. A signal interrupts
+// such a receive even if it would be restarted otherwise.
+fn is_timeout(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use packets::PacketHeader;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn test_new_reports_bind_failure() {
+        assert!(matches!(F1TelemetryClient::new("not an address"), Err(Error::Bind(_))));
+    }
+
+    #[test]
+    fn test_receive_packet_reports_truncated_packet() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.send_to(&[0u8; 10], client.socket.local_addr().unwrap()).unwrap();
+
+        assert!(matches!(
+            client.receive_packet(),
+            Err(Error::TruncatedPacket {
+                expected: 29,
+                actual: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn test_receive_packet_reports_unsupported_packet_format() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let header: PacketHeader = PacketHeader {
+            packet_format: 2019,
+            ..PacketHeader::default()
+        };
+        sender
+            .send_to(&header.serialize().unwrap(), client.socket.local_addr().unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            client.receive_packet(),
+            Err(Error::UnsupportedPacketFormat(2019))
+        ));
+    }
+
+    #[test]
+    fn test_receive_packet_calls_all_handlers_in_order() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let calls: Arc<Mutex<Vec<(&str, u32)>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let mut count: u32 = 0;
+        let logger: Arc<Mutex<Vec<(&str, u32)>>> = Arc::clone(&calls);
+        client.add_packet_lap_data_handler(Box::new(move |packet| {
+            count += 1;
+            logger
+                .lock()
+                .unwrap()
+                .push(("logger", count + packet.header.frame_identifier));
+        }));
+        let dashboard: Arc<Mutex<Vec<(&str, u32)>>> = Arc::clone(&calls);
+        let id: SubscriptionId = client.add_packet_lap_data_handler(Box::new(move |packet| {
+            dashboard
+                .lock()
+                .unwrap()
+                .push(("dashboard", packet.header.frame_identifier));
+        }));
+
+        let packet: Packet = Packet::Lap(PacketLapData {
+            header: PacketHeader {
+                packet_format: 2024,
+                packet_id: 2,
+                frame_identifier: 100,
+                ..PacketHeader::default()
+            },
+            ..PacketLapData::default()
+        });
+        let addr = client.socket.local_addr().unwrap();
+        sender.send_to(&packet.encode().unwrap(), addr).unwrap();
+        client.receive_packet().unwrap();
+        assert!(client.remove_handler(id));
+        assert!(!client.remove_handler(id));
+        sender.send_to(&packet.encode().unwrap(), addr).unwrap();
+        client.receive_packet().unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![("logger", 101), ("dashboard", 100), ("logger", 102)]
+        );
+    }
+
+    fn lap_packet(frame_identifier: u32) -> Vec<u8> {
+        Packet::Lap(PacketLapData {
+            header: PacketHeader {
+                packet_format: 2024,
+                packet_id: 2,
+                frame_identifier,
+                ..PacketHeader::default()
+            },
+            ..PacketLapData::default()
+        })
+        .encode()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_handlers_can_share_state_without_send() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let frames: Rc<RefCell<Vec<u32>>> = Rc::new(RefCell::new(Vec::new()));
+        let shared: Rc<RefCell<Vec<u32>>> = Rc::clone(&frames);
+        client.add_packet_lap_data_handler(Box::new(move |packet| {
+            shared.borrow_mut().push(packet.header.frame_identifier);
+        }));
+
+        client.process_datagram(&lap_packet(1)).unwrap();
+        client.process_datagram(&lap_packet(2)).unwrap();
+
+        assert_eq!(*frames.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_poll_once_times_out() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+
+        assert!(matches!(client.poll_once(Duration::from_millis(10)), Ok(None)));
+    }
+
+    #[test]
+    fn test_poll_once_returns_packet() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender
+            .send_to(&lap_packet(7), client.socket.local_addr().unwrap())
+            .unwrap();
+
+        let packet: Option<Packet> = client.poll_once(Duration::from_secs(5)).unwrap();
+
+        assert_eq!(packet.map(|packet| packet.header().frame_identifier), Some(7));
+    }
+
+    #[test]
+    fn test_run_for_returns_after_duration() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let started: Instant = Instant::now();
+
+        client.run_for(Duration::from_millis(150));
+
+        assert!(started.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn test_run_until_stops_on_predicate() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let frames: Arc<Mutex<Vec<u32>>> = Arc::new(Mutex::new(Vec::new()));
+        let shared: Arc<Mutex<Vec<u32>>> = Arc::clone(&frames);
+        client.add_packet_lap_data_handler(Box::new(move |packet| {
+            shared.lock().unwrap().push(packet.header.frame_identifier);
+        }));
+        for frame_identifier in 0..4 {
+            sender
+                .send_to(&lap_packet(frame_identifier), client.socket.local_addr().unwrap())
+                .unwrap();
+        }
+
+        client.run_until(|packet| packet.header().frame_identifier == 1);
+
+        assert_eq!(*frames.lock().unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_shutdown_handle_stops_run() {
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let shutdown_handle: ShutdownHandle = client.shutdown_handle();
+        let shutdown = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            shutdown_handle.shutdown();
+        });
+
+        client.run();
+
+        shutdown.join().unwrap();
+    }
+
+    #[test]
+    fn test_recorder_captures_every_datagram() {
+        let path = std::env::temp_dir().join(format!("f1-telemetry-client-{}.f1tc", std::process::id()));
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        client.set_recorder(Recorder::create(&path).unwrap());
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = client.socket.local_addr().unwrap();
+        sender.send_to(&lap_packet(1), addr).unwrap();
+        sender.send_to(&[0u8; 10], addr).unwrap();
+
+        assert!(client.poll_once(Duration::from_secs(5)).unwrap().is_some());
+        assert!(client.poll_once(Duration::from_secs(5)).is_err());
+        drop(client.take_recorder());
+
+        let records: Vec<CaptureRecord> = CaptureReader::open(&path).unwrap().collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].bytes, lap_packet(1));
+        assert_eq!(records[1].bytes, vec![0u8; 10]);
+    }
+
+    #[test]
+    fn test_pcap_writer_captures_every_datagram() {
+        let path = std::env::temp_dir().join(format!("f1-telemetry-client-{}.pcap", std::process::id()));
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        client.set_pcap_writer(PcapWriter::create(&path).unwrap());
+        let sender: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = client.socket.local_addr().unwrap();
+        sender.send_to(&lap_packet(1), addr).unwrap();
+        sender.send_to(&lap_packet(2), addr).unwrap();
+
+        assert!(client.poll_once(Duration::from_secs(5)).unwrap().is_some());
+        assert!(client.poll_once(Duration::from_secs(5)).unwrap().is_some());
+        drop(client.take_pcap_writer());
+
+        let datagrams: Vec<PcapDatagram> = PcapReader::open(&path).unwrap().collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(datagrams.len(), 2);
+        for (datagram, frame_identifier) in datagrams.iter().zip([1, 2]) {
+            assert_eq!(datagram.source, sender.local_addr().unwrap());
+            assert_eq!(datagram.destination, addr);
+            assert_eq!(datagram.payload, lap_packet(frame_identifier));
         }
     }
 }
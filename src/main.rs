@@ -1,7 +1,458 @@
-use f1_telemetry_client::F1TelemetryClient;
+use f1_telemetry_client::packets::Event;
+#[cfg(feature = "influxdb")]
+use f1_telemetry_client::InfluxWriter;
+#[cfg(feature = "prometheus")]
+use f1_telemetry_client::MetricsServer;
+#[cfg(feature = "mqtt")]
+use f1_telemetry_client::MqttPublisher;
+#[cfg(feature = "json")]
+use f1_telemetry_client::Packet;
+#[cfg(feature = "parquet")]
+use f1_telemetry_client::ParquetExporter;
+#[cfg(feature = "sqlite")]
+use f1_telemetry_client::SqliteStore;
+#[cfg(feature = "websocket")]
+use f1_telemetry_client::WebSocketServer;
+use f1_telemetry_client::{
+    CaptureReader, Compression, CsvExporter, Error, F1TelemetryClient, ForwardStats, Forwarder,
+    PacketSink, PcapWriter, Recorder, ReplaySpeed, Replayer, ShutdownHandle,
+};
+#[cfg(feature = "mqtt")]
+use rumqttc::QoS;
+use std::env;
+#[cfg(feature = "json")]
+use std::fs::File;
+#[cfg(feature = "json")]
+use std::io::{self, LineWriter, Write};
+use std::net::SocketAddr;
+use std::process;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
-fn main() {
-    let mut client: F1TelemetryClient = F1TelemetryClient::new("0.0.0.0:20777");
+static SIGNAL_SHUTDOWN_HANDLE: OnceLock<ShutdownHandle> = OnceLock::new();
+
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:20777";
+const STATS_INTERVAL: Duration = Duration::from_secs(10);
+const USAGE: &str = "usage: f1-telemetry-client [record <path> [bind-address]]
+       f1-telemetry-client csv <directory> [bind-address | --capture <path>]
+       f1-telemetry-client replay <path> <target-address> [--speed <multiplier>|max] [--loop]
+                           [--start <session-time>] [--stop <session-time>]
+       f1-telemetry-client forward <target-address>[=<id>,...]... [--bind <bind-address>]";
+#[cfg(feature = "json")]
+const JSON_USAGE: &str =
+    "       f1-telemetry-client json [bind-address] [--packets <id>,...] [--output <path>]";
+#[cfg(feature = "influxdb")]
+const INFLUXDB_USAGE: &str = "       f1-telemetry-client influxdb <url> [bind-address]";
+#[cfg(feature = "prometheus")]
+const METRICS_USAGE: &str = "       f1-telemetry-client metrics <listen-address> [bind-address]";
+#[cfg(feature = "websocket")]
+const WEBSOCKET_USAGE: &str =
+    "       f1-telemetry-client websocket <listen-address> [bind-address]";
+#[cfg(feature = "mqtt")]
+const MQTT_USAGE: &str = "       f1-telemetry-client mqtt <host>[:<port>] [bind-address] [--qos 0|1|2] [--max-rate <per-second>]";
+#[cfg(feature = "parquet")]
+const PARQUET_USAGE: &str =
+    "       f1-telemetry-client parquet <directory> [bind-address | --capture <path>]";
+#[cfg(feature = "sqlite")]
+const SQLITE_USAGE: &str =
+    "       f1-telemetry-client sqlite <database> [bind-address | --capture <path>]";
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [] => print(DEFAULT_BIND_ADDRESS),
+        ["record", path] => record(path, DEFAULT_BIND_ADDRESS),
+        ["record", path, bind_address] => record(path, bind_address),
+        ["csv", directory, "--capture", path] => {
+            let mut exporter: CsvExporter = CsvExporter::create(directory)?;
+            exporter.push_capture(CaptureReader::open(path)?)?;
+            exporter.finish()?;
+            Ok(())
+        }
+        ["csv", directory] => csv(directory, DEFAULT_BIND_ADDRESS),
+        ["csv", directory, bind_address] => csv(directory, bind_address),
+        ["replay", path, target_address, options @ ..] => match replayer(path, options)? {
+            Some(mut replayer) => replayer.replay_to(target_address),
+            None => usage(),
+        },
+        ["forward", options @ ..] => forward(options),
+        #[cfg(feature = "json")]
+        ["json", options @ ..] => json(options),
+        #[cfg(feature = "influxdb")]
+        ["influxdb", url] => influxdb(url, DEFAULT_BIND_ADDRESS),
+        #[cfg(feature = "influxdb")]
+        ["influxdb", url, bind_address] => influxdb(url, bind_address),
+        #[cfg(feature = "prometheus")]
+        ["metrics", listen_address] => metrics(listen_address, DEFAULT_BIND_ADDRESS),
+        #[cfg(feature = "prometheus")]
+        ["metrics", listen_address, bind_address] => metrics(listen_address, bind_address),
+        #[cfg(feature = "websocket")]
+        ["websocket", listen_address] => websocket(listen_address, DEFAULT_BIND_ADDRESS),
+        #[cfg(feature = "websocket")]
+        ["websocket", listen_address, bind_address] => websocket(listen_address, bind_address),
+        #[cfg(feature = "mqtt")]
+        ["mqtt", broker, options @ ..] => mqtt(broker, options),
+        #[cfg(feature = "parquet")]
+        ["parquet", directory, "--capture", path] => {
+            let mut exporter: ParquetExporter = ParquetExporter::create(directory)?;
+            exporter.push_capture(CaptureReader::open(path)?)?;
+            exporter.finish()?;
+            Ok(())
+        }
+        #[cfg(feature = "parquet")]
+        ["parquet", directory] => parquet(directory, DEFAULT_BIND_ADDRESS),
+        #[cfg(feature = "parquet")]
+        ["parquet", directory, bind_address] => parquet(directory, bind_address),
+        #[cfg(feature = "sqlite")]
+        ["sqlite", database, "--capture", path] => {
+            let mut store: SqliteStore = SqliteStore::open(database)?;
+            store.push_capture(CaptureReader::open(path)?)?;
+            store.finish()
+        }
+        #[cfg(feature = "sqlite")]
+        ["sqlite", database] => sqlite(database, DEFAULT_BIND_ADDRESS),
+        #[cfg(feature = "sqlite")]
+        ["sqlite", database, bind_address] => sqlite(database, bind_address),
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    #[cfg(feature = "json")]
+    eprintln!("{JSON_USAGE}");
+    #[cfg(feature = "influxdb")]
+    eprintln!("{INFLUXDB_USAGE}");
+    #[cfg(feature = "prometheus")]
+    eprintln!("{METRICS_USAGE}");
+    #[cfg(feature = "websocket")]
+    eprintln!("{WEBSOCKET_USAGE}");
+    #[cfg(feature = "mqtt")]
+    eprintln!("{MQTT_USAGE}");
+    #[cfg(feature = "parquet")]
+    eprintln!("{PARQUET_USAGE}");
+    #[cfg(feature = "sqlite")]
+    eprintln!("{SQLITE_USAGE}");
+    process::exit(2);
+}
+
+fn replayer(path: &str, options: &[&str]) -> Result<Option<Replayer>, Error> {
+    let mut replayer: Replayer = Replayer::open(path)?;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match *option {
+            "--loop" => replayer.set_looping(true),
+            "--speed" => match options.next() {
+                Some(&"max") => replayer.set_speed(ReplaySpeed::AsFastAsPossible)?,
+                Some(speed) => match speed.trim_end_matches('x').parse::<f64>() {
+                    Ok(multiplier) => replayer.set_speed(ReplaySpeed::Multiplier(multiplier))?,
+                    Err(_) => return Ok(None),
+                },
+                None => return Ok(None),
+            },
+            "--start" => match options
+                .next()
+                .map(|session_time| session_time.parse::<f32>())
+            {
+                Some(Ok(session_time)) => replayer.set_start_session_time(Some(session_time)),
+                _ => return Ok(None),
+            },
+            "--stop" => match options
+                .next()
+                .map(|session_time| session_time.parse::<f32>())
+            {
+                Some(Ok(session_time)) => replayer.set_stop_session_time(Some(session_time)),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        }
+    }
+    Ok(Some(replayer))
+}
+
+fn record(path: &str, bind_address: &str) -> Result<(), Error> {
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    if path.ends_with(".pcap") {
+        client.set_pcap_writer(PcapWriter::create(path)?);
+    } else {
+        client.set_recorder(Recorder::create_with_compression(path, compression(path))?);
+    }
+    client.set_error_handler(Box::new(|e| {
+        eprintln!("{e}");
+    }));
+    shutdown_on_signal(client.shutdown_handle());
+    client.run();
+    if let Some(recorder) = client.take_recorder() {
+        recorder.finish()?;
+    }
+    if let Some(pcap_writer) = client.take_pcap_writer() {
+        pcap_writer.finish()?;
+    }
+    Ok(())
+}
+
+// Each target may be followed by the packet ids to send to it, e.g.
+// `127.0.0.1:20778=6,7`. The statistics of each target are printed every ten
+// seconds.
+fn forward(options: &[&str]) -> Result<(), Error> {
+    let mut bind_address: &str = DEFAULT_BIND_ADDRESS;
+    let mut targets: Vec<(&str, Option<Vec<u8>>)> = Vec::new();
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match (*option, option.split_once('=')) {
+            ("--bind", _) => match options.next() {
+                Some(address) => bind_address = address,
+                None => usage(),
+            },
+            (_, Some((address, ids))) => match ids.split(',').map(str::parse).collect() {
+                Ok(ids) => targets.push((address, Some(ids))),
+                Err(_) => usage(),
+            },
+            (address, None) => targets.push((address, None)),
+        }
+    }
+    if targets.is_empty() {
+        usage();
+    }
+
+    let mut forwarder: Forwarder = Forwarder::new(bind_address)?;
+    for (address, packet_ids) in &targets {
+        forwarder.add_target(*address, packet_ids.as_deref())?;
+    }
+    let mut reported: Instant = Instant::now();
+    loop {
+        if let Err(e) = forwarder.poll_once(STATS_INTERVAL.saturating_sub(reported.elapsed())) {
+            eprintln!("{e}");
+        }
+        if reported.elapsed() >= STATS_INTERVAL {
+            let stats: Vec<(SocketAddr, ForwardStats)> = forwarder.stats();
+            for (address, stats) in stats {
+                eprintln!(
+                    "{address}: {} forwarded, {} filtered, {} dropped",
+                    stats.forwarded, stats.filtered, stats.dropped
+                );
+            }
+            reported = Instant::now();
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+fn json(options: &[&str]) -> Result<(), Error> {
+    let (bind_address, options) = match options {
+        [bind_address, options @ ..] if !bind_address.starts_with("--") => (*bind_address, options),
+        _ => (DEFAULT_BIND_ADDRESS, options),
+    };
+    let mut packet_ids: Option<Vec<u8>> = None;
+    let mut output: Box<dyn Write> = Box::new(io::stdout());
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match (*option, options.next()) {
+            ("--packets", Some(ids)) => match ids.split(',').map(str::parse).collect() {
+                Ok(ids) => packet_ids = Some(ids),
+                Err(_) => usage(),
+            },
+            ("--output", Some(path)) => output = Box::new(File::create(path)?),
+            _ => usage(),
+        }
+    }
+
+    let mut output: LineWriter<Box<dyn Write>> = LineWriter::new(output);
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    for received_packet in client.packets() {
+        let packet: Packet = match received_packet {
+            Ok(received_packet) => received_packet.packet,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+        if let Some(packet_ids) = &packet_ids {
+            if !packet_ids.contains(&packet.header().packet_id) {
+                continue;
+            }
+        }
+        serde_json::to_writer(&mut output, &packet).map_err(io::Error::from)?;
+        output.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn csv(directory: &str, bind_address: &str) -> Result<(), Error> {
+    let mut exporter: CsvExporter = CsvExporter::create(directory)?;
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    shutdown_on_signal(client.shutdown_handle());
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => {
+                if let Err(e) = exporter.push(&received_packet.packet) {
+                    eprintln!("{e}");
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    exporter.finish()?;
+    Ok(())
+}
+
+// `udp://host:port` writes to a UDP listener, anything else is an HTTP write
+// endpoint. The token for InfluxDB 2 is read from INFLUXDB_TOKEN.
+#[cfg(feature = "influxdb")]
+fn influxdb(url: &str, bind_address: &str) -> Result<(), Error> {
+    let mut writer: InfluxWriter = match (url.strip_prefix("udp://"), env::var("INFLUXDB_TOKEN")) {
+        (Some(address), _) => InfluxWriter::udp(address)?,
+        (None, Ok(token)) => InfluxWriter::http_with_token(url, &token),
+        (None, Err(_)) => InfluxWriter::http(url),
+    };
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    shutdown_on_signal(client.shutdown_handle());
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => {
+                if let Err(e) = writer.push(&received_packet.packet) {
+                    eprintln!("{e}");
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    writer.finish()
+}
+
+#[cfg(feature = "prometheus")]
+fn metrics(listen_address: &str, bind_address: &str) -> Result<(), Error> {
+    let server: MetricsServer = MetricsServer::bind(listen_address)?;
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => server.push(&received_packet.packet),
+            Err(e) => {
+                server.push_error(&e);
+                eprintln!("{e}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "websocket")]
+fn websocket(listen_address: &str, bind_address: &str) -> Result<(), Error> {
+    let server: WebSocketServer = WebSocketServer::bind(listen_address)?;
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    server.attach(&mut client, &(0..=14).collect::<Vec<u8>>());
+    client.set_error_handler(Box::new(|e| {
+        eprintln!("{e}");
+    }));
+    client.run();
+    Ok(())
+}
+
+#[cfg(feature = "mqtt")]
+fn mqtt(broker: &str, options: &[&str]) -> Result<(), Error> {
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => (host, port),
+            Err(_) => usage(),
+        },
+        None => (broker, 1883),
+    };
+    let (bind_address, options) = match options {
+        [bind_address, options @ ..] if !bind_address.starts_with("--") => (*bind_address, options),
+        _ => (DEFAULT_BIND_ADDRESS, options),
+    };
+    let mut publisher: MqttPublisher = MqttPublisher::connect("f1-telemetry-client", host, port);
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match (*option, options.next()) {
+            ("--qos", Some(&"0")) => publisher.set_qos(QoS::AtMostOnce),
+            ("--qos", Some(&"1")) => publisher.set_qos(QoS::AtLeastOnce),
+            ("--qos", Some(&"2")) => publisher.set_qos(QoS::ExactlyOnce),
+            ("--max-rate", Some(max_rate)) => match max_rate.parse::<f64>() {
+                Ok(max_rate) if max_rate > 0.0 && max_rate.is_finite() => {
+                    publisher.set_max_rate(Some(max_rate))
+                }
+                _ => usage(),
+            },
+            _ => usage(),
+        }
+    }
+
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    shutdown_on_signal(client.shutdown_handle());
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => {
+                if let Err(e) = publisher.push(&received_packet.packet) {
+                    eprintln!("{e}");
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    publisher.finish()
+}
+
+// Parquet files are only readable once their footer is written, so the live
+// export is finished on ctrl-c rather than cut off.
+#[cfg(feature = "parquet")]
+fn parquet(directory: &str, bind_address: &str) -> Result<(), Error> {
+    let mut exporter: ParquetExporter = ParquetExporter::create(directory)?;
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    shutdown_on_signal(client.shutdown_handle());
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => {
+                if let Err(e) = exporter.push(&received_packet.packet) {
+                    eprintln!("{e}");
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    exporter.finish()?;
+    Ok(())
+}
+
+// Shuts down on ctrl-c or SIGTERM, so that the output is finished rather than
+// cut off. A second signal ends the process straight away.
+fn shutdown_on_signal(shutdown_handle: ShutdownHandle) {
+    if SIGNAL_SHUTDOWN_HANDLE.set(shutdown_handle).is_err() {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        let handler: extern "C" fn(libc::c_int) = on_signal;
+        unsafe {
+            libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+            libc::signal(libc::SIGTERM, handler as libc::sighandler_t);
+        }
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_signal(signal: libc::c_int) {
+    if let Some(shutdown_handle) = SIGNAL_SHUTDOWN_HANDLE.get() {
+        shutdown_handle.shutdown();
+    }
+    unsafe {
+        libc::signal(signal, libc::SIG_DFL);
+    }
+}
+
+fn compression(path: &str) -> Compression {
+    match path.rsplit_once('.').map(|(_, extension)| extension) {
+        #[cfg(feature = "zstd")]
+        Some("zst") => Compression::Zstd,
+        #[cfg(feature = "lz4")]
+        Some("lz4") => Compression::Lz4,
+        _ => Compression::None,
+    }
+}
+
+fn print(bind_address: &str) -> Result<(), Error> {
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
     client.set_packet_car_damage_data_handler(Box::new(|packet| {
         println!("{packet:?}");
     }));
@@ -14,30 +465,33 @@ fn main() {
     client.set_packet_car_telemetry_data_handler(Box::new(|packet| {
         println!("{packet:?}");
     }));
-    client.set_packet_event_data_handler(Box::new(|packet| unsafe {
-        let ed = packet.event_details;
-        match &packet.event_string_code {
-            b"FTLP" => println!("{:?}", ed.fastest_lap),
-            b"RTMT" => println!("{:?}", ed.retirement),
-            b"TMPT" => println!("{:?}", ed.team_mate_in_pits),
-            b"RCWN" => println!("{:?}", ed.race_winner),
-            b"PENA" => println!("{:?}", ed.penalty),
-            b"SPTP" => println!("{:?}", ed.speed_trap),
-            b"STLG" => println!("{:?}", ed.start_lights),
-            b"DTSV" => println!("{:?}", ed.drive_through_penalty_served),
-            b"SGSV" => println!("{:?}", ed.stop_go_penalty_served),
-            b"FLBK" => println!("{:?}", ed.flashback),
-            b"BUTN" => println!("{:?}", ed.buttons),
-            b"OVTK" => println!("{:?}", ed.overtake),
-            b"SSTA" => println!("Session Started"),
-            b"SEND" => println!("Session Ended"),
-            b"DRSE" => println!("DRS Enabled"),
-            b"DRSD" => println!("DRS Disabled"),
-            b"CHQF" => println!("Chequered Flag"),
-            b"LGOT" => println!("Lights Out"),
-            b"RDFL" => println!("Red Flag"),
-            _ => {}
+    client.set_packet_event_data_handler(Box::new(|packet| match packet.event {
+        Event::SessionStarted => println!("Session Started"),
+        Event::SessionEnded => println!("Session Ended"),
+        Event::FastestLap(fastest_lap) => println!("{fastest_lap:?}"),
+        Event::Retirement(retirement) => println!("{retirement:?}"),
+        Event::DrsEnabled => println!("DRS Enabled"),
+        Event::DrsDisabled => println!("DRS Disabled"),
+        Event::TeamMateInPits(team_mate_in_pits) => println!("{team_mate_in_pits:?}"),
+        Event::ChequeredFlag => println!("Chequered Flag"),
+        Event::RaceWinner(race_winner) => println!("{race_winner:?}"),
+        Event::Penalty(penalty) => println!("{penalty:?}"),
+        Event::SpeedTrap(speed_trap) => println!("{speed_trap:?}"),
+        Event::StartLights(start_lights) => println!("{start_lights:?}"),
+        Event::LightsOut => println!("Lights Out"),
+        Event::DriveThroughPenaltyServed(drive_through_penalty_served) => {
+            println!("{drive_through_penalty_served:?}")
         }
+        Event::StopGoPenaltyServed(stop_go_penalty_served) => {
+            println!("{stop_go_penalty_served:?}")
+        }
+        Event::Flashback(flashback) => println!("{flashback:?}"),
+        Event::Buttons(buttons) => println!("{buttons:?}"),
+        Event::RedFlag => println!("Red Flag"),
+        Event::Overtake(overtake) => println!("{overtake:?}"),
+        Event::SafetyCar(safety_car) => println!("{safety_car:?}"),
+        Event::Collision(collision) => println!("{collision:?}"),
+        Event::Unknown(..) => {}
     }));
     client.set_packet_final_classification_data_handler(Box::new(|packet| {
         println!("{packet:?}");
@@ -63,8 +517,33 @@ fn main() {
     client.set_packet_session_history_data_handler(Box::new(|packet| {
         println!("{packet:?}");
     }));
+    client.set_packet_time_trial_data_handler(Box::new(|packet| {
+        println!("{packet:?}");
+    }));
     client.set_packet_tyre_sets_data_handler(Box::new(|packet| {
         println!("{packet:?}");
     }));
+    client.set_error_handler(Box::new(|e| {
+        eprintln!("{e}");
+    }));
     client.run();
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite(database: &str, bind_address: &str) -> Result<(), Error> {
+    let mut store: SqliteStore = SqliteStore::open(database)?;
+    let mut client: F1TelemetryClient = F1TelemetryClient::new(bind_address)?;
+    shutdown_on_signal(client.shutdown_handle());
+    for received_packet in client.packets() {
+        match received_packet {
+            Ok(received_packet) => {
+                if let Err(e) = store.push(&received_packet.packet) {
+                    eprintln!("{e}");
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    store.finish()
 }
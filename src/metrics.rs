@@ -0,0 +1,332 @@
+use crate::columns::WHEELS;
+use crate::packets::{PacketHeader, NUM_CARS};
+use crate::{Error, Packet};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use tiny_http::{Header, Method, Response, Server};
+
+#[derive(Default, Clone, Copy)]
+struct Car {
+    speed: Option<u16>,
+    engine_rpm: Option<u16>,
+    fuel_in_tank: Option<f32>,
+    ers_store_energy: Option<f32>,
+    tyres_wear: Option<[f32; 4]>,
+    car_position: Option<u8>,
+}
+
+#[derive(Default)]
+struct State {
+    datagrams: BTreeMap<u8, u64>,
+    decode_errors: u64,
+    dropped_frames: u64,
+    session_uid: u64,
+    frame_identifier: Option<u32>,
+    send_interval: Option<u32>,
+    cars: [Car; NUM_CARS],
+}
+
+impl State {
+    fn push(&mut self, packet: &Packet) {
+        let header: PacketHeader = packet.header();
+        *self.datagrams.entry(header.packet_id).or_default() += 1;
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.frame_identifier = None;
+            self.send_interval = None;
+            self.cars = [Car::default(); NUM_CARS];
+        }
+        match packet {
+            Packet::CarTelemetry(packet) => {
+                self.push_frame_identifier(header.frame_identifier);
+                for (car, car_telemetry_data) in self.cars.iter_mut().zip(packet.car_telemetry_data)
+                {
+                    car.speed = Some(car_telemetry_data.speed);
+                    car.engine_rpm = Some(car_telemetry_data.engine_rpm);
+                }
+            }
+            Packet::CarStatus(packet) => {
+                for (car, car_status_data) in self.cars.iter_mut().zip(packet.car_status_data) {
+                    car.fuel_in_tank = Some(car_status_data.fuel_in_tank);
+                    car.ers_store_energy = Some(car_status_data.ers_store_energy);
+                }
+            }
+            Packet::CarDamage(packet) => {
+                for (car, car_damage_data) in self.cars.iter_mut().zip(packet.car_damage_data) {
+                    car.tyres_wear = Some(car_damage_data.tyres_wear);
+                }
+            }
+            Packet::Lap(packet) => {
+                for (car, lap_data) in self.cars.iter_mut().zip(packet.lap_data) {
+                    car.car_position = Some(lap_data.car_position);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Car telemetry is sent at the configured UDP rate, so its frame
+    // identifiers are usually a few frames apart, and by a varying amount as
+    // the frame rate changes. A gap of at least twice the last gap without loss
+    // means that sends were lost. Flashbacks rewind the frame identifier and
+    // don't count.
+    fn push_frame_identifier(&mut self, frame_identifier: u32) {
+        if let Some(last) = self
+            .frame_identifier
+            .filter(|&last| frame_identifier > last)
+        {
+            let gap: u32 = frame_identifier - last;
+            let sends: u32 = self
+                .send_interval
+                .map_or(1, |send_interval| (gap / send_interval).max(1));
+            if sends > 1 {
+                self.dropped_frames += (sends - 1) as u64;
+            } else {
+                self.send_interval = Some(gap);
+            }
+        }
+        self.frame_identifier = Some(frame_identifier);
+    }
+
+    fn push_error(&mut self, error: &Error) {
+        match error {
+            Error::UnknownPacketId(packet_id) | Error::Decode { packet_id, .. } => {
+                *self.datagrams.entry(*packet_id).or_default() += 1;
+            }
+            Error::TruncatedPacket { .. } | Error::UnsupportedPacketFormat(_) => {}
+            _ => return,
+        }
+        self.decode_errors += 1;
+    }
+
+    fn render(&self) -> String {
+        let mut text: String = String::new();
+        gauge(
+            &mut text,
+            "f1_speed_kph",
+            "Speed of the car in kilometres per hour.",
+        );
+        self.samples(&mut text, "f1_speed_kph", |car| car.speed.map(f64::from));
+        gauge(&mut text, "f1_engine_rpm", "Engine RPM.");
+        self.samples(&mut text, "f1_engine_rpm", |car| {
+            car.engine_rpm.map(f64::from)
+        });
+        gauge(&mut text, "f1_fuel_in_tank_kg", "Fuel mass in the tank.");
+        self.samples(&mut text, "f1_fuel_in_tank_kg", |car| {
+            car.fuel_in_tank.map(f64::from)
+        });
+        gauge(&mut text, "f1_ers_store_energy_joules", "ERS energy store.");
+        self.samples(&mut text, "f1_ers_store_energy_joules", |car| {
+            car.ers_store_energy.map(f64::from)
+        });
+        gauge(&mut text, "f1_tyre_wear_percent", "Tyre wear per wheel.");
+        for (car_index, car) in self.cars.iter().enumerate() {
+            if let Some(tyres_wear) = car.tyres_wear {
+                for (wheel, index) in WHEELS {
+                    let _ = writeln!(
+                        text,
+                        "f1_tyre_wear_percent{{car_index=\"{car_index}\",wheel=\"{wheel}\"}} {}",
+                        tyres_wear[index]
+                    );
+                }
+            }
+        }
+        gauge(&mut text, "f1_car_position", "Race position of the car.");
+        self.samples(&mut text, "f1_car_position", |car| {
+            car.car_position.map(f64::from)
+        });
+
+        counter(
+            &mut text,
+            "f1_datagrams_received_total",
+            "Datagrams received per packet id.",
+        );
+        for (packet_id, count) in &self.datagrams {
+            let _ = writeln!(
+                text,
+                "f1_datagrams_received_total{{packet_id=\"{packet_id}\"}} {count}"
+            );
+        }
+        counter(
+            &mut text,
+            "f1_decode_errors_total",
+            "Datagrams that failed to decode.",
+        );
+        let _ = writeln!(text, "f1_decode_errors_total {}", self.decode_errors);
+        counter(
+            &mut text,
+            "f1_dropped_frames_total",
+            "Sends at the configured UDP rate of which no car telemetry arrived.",
+        );
+        let _ = writeln!(text, "f1_dropped_frames_total {}", self.dropped_frames);
+        text
+    }
+
+    fn samples(&self, text: &mut String, name: &str, value: impl Fn(&Car) -> Option<f64>) {
+        for (car_index, car) in self.cars.iter().enumerate() {
+            if let Some(value) = value(car) {
+                let _ = writeln!(text, "{name}{{car_index=\"{car_index}\"}} {value}");
+            }
+        }
+    }
+}
+
+fn gauge(text: &mut String, name: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} gauge");
+}
+
+fn counter(text: &mut String, name: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} counter");
+}
+
+// Serves `/metrics` in the Prometheus text format from a background thread.
+// Gauges hold the values of the latest packets of the current session, per
+// car; counters cover everything pushed since the server was started.
+pub struct MetricsServer {
+    state: Arc<Mutex<State>>,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, Error> {
+        let server: Arc<Server> =
+            Arc::new(Server::http(address).map_err(|e| Error::Bind(io::Error::other(e)))?);
+        let state: Arc<Mutex<State>> = Arc::new(Mutex::new(State::default()));
+        let thread: JoinHandle<()> = {
+            let server: Arc<Server> = Arc::clone(&server);
+            let state: Arc<Mutex<State>> = Arc::clone(&state);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let response =
+                        if *request.method() == Method::Get && request.url() == "/metrics" {
+                            let text: String = state.lock().unwrap().render();
+                            let content_type: Header = Header::from_bytes(
+                                "Content-Type",
+                                "text/plain; version=0.0.4; charset=utf-8",
+                            )
+                            .unwrap();
+                            Response::from_string(text).with_header(content_type)
+                        } else {
+                            Response::from_string("not found").with_status_code(404)
+                        };
+                    let _ = request.respond(response);
+                }
+            })
+        };
+
+        Ok(MetricsServer {
+            state,
+            server,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    pub fn push(&self, packet: &Packet) {
+        self.state.lock().unwrap().push(packet);
+    }
+
+    // Counts datagrams that failed to decode. Other errors are ignored.
+    pub fn push_error(&self, error: &Error) {
+        self.state.lock().unwrap().push_error(error);
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{PacketCarDamageData, PacketCarTelemetryData};
+    use crate::test_support::header;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    fn get(server: &MetricsServer, path: &str) -> String {
+        let mut stream: TcpStream = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response: String = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_metrics_server_exposes_latest_values_and_counters() {
+        let server: MetricsServer = MetricsServer::bind("127.0.0.1:0").unwrap();
+        for (frame_identifier, speed) in [(1, 100), (2, 110), (5, 120)] {
+            let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+                header: header(6, 7, frame_identifier),
+                ..PacketCarTelemetryData::default()
+            };
+            packet.car_telemetry_data[3].speed = speed;
+            packet.car_telemetry_data[3].engine_rpm = 11000;
+            server.push(&Packet::CarTelemetry(packet));
+        }
+        let mut packet: PacketCarDamageData = PacketCarDamageData {
+            header: header(10, 7, 5),
+            ..PacketCarDamageData::default()
+        };
+        packet.car_damage_data[3].tyres_wear = [1.0, 2.0, 3.0, 4.5];
+        server.push(&Packet::CarDamage(packet));
+        server.push_error(&Packet::decode(&[0; 10]).unwrap_err());
+        server.push_error(&Error::UnknownPacketId(42));
+        server.push_error(&Error::Io(io::ErrorKind::TimedOut.into()));
+
+        let response: String = get(&server, "/metrics");
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.contains("# TYPE f1_speed_kph gauge\n"));
+        assert!(response.contains("\nf1_speed_kph{car_index=\"3\"} 120\n"));
+        assert!(response.contains("\nf1_engine_rpm{car_index=\"3\"} 11000\n"));
+        assert!(response.contains("\nf1_tyre_wear_percent{car_index=\"3\",wheel=\"fl\"} 3\n"));
+        assert!(response.contains("\nf1_tyre_wear_percent{car_index=\"3\",wheel=\"rr\"} 2\n"));
+        assert!(!response.contains("f1_fuel_in_tank_kg{"));
+        assert!(response.contains("\nf1_datagrams_received_total{packet_id=\"6\"} 3\n"));
+        assert!(response.contains("\nf1_datagrams_received_total{packet_id=\"10\"} 1\n"));
+        assert!(response.contains("\nf1_datagrams_received_total{packet_id=\"42\"} 1\n"));
+        assert!(response.contains("\nf1_decode_errors_total 2\n"));
+        assert!(response.contains("\nf1_dropped_frames_total 2\n"));
+    }
+
+    #[test]
+    fn test_metrics_server_dropped_frames_at_send_interval() {
+        let server: MetricsServer = MetricsServer::bind("127.0.0.1:0").unwrap();
+        for frame_identifier in [0, 3, 6, 9, 11, 14, 20, 4, 7] {
+            server.push(&Packet::CarTelemetry(PacketCarTelemetryData {
+                header: header(6, 7, frame_identifier),
+                ..PacketCarTelemetryData::default()
+            }));
+        }
+
+        let response: String = get(&server, "/metrics");
+
+        assert!(response.contains("\nf1_dropped_frames_total 1\n"));
+    }
+
+    #[test]
+    fn test_metrics_server_not_found() {
+        let server: MetricsServer = MetricsServer::bind("127.0.0.1:0").unwrap();
+
+        assert!(get(&server, "/").starts_with("HTTP/1.1 404"));
+    }
+}
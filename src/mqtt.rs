@@ -0,0 +1,402 @@
+use crate::packets::{PacketHeader, NUM_CARS};
+use crate::{Error, Packet, ShutdownHandle};
+use rumqttc::{
+    Client, ClientError, Connection, MqttOptions, Outgoing, PubAck, PubComp, QoS, RecvTimeoutError,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Publishes waiting to be sent. Once the queue is full, further publishes are
+// dropped rather than holding back the telemetry client.
+const QUEUE_CAPACITY: usize = 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+// How often the background thread checks for shutdown when the connection is
+// idle. Long enough not to cut off a connection attempt.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// How long `finish` waits for the queued publishes to be sent and acknowledged.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Publishes decoded packets as JSON to an MQTT broker, on the topics
+//
+// - `f1/<session_uid>/car/<car_index>/telemetry`, `status`, `motion`, `lap`
+//   and `damage`, with the car's entry of the per-car packets;
+// - `f1/<session_uid>/event/<code>`, with the event, e.g. `event/FTLP`.
+//
+// Connection errors are returned by the next call to `push` or `finish`; the
+// connection is retried in the background.
+pub struct MqttPublisher {
+    client: Client,
+    queued: Arc<AtomicU64>,
+    errors: Receiver<Error>,
+    shutdown_handle: ShutdownHandle,
+    thread: Option<JoinHandle<()>>,
+    qos: QoS,
+    min_interval: Option<Duration>,
+    published: HashMap<String, Instant>,
+    session_uid: u64,
+    num_active_cars: usize,
+}
+
+impl MqttPublisher {
+    pub fn connect(client_id: &str, host: &str, port: u16) -> Self {
+        let (client, connection) =
+            Client::new(MqttOptions::new(client_id, host, port), QUEUE_CAPACITY);
+        let (error_sender, errors) = mpsc::channel::<Error>();
+        let queued: Arc<AtomicU64> = Arc::default();
+        let shutdown_handle: ShutdownHandle = ShutdownHandle::default();
+        let thread: JoinHandle<()> = {
+            let client: Client = client.clone();
+            let queued: Arc<AtomicU64> = queued.clone();
+            let shutdown_handle: ShutdownHandle = shutdown_handle.clone();
+            thread::spawn(move || {
+                poll(
+                    connection,
+                    &client,
+                    &queued,
+                    &error_sender,
+                    &shutdown_handle,
+                )
+            })
+        };
+
+        MqttPublisher {
+            client,
+            queued,
+            errors,
+            shutdown_handle,
+            thread: Some(thread),
+            qos: QoS::AtMostOnce,
+            min_interval: None,
+            published: HashMap::new(),
+            session_uid: 0,
+            num_active_cars: NUM_CARS,
+        }
+    }
+
+    pub fn set_qos(&mut self, qos: QoS) {
+        self.qos = qos;
+    }
+
+    // The most messages per second to publish on each topic, or `None` to
+    // publish every packet.
+    pub fn set_max_rate(&mut self, max_rate: Option<f64>) {
+        self.min_interval =
+            max_rate.and_then(|max_rate| Duration::try_from_secs_f64(1.0 / max_rate).ok());
+    }
+
+    pub fn push(&mut self, packet: &Packet) -> Result<(), Error> {
+        let header: PacketHeader = packet.header();
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.num_active_cars = NUM_CARS;
+            self.published.clear();
+        }
+        match packet {
+            Packet::Participants(packet) => {
+                self.num_active_cars = (packet.num_active_cars as usize).min(NUM_CARS)
+            }
+            Packet::CarTelemetry(packet) => {
+                self.publish_cars("telemetry", &packet.car_telemetry_data)?
+            }
+            Packet::CarStatus(packet) => self.publish_cars("status", &packet.car_status_data)?,
+            Packet::Motion(packet) => self.publish_cars("motion", &packet.car_motion_data)?,
+            Packet::Lap(packet) => self.publish_cars("lap", &packet.lap_data)?,
+            Packet::CarDamage(packet) => self.publish_cars("damage", &packet.car_damage_data)?,
+            Packet::Event(packet) => {
+                let code: [u8; 4] = packet.event.code();
+                // Topics are built from the code, so skip codes that aren't plain names.
+                if code.iter().all(u8::is_ascii_alphanumeric) {
+                    let topic: String = format!(
+                        "f1/{}/event/{}",
+                        self.session_uid,
+                        String::from_utf8_lossy(&code)
+                    );
+                    self.publish(topic, &packet.event)?;
+                }
+            }
+            _ => {}
+        }
+
+        self.check_errors()
+    }
+
+    // Disconnects once the queued publishes have been sent and, at QoS 1 and
+    // 2, acknowledged, or after `DISCONNECT_TIMEOUT`.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.shutdown_handle.shutdown();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+
+        self.check_errors()
+    }
+
+    fn publish_cars<T: Serialize>(&mut self, name: &str, cars: &[T]) -> Result<(), Error> {
+        for (car_index, car) in cars.iter().enumerate().take(self.num_active_cars) {
+            let topic: String = format!("f1/{}/car/{car_index}/{name}", self.session_uid);
+            self.publish(topic, car)?;
+        }
+
+        Ok(())
+    }
+
+    fn publish(&mut self, topic: String, value: &impl Serialize) -> Result<(), Error> {
+        if let Some(min_interval) = self.min_interval {
+            let now: Instant = Instant::now();
+            match self.published.get(&topic) {
+                Some(published) if now.duration_since(*published) < min_interval => return Ok(()),
+                _ => {
+                    self.published.insert(topic.clone(), now);
+                }
+            }
+        }
+        let payload: Vec<u8> = serde_json::to_vec(value).map_err(std::io::Error::from)?;
+        match self.client.try_publish(topic, self.qos, false, payload) {
+            Ok(()) => {
+                self.queued.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(ClientError::TryRequest(_)) => Ok(()),
+            Err(e) => Err(Error::Mqtt(Box::new(e))),
+        }
+    }
+
+    fn check_errors(&self) -> Result<(), Error> {
+        match self.errors.try_recv() {
+            Ok(e) => Err(e),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            self.shutdown_handle.shutdown();
+        }
+    }
+}
+
+// Drives the connection. On shutdown, the disconnect is only requested once
+// every queued publish has been sent and no acknowledgement is outstanding, so
+// that it can't be held up behind a full queue or cut off acknowledgements.
+fn poll(
+    mut connection: Connection,
+    client: &Client,
+    queued: &AtomicU64,
+    errors: &mpsc::Sender<Error>,
+    shutdown_handle: &ShutdownHandle,
+) {
+    // Packet ids of the publishes waiting for a PUBACK or PUBCOMP.
+    let mut outstanding: HashSet<u16> = HashSet::new();
+    let mut sent: u64 = 0;
+    let mut deadline: Option<Instant> = None;
+    let mut disconnecting: bool = false;
+    loop {
+        if shutdown_handle.is_shutdown() {
+            let deadline: Instant =
+                *deadline.get_or_insert_with(|| Instant::now() + DISCONNECT_TIMEOUT);
+            if Instant::now() >= deadline {
+                return;
+            }
+            if !disconnecting && sent >= queued.load(Ordering::Relaxed) && outstanding.is_empty() {
+                disconnecting = client.try_disconnect().is_ok();
+            }
+        }
+        match connection.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(rumqttc::Event::Outgoing(Outgoing::Disconnect))) => return,
+            // Publishes sent again after a reconnect are already outstanding.
+            Ok(Ok(rumqttc::Event::Outgoing(Outgoing::Publish(pkid)))) => {
+                if pkid == 0 || outstanding.insert(pkid) {
+                    sent += 1;
+                }
+            }
+            Ok(Ok(rumqttc::Event::Incoming(
+                rumqttc::Packet::PubAck(PubAck { pkid, .. })
+                | rumqttc::Packet::PubComp(PubComp { pkid, .. }),
+            ))) => {
+                outstanding.remove(&pkid);
+            }
+            Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+            Ok(Err(e)) => {
+                let _ = errors.send(Error::Mqtt(Box::new(e)));
+                if shutdown_handle.is_shutdown() {
+                    return;
+                }
+                thread::sleep(RECONNECT_DELAY);
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{
+        Event, FastestLap, PacketCarTelemetryData, PacketEventData, PacketParticipantsData,
+    };
+    use crate::test_support::header;
+    use serde_json::Value;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    // Topic, QoS and payload of each publish.
+    type Published = Vec<(String, u8, Value)>;
+
+    fn telemetry(frame_identifier: u32, speed: u16) -> Packet {
+        let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: header(6, 7, frame_identifier),
+            ..PacketCarTelemetryData::default()
+        };
+        packet.car_telemetry_data[1].speed = speed;
+        Packet::CarTelemetry(packet)
+    }
+
+    fn participants() -> Packet {
+        Packet::Participants(PacketParticipantsData {
+            header: header(4, 7, 0),
+            num_active_cars: 2,
+            ..PacketParticipantsData::default()
+        })
+    }
+
+    fn fastest_lap() -> Packet {
+        Packet::Event(PacketEventData {
+            header: header(3, 7, 0),
+            event: Event::FastestLap(FastestLap {
+                vehicle_idx: 1,
+                lap_time: 80.5,
+            }),
+        })
+    }
+
+    fn read_packet(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+        let mut byte: [u8; 1] = [0];
+        stream.read_exact(&mut byte).unwrap();
+        let packet_type: u8 = byte[0];
+        let (mut length, mut shift): (usize, u32) = (0, 0);
+        loop {
+            stream.read_exact(&mut byte).unwrap();
+            length |= ((byte[0] & 0x7f) as usize) << shift;
+            shift += 7;
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut body: Vec<u8> = vec![0; length];
+        stream.read_exact(&mut body).unwrap();
+        (packet_type, body)
+    }
+
+    // Accepts one connection, acknowledges it and every QoS 1 and 2 publish,
+    // and returns the publishes on disconnect.
+    fn broker() -> (u16, JoinHandle<Published>) {
+        let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port: u16 = listener.local_addr().unwrap().port();
+        let broker: JoinHandle<Published> = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut published: Published = Vec::new();
+            loop {
+                let (packet_type, body) = read_packet(&mut stream);
+                match packet_type >> 4 {
+                    1 => stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap(),
+                    3 => {
+                        let qos: u8 = (packet_type >> 1) & 0x03;
+                        let topic_length: usize = u16::from_be_bytes([body[0], body[1]]) as usize;
+                        let topic: String =
+                            String::from_utf8(body[2..2 + topic_length].to_vec()).unwrap();
+                        let mut payload: &[u8] = &body[2 + topic_length..];
+                        if qos > 0 {
+                            let ack: u8 = if qos == 1 { 0x40 } else { 0x50 };
+                            stream
+                                .write_all(&[ack, 0x02, payload[0], payload[1]])
+                                .unwrap();
+                            payload = &payload[2..];
+                        }
+                        published.push((topic, qos, serde_json::from_slice(payload).unwrap()));
+                    }
+                    6 => stream.write_all(&[0x70, 0x02, body[0], body[1]]).unwrap(),
+                    12 => stream.write_all(&[0xd0, 0x00]).unwrap(),
+                    14 => return published,
+                    _ => {}
+                }
+            }
+        });
+        (port, broker)
+    }
+
+    #[test]
+    fn test_mqtt_publisher_topics() {
+        let (port, broker) = broker();
+        let mut publisher: MqttPublisher = MqttPublisher::connect("test", "127.0.0.1", port);
+        publisher.set_qos(QoS::AtLeastOnce);
+
+        for packet in [participants(), telemetry(1, 300), fastest_lap()] {
+            publisher.push(&packet).unwrap();
+        }
+        publisher.finish().unwrap();
+
+        let published: Published = broker.join().unwrap();
+        let topics: Vec<&str> = published
+            .iter()
+            .map(|(topic, _, _)| topic.as_str())
+            .collect();
+        assert_eq!(
+            topics,
+            vec![
+                "f1/7/car/0/telemetry",
+                "f1/7/car/1/telemetry",
+                "f1/7/event/FTLP"
+            ]
+        );
+        assert!(published.iter().all(|(_, qos, _)| *qos == 1));
+        assert_eq!(published[1].2["speed"], 300);
+        assert_eq!(published[2].2["type"], "FastestLap");
+        assert_eq!(published[2].2["details"]["vehicle_idx"], 1);
+    }
+
+    #[test]
+    fn test_mqtt_publisher_finish_waits_for_acknowledgements() {
+        let (port, broker) = broker();
+        let mut publisher: MqttPublisher = MqttPublisher::connect("test", "127.0.0.1", port);
+        publisher.set_qos(QoS::ExactlyOnce);
+
+        publisher.push(&participants()).unwrap();
+        for frame_identifier in 0..50 {
+            publisher.push(&telemetry(frame_identifier, 300)).unwrap();
+        }
+        publisher.finish().unwrap();
+
+        let published: Published = broker.join().unwrap();
+        assert_eq!(published.len(), 100);
+        assert!(published.iter().all(|(_, qos, _)| *qos == 2));
+    }
+
+    #[test]
+    fn test_mqtt_publisher_rate_limit() {
+        let (port, broker) = broker();
+        let mut publisher: MqttPublisher = MqttPublisher::connect("test", "127.0.0.1", port);
+        publisher.set_max_rate(Some(1.0));
+
+        for packet in [
+            participants(),
+            telemetry(1, 300),
+            telemetry(2, 310),
+            fastest_lap(),
+        ] {
+            publisher.push(&packet).unwrap();
+        }
+        publisher.finish().unwrap();
+
+        let published: Published = broker.join().unwrap();
+        assert_eq!(published.len(), 3);
+        assert!(published.iter().all(|(_, qos, _)| *qos == 0));
+        assert_eq!(published[1].2["speed"], 300);
+    }
+}
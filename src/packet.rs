@@ -0,0 +1,690 @@
+use crate::packets::v2021;
+use crate::packets::v2022;
+use crate::packets::v2023;
+use crate::packets::PacketCarDamageData;
+use crate::packets::PacketCarSetupData;
+use crate::packets::PacketCarStatusData;
+use crate::packets::PacketCarTelemetryData;
+use crate::packets::PacketEventData;
+use crate::packets::PacketFinalClassificationData;
+use crate::packets::PacketHeader;
+use crate::packets::PacketLapData;
+use crate::packets::PacketLobbyInfoData;
+use crate::packets::PacketMotionData;
+use crate::packets::PacketMotionExData;
+use crate::packets::PacketParticipantsData;
+use crate::packets::PacketSessionData;
+use crate::packets::PacketSessionHistoryData;
+use crate::packets::PacketTimeTrialData;
+use crate::packets::PacketTyreSetsData;
+use crate::Error;
+use std::io::ErrorKind;
+use std::mem::size_of;
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Packet {
+    Motion(PacketMotionData),                           // Packet ID 0
+    Session(PacketSessionData),                         // Packet ID 1
+    Lap(PacketLapData),                                 // Packet ID 2
+    Event(PacketEventData),                             // Packet ID 3
+    Participants(PacketParticipantsData),               // Packet ID 4
+    CarSetup(PacketCarSetupData),                       // Packet ID 5
+    CarTelemetry(PacketCarTelemetryData),               // Packet ID 6
+    CarStatus(PacketCarStatusData),                     // Packet ID 7
+    FinalClassification(PacketFinalClassificationData), // Packet ID 8
+    LobbyInfo(PacketLobbyInfoData),                     // Packet ID 9
+    CarDamage(PacketCarDamageData),                     // Packet ID 10
+    SessionHistory(PacketSessionHistoryData),           // Packet ID 11
+    TyreSets(PacketTyreSetsData),                       // Packet ID 12
+    MotionEx(PacketMotionExData),                       // Packet ID 13
+    TimeTrial(PacketTimeTrialData),                     // Packet ID 14
+}
+
+impl Packet {
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let header: PacketHeader =
+            PacketHeader::unserialize(bytes).map_err(|_| Error::TruncatedPacket {
+                expected: size_of::<PacketHeader>(),
+                actual: bytes.len(),
+            })?;
+        let packet_format: u16 = header.packet_format;
+        if matches!(packet_format, 2021 | 2022) {
+            return Packet::decode_legacy(packet_format, bytes);
+        }
+        if !matches!(packet_format, 2023 | 2024) {
+            return Err(Error::UnsupportedPacketFormat(packet_format));
+        }
+
+        let packet_id: u8 = header.packet_id;
+        let packet: Packet = match (packet_format, packet_id) {
+            (2023, 1) => Packet::Session(
+                decode(packet_id, bytes, v2023::PacketSessionData::unserialize)?.into(),
+            ),
+            (2023, 2) => {
+                Packet::Lap(decode(packet_id, bytes, v2023::PacketLapData::unserialize)?.into())
+            }
+            (2023, 4) => Packet::Participants(
+                decode(packet_id, bytes, v2023::PacketParticipantsData::unserialize)?.into(),
+            ),
+            (2023, 5) => Packet::CarSetup(
+                decode(packet_id, bytes, v2023::PacketCarSetupData::unserialize)?.into(),
+            ),
+            (2023, 9) => Packet::LobbyInfo(
+                decode(packet_id, bytes, v2023::PacketLobbyInfoData::unserialize)?.into(),
+            ),
+            (2023, 13) => Packet::MotionEx(
+                decode(packet_id, bytes, v2023::PacketMotionExData::unserialize)?.into(),
+            ),
+            (_, 0) => Packet::Motion(decode(packet_id, bytes, PacketMotionData::unserialize)?),
+            (_, 1) => Packet::Session(decode(packet_id, bytes, PacketSessionData::unserialize)?),
+            (_, 2) => Packet::Lap(decode(packet_id, bytes, PacketLapData::unserialize)?),
+            (_, 3) => Packet::Event(decode(packet_id, bytes, PacketEventData::unserialize)?),
+            (_, 4) => Packet::Participants(decode(
+                packet_id,
+                bytes,
+                PacketParticipantsData::unserialize,
+            )?),
+            (_, 5) => Packet::CarSetup(decode(packet_id, bytes, PacketCarSetupData::unserialize)?),
+            (_, 6) => Packet::CarTelemetry(decode(
+                packet_id,
+                bytes,
+                PacketCarTelemetryData::unserialize,
+            )?),
+            (_, 7) => {
+                Packet::CarStatus(decode(packet_id, bytes, PacketCarStatusData::unserialize)?)
+            }
+            (_, 8) => Packet::FinalClassification(decode(
+                packet_id,
+                bytes,
+                PacketFinalClassificationData::unserialize,
+            )?),
+            (_, 9) => {
+                Packet::LobbyInfo(decode(packet_id, bytes, PacketLobbyInfoData::unserialize)?)
+            }
+            (_, 10) => {
+                Packet::CarDamage(decode(packet_id, bytes, PacketCarDamageData::unserialize)?)
+            }
+            (_, 11) => Packet::SessionHistory(decode(
+                packet_id,
+                bytes,
+                PacketSessionHistoryData::unserialize,
+            )?),
+            (_, 12) => Packet::TyreSets(decode(packet_id, bytes, PacketTyreSetsData::unserialize)?),
+            (_, 13) => Packet::MotionEx(decode(packet_id, bytes, PacketMotionExData::unserialize)?),
+            (2024, 14) => {
+                Packet::TimeTrial(decode(packet_id, bytes, PacketTimeTrialData::unserialize)?)
+            }
+            _ => return Err(Error::UnknownPacketId(packet_id)),
+        };
+
+        Ok(packet)
+    }
+
+    // F1 21 and F1 22 motion packets also carry the player car's extended
+    // motion data, which later formats send as a packet of its own. Returns it
+    // as a motion ex packet, or None for any other datagram.
+    pub fn decode_motion_ex(bytes: &[u8]) -> Option<Self> {
+        let header: v2022::PacketHeader = v2022::PacketHeader::unserialize(bytes).ok()?;
+        if !matches!({ header.packet_format }, 2021 | 2022) || header.packet_id != 0 {
+            return None;
+        }
+        let packet: v2022::PacketMotionData = v2022::PacketMotionData::unserialize(bytes).ok()?;
+
+        Some(Packet::MotionEx(packet.into()))
+    }
+
+    fn decode_legacy(packet_format: u16, bytes: &[u8]) -> Result<Self, Error> {
+        let header: v2022::PacketHeader = v2022::PacketHeader::unserialize(bytes)?;
+        let packet_id: u8 = header.packet_id;
+        let packet: Packet = match (packet_format, packet_id) {
+            (2021, 1) => Packet::Session(
+                decode(packet_id, bytes, v2021::PacketSessionData::unserialize)?.into(),
+            ),
+            (2021, 2) => {
+                Packet::Lap(decode(packet_id, bytes, v2021::PacketLapData::unserialize)?.into())
+            }
+            (2021, 3) => {
+                Packet::Event(decode(packet_id, bytes, v2021::PacketEventData::unserialize)?.into())
+            }
+            (2021, 8) => Packet::FinalClassification(
+                decode(
+                    packet_id,
+                    bytes,
+                    v2021::PacketFinalClassificationData::unserialize,
+                )?
+                .into(),
+            ),
+            (2021, 10) => Packet::CarDamage(
+                decode(packet_id, bytes, v2021::PacketCarDamageData::unserialize)?.into(),
+            ),
+            (_, 0) => Packet::Motion(
+                decode(packet_id, bytes, v2022::PacketMotionData::unserialize)?.into(),
+            ),
+            (_, 1) => Packet::Session(
+                decode(packet_id, bytes, v2022::PacketSessionData::unserialize)?.into(),
+            ),
+            (_, 2) => {
+                Packet::Lap(decode(packet_id, bytes, v2022::PacketLapData::unserialize)?.into())
+            }
+            (_, 3) => {
+                Packet::Event(decode(packet_id, bytes, v2022::PacketEventData::unserialize)?.into())
+            }
+            (_, 4) => Packet::Participants(
+                decode(packet_id, bytes, v2022::PacketParticipantsData::unserialize)?.into(),
+            ),
+            (_, 5) => Packet::CarSetup(
+                decode(packet_id, bytes, v2022::PacketCarSetupData::unserialize)?.into(),
+            ),
+            (_, 6) => Packet::CarTelemetry(
+                decode(packet_id, bytes, v2022::PacketCarTelemetryData::unserialize)?.into(),
+            ),
+            (_, 7) => Packet::CarStatus(
+                decode(packet_id, bytes, v2022::PacketCarStatusData::unserialize)?.into(),
+            ),
+            (_, 8) => Packet::FinalClassification(
+                decode(
+                    packet_id,
+                    bytes,
+                    v2022::PacketFinalClassificationData::unserialize,
+                )?
+                .into(),
+            ),
+            (_, 9) => Packet::LobbyInfo(
+                decode(packet_id, bytes, v2022::PacketLobbyInfoData::unserialize)?.into(),
+            ),
+            (_, 10) => Packet::CarDamage(
+                decode(packet_id, bytes, v2022::PacketCarDamageData::unserialize)?.into(),
+            ),
+            (_, 11) => Packet::SessionHistory(
+                decode(
+                    packet_id,
+                    bytes,
+                    v2022::PacketSessionHistoryData::unserialize,
+                )?
+                .into(),
+            ),
+            _ => return Err(Error::UnknownPacketId(packet_id)),
+        };
+
+        Ok(packet)
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let bytes: Vec<u8> = match (self.header().packet_format, self) {
+            (2021, Packet::Session(packet)) => {
+                v2021::PacketSessionData::from(*packet).serialize()?
+            }
+            (2021, Packet::Lap(packet)) => v2021::PacketLapData::from(*packet).serialize()?,
+            (2021, Packet::Event(packet)) => v2021::PacketEventData::from(*packet).serialize()?,
+            (2021, Packet::FinalClassification(packet)) => {
+                v2021::PacketFinalClassificationData::from(*packet).serialize()?
+            }
+            (2021, Packet::CarDamage(packet)) => {
+                v2021::PacketCarDamageData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::Motion(packet)) => {
+                v2022::PacketMotionData::from(*packet).serialize()?
+            }
+            (2022, Packet::Session(packet)) => {
+                v2022::PacketSessionData::from(*packet).serialize()?
+            }
+            (2022, Packet::Lap(packet)) => v2022::PacketLapData::from(*packet).serialize()?,
+            (2022, Packet::Event(packet)) => v2022::PacketEventData::from(*packet).serialize()?,
+            (2021 | 2022, Packet::Participants(packet)) => {
+                v2022::PacketParticipantsData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::CarSetup(packet)) => {
+                v2022::PacketCarSetupData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::CarTelemetry(packet)) => {
+                v2022::PacketCarTelemetryData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::CarStatus(packet)) => {
+                v2022::PacketCarStatusData::from(*packet).serialize()?
+            }
+            (2022, Packet::FinalClassification(packet)) => {
+                v2022::PacketFinalClassificationData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::LobbyInfo(packet)) => {
+                v2022::PacketLobbyInfoData::from(*packet).serialize()?
+            }
+            (2022, Packet::CarDamage(packet)) => {
+                v2022::PacketCarDamageData::from(*packet).serialize()?
+            }
+            (2021 | 2022, Packet::SessionHistory(packet)) => {
+                v2022::PacketSessionHistoryData::from(*packet).serialize()?
+            }
+            (2021 | 2022, _) => return Err(Error::UnknownPacketId(self.header().packet_id)),
+            (2023, Packet::Session(packet)) => {
+                v2023::PacketSessionData::from(*packet).serialize()?
+            }
+            (2023, Packet::Lap(packet)) => v2023::PacketLapData::from(*packet).serialize()?,
+            (2023, Packet::Participants(packet)) => {
+                v2023::PacketParticipantsData::from(*packet).serialize()?
+            }
+            (2023, Packet::CarSetup(packet)) => {
+                v2023::PacketCarSetupData::from(*packet).serialize()?
+            }
+            (2023, Packet::LobbyInfo(packet)) => {
+                v2023::PacketLobbyInfoData::from(*packet).serialize()?
+            }
+            (2023, Packet::MotionEx(packet)) => {
+                v2023::PacketMotionExData::from(*packet).serialize()?
+            }
+            (_, Packet::Motion(packet)) => packet.serialize()?,
+            (_, Packet::Session(packet)) => packet.serialize()?,
+            (_, Packet::Lap(packet)) => packet.serialize()?,
+            (_, Packet::Event(packet)) => packet.serialize()?,
+            (_, Packet::Participants(packet)) => packet.serialize()?,
+            (_, Packet::CarSetup(packet)) => packet.serialize()?,
+            (_, Packet::CarTelemetry(packet)) => packet.serialize()?,
+            (_, Packet::CarStatus(packet)) => packet.serialize()?,
+            (_, Packet::FinalClassification(packet)) => packet.serialize()?,
+            (_, Packet::LobbyInfo(packet)) => packet.serialize()?,
+            (_, Packet::CarDamage(packet)) => packet.serialize()?,
+            (_, Packet::SessionHistory(packet)) => packet.serialize()?,
+            (_, Packet::TyreSets(packet)) => packet.serialize()?,
+            (_, Packet::MotionEx(packet)) => packet.serialize()?,
+            (_, Packet::TimeTrial(packet)) => packet.serialize()?,
+        };
+
+        Ok(bytes)
+    }
+
+    pub fn header(&self) -> PacketHeader {
+        match self {
+            Packet::Motion(packet) => packet.header,
+            Packet::Session(packet) => packet.header,
+            Packet::Lap(packet) => packet.header,
+            Packet::Event(packet) => packet.header,
+            Packet::Participants(packet) => packet.header,
+            Packet::CarSetup(packet) => packet.header,
+            Packet::CarTelemetry(packet) => packet.header,
+            Packet::CarStatus(packet) => packet.header,
+            Packet::FinalClassification(packet) => packet.header,
+            Packet::LobbyInfo(packet) => packet.header,
+            Packet::CarDamage(packet) => packet.header,
+            Packet::SessionHistory(packet) => packet.header,
+            Packet::TyreSets(packet) => packet.header,
+            Packet::MotionEx(packet) => packet.header,
+            Packet::TimeTrial(packet) => packet.header,
+        }
+    }
+}
+
+fn decode<T>(
+    packet_id: u8,
+    bytes: &[u8],
+    unserialize: fn(&[u8]) -> Result<T, Error>,
+) -> Result<T, Error> {
+    // The decoders read from cursors over the datagram, and a cursor that runs
+    // out of bytes is left at the end of them, so that is where decoding failed.
+    unserialize(bytes).map_err(|e| match e {
+        Error::TruncatedPacket { expected, actual } => Error::Decode {
+            packet_id,
+            offset: actual,
+            source: std::io::Error::new(
+                ErrorKind::UnexpectedEof,
+                format!("expected {expected} bytes"),
+            ),
+        },
+        Error::Io(source) => Error::Decode {
+            packet_id,
+            offset: bytes.len(),
+            source,
+        },
+        e => e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::Event;
+    #[cfg(feature = "serde")]
+    use crate::packets::{FastestLap, ParticipantData};
+
+    fn header(packet_format: u16, packet_id: u8) -> PacketHeader {
+        PacketHeader {
+            packet_format,
+            packet_id,
+            ..PacketHeader::default()
+        }
+    }
+
+    fn packets(packet_format: u16) -> Vec<Packet> {
+        vec![
+            Packet::Motion(PacketMotionData {
+                header: header(packet_format, 0),
+                ..PacketMotionData::default()
+            }),
+            Packet::Session(PacketSessionData {
+                header: header(packet_format, 1),
+                ..PacketSessionData::default()
+            }),
+            Packet::Lap(PacketLapData {
+                header: header(packet_format, 2),
+                ..PacketLapData::default()
+            }),
+            Packet::Event(PacketEventData {
+                header: header(packet_format, 3),
+                event: Event::SessionStarted,
+            }),
+            Packet::Participants(PacketParticipantsData {
+                header: header(packet_format, 4),
+                ..PacketParticipantsData::default()
+            }),
+            Packet::CarSetup(PacketCarSetupData {
+                header: header(packet_format, 5),
+                ..PacketCarSetupData::default()
+            }),
+            Packet::CarTelemetry(PacketCarTelemetryData {
+                header: header(packet_format, 6),
+                ..PacketCarTelemetryData::default()
+            }),
+            Packet::CarStatus(PacketCarStatusData {
+                header: header(packet_format, 7),
+                ..PacketCarStatusData::default()
+            }),
+            Packet::FinalClassification(PacketFinalClassificationData {
+                header: header(packet_format, 8),
+                ..PacketFinalClassificationData::default()
+            }),
+            Packet::LobbyInfo(PacketLobbyInfoData {
+                header: header(packet_format, 9),
+                ..PacketLobbyInfoData::default()
+            }),
+            Packet::CarDamage(PacketCarDamageData {
+                header: header(packet_format, 10),
+                ..PacketCarDamageData::default()
+            }),
+            Packet::SessionHistory(PacketSessionHistoryData {
+                header: header(packet_format, 11),
+                ..PacketSessionHistoryData::default()
+            }),
+            Packet::TyreSets(PacketTyreSetsData {
+                header: header(packet_format, 12),
+                ..PacketTyreSetsData::default()
+            }),
+            Packet::MotionEx(PacketMotionExData {
+                header: header(packet_format, 13),
+                ..PacketMotionExData::default()
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_packet_encode_decode_2023() {
+        for original_packet in packets(2023) {
+            let encoded_packet: Vec<u8> = original_packet.encode().unwrap();
+            let decoded_packet: Packet = Packet::decode(&encoded_packet).unwrap();
+
+            assert_eq!(original_packet, decoded_packet);
+            assert_eq!(original_packet.header(), decoded_packet.header());
+        }
+    }
+
+    #[test]
+    fn test_packet_encode_decode_2024() {
+        let mut original_packets: Vec<Packet> = packets(2024);
+        original_packets.push(Packet::TimeTrial(PacketTimeTrialData {
+            header: header(2024, 14),
+            ..PacketTimeTrialData::default()
+        }));
+
+        for original_packet in original_packets {
+            let encoded_packet: Vec<u8> = original_packet.encode().unwrap();
+            let decoded_packet: Packet = Packet::decode(&encoded_packet).unwrap();
+
+            assert_eq!(original_packet, decoded_packet);
+            assert_eq!(original_packet.header(), decoded_packet.header());
+        }
+    }
+
+    #[test]
+    fn test_packet_encode_decode_2022() {
+        for original_packet in packets(2022).into_iter().take(12) {
+            let encoded_packet: Vec<u8> = original_packet.encode().unwrap();
+            let decoded_packet: Packet = Packet::decode(&encoded_packet).unwrap();
+
+            assert_eq!(original_packet, decoded_packet);
+            assert_eq!(original_packet.header(), decoded_packet.header());
+        }
+    }
+
+    #[test]
+    fn test_packet_encode_decode_2021() {
+        for original_packet in packets(2021).into_iter().take(12) {
+            let encoded_packet: Vec<u8> = original_packet.encode().unwrap();
+            let decoded_packet: Packet = Packet::decode(&encoded_packet).unwrap();
+
+            assert_eq!(original_packet, decoded_packet);
+            assert_eq!(original_packet.header(), decoded_packet.header());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_packet_serde_round_trip() {
+        let mut original_packets: Vec<Packet> = packets(2024);
+        original_packets.push(Packet::TimeTrial(PacketTimeTrialData {
+            header: header(2024, 14),
+            ..PacketTimeTrialData::default()
+        }));
+        for event in [
+            Event::FastestLap(FastestLap {
+                vehicle_idx: 3,
+                lap_time: 81.25,
+            }),
+            Event::Unknown(*b"ABCD", [1; 12]),
+        ] {
+            original_packets.push(Packet::Event(PacketEventData {
+                header: header(2024, 3),
+                event,
+            }));
+        }
+
+        for original_packet in original_packets {
+            let json: String = serde_json::to_string(&original_packet).unwrap();
+            let deserialized_packet: Packet = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(original_packet, deserialized_packet);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_packet_serde_representation() {
+        let mut participants: PacketParticipantsData = PacketParticipantsData {
+            header: header(2024, 4),
+            ..PacketParticipantsData::default()
+        };
+        participants.participants[0].name[..8].copy_from_slice(b"HAMILTON");
+        let event: Packet = Packet::Event(PacketEventData {
+            header: header(2024, 3),
+            event: Event::FastestLap(FastestLap {
+                vehicle_idx: 3,
+                lap_time: 81.25,
+            }),
+        });
+
+        let participants: serde_json::Value =
+            serde_json::to_value(Packet::Participants(participants)).unwrap();
+        let event: serde_json::Value = serde_json::to_value(event).unwrap();
+        let unknown: serde_json::Value =
+            serde_json::to_value(Event::Unknown(*b"ABCD", [0; 12])).unwrap();
+
+        assert_eq!(participants["type"], "Participants");
+        assert_eq!(participants["header"]["packet_id"], 4);
+        assert_eq!(participants["participants"][0]["name"], "HAMILTON");
+        assert_eq!(participants["participants"][1]["name"], "");
+        assert_eq!(event["type"], "Event");
+        assert_eq!(
+            event["event"],
+            serde_json::json!({"type": "FastestLap", "details": {"vehicle_idx": 3, "lap_time": 81.25}})
+        );
+        assert_eq!(unknown["details"][0], "ABCD");
+        assert!(
+            serde_json::from_value::<ParticipantData>(serde_json::json!({
+                "ai_controlled": 0, "driver_id": 0, "network_id": 0, "team_id": 0, "my_team": 0,
+                "race_number": 0, "nationality": 0, "name": "X".repeat(49), "your_telemetry": 0,
+                "show_online_names": 0, "tech_level": 0, "platform": 0
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_packet_encode_legacy_unknown_packet_id() {
+        for packet_format in [2021, 2022] {
+            let packet: Packet = packets(packet_format)[12];
+
+            assert!(matches!(packet.encode(), Err(Error::UnknownPacketId(12))));
+        }
+    }
+
+    #[test]
+    fn test_packet_decode_truncated_header() {
+        assert!(matches!(
+            Packet::decode(&[0u8; 10]),
+            Err(Error::TruncatedPacket {
+                expected: 29,
+                actual: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn test_packet_decode_truncated_body() {
+        let bytes: Vec<u8> = header(2024, 0).serialize().unwrap();
+
+        assert!(matches!(
+            Packet::decode(&bytes),
+            Err(Error::Decode {
+                packet_id: 0,
+                offset: 29,
+                ref source,
+            }) if source.kind() == ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_packet_decode_unsupported_packet_format() {
+        let bytes: Vec<u8> = PacketHeader {
+            packet_format: 2019,
+            ..header(2024, 0)
+        }
+        .serialize()
+        .unwrap();
+
+        assert!(matches!(
+            Packet::decode(&bytes),
+            Err(Error::UnsupportedPacketFormat(2019))
+        ));
+    }
+
+    #[test]
+    fn test_packet_decode_unknown_packet_id() {
+        let bytes: Vec<u8> = header(2024, 42).serialize().unwrap();
+
+        assert!(matches!(
+            Packet::decode(&bytes),
+            Err(Error::UnknownPacketId(42))
+        ));
+    }
+
+    #[test]
+    fn test_packet_decode_2023_layout() {
+        let bytes: Vec<u8> = v2023::PacketLapData {
+            header: header(2023, 2),
+            ..v2023::PacketLapData::default()
+        }
+        .serialize()
+        .unwrap();
+
+        assert_eq!(bytes.len(), 1131);
+        assert!(matches!(Packet::decode(&bytes), Ok(Packet::Lap(_))));
+    }
+
+    #[test]
+    fn test_packet_decode_legacy_layout() {
+        let bytes: Vec<u8> = v2022::PacketLapData {
+            header: v2022::PacketHeader {
+                packet_format: 2022,
+                packet_id: 2,
+                ..v2022::PacketHeader::default()
+            },
+            ..v2022::PacketLapData::default()
+        }
+        .serialize()
+        .unwrap();
+
+        assert_eq!(bytes.len(), 972);
+        assert!(matches!(Packet::decode(&bytes), Ok(Packet::Lap(_))));
+
+        let bytes: Vec<u8> = v2021::PacketLapData {
+            header: v2022::PacketHeader {
+                packet_format: 2021,
+                packet_id: 2,
+                ..v2022::PacketHeader::default()
+            },
+            ..v2021::PacketLapData::default()
+        }
+        .serialize()
+        .unwrap();
+
+        assert_eq!(bytes.len(), 970);
+        assert!(matches!(Packet::decode(&bytes), Ok(Packet::Lap(_))));
+    }
+
+    #[test]
+    fn test_packet_decode_motion_ex_legacy() {
+        let bytes: Vec<u8> = v2022::PacketMotionData {
+            header: v2022::PacketHeader {
+                packet_format: 2022,
+                packet_id: 0,
+                frame_identifier: 42,
+                ..v2022::PacketHeader::default()
+            },
+            wheel_speed: [1.0, 2.0, 3.0, 4.0],
+            wheel_slip: [0.1, 0.2, 0.3, 0.4],
+            local_velocity_x: 5.0,
+            front_wheels_angle: 0.5,
+            ..v2022::PacketMotionData::default()
+        }
+        .serialize()
+        .unwrap();
+
+        let Some(Packet::MotionEx(packet)) = Packet::decode_motion_ex(&bytes) else {
+            panic!("expected a motion ex packet");
+        };
+        assert_eq!({ packet.header.packet_id }, 13);
+        assert_eq!({ packet.header.frame_identifier }, 42);
+        assert_eq!({ packet.wheel_speed }, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!({ packet.wheel_slip_ratio }, [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!({ packet.local_velocity_x }, 5.0);
+        assert_eq!({ packet.front_wheels_angle }, 0.5);
+
+        for packet in [&packets(2022)[2], &packets(2024)[0]] {
+            assert_eq!(Packet::decode_motion_ex(&packet.encode().unwrap()), None);
+        }
+    }
+
+    #[test]
+    fn test_packet_decode_time_trial_requires_2024() {
+        let bytes: Vec<u8> = PacketTimeTrialData {
+            header: header(2023, 14),
+            ..PacketTimeTrialData::default()
+        }
+        .serialize()
+        .unwrap();
+
+        assert!(matches!(
+            Packet::decode(&bytes),
+            Err(Error::UnknownPacketId(14))
+        ));
+    }
+}
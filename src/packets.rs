@@ -12,8 +12,13 @@ mod packet_motion_ex_data;
 mod packet_participants_data;
 mod packet_session_data;
 mod packet_session_history_data;
+mod packet_time_trial_data;
 mod packet_tyre_sets_data;
 
+pub mod v2021;
+pub mod v2022;
+pub mod v2023;
+
 pub use packet_car_damage_data::*;
 pub use packet_car_setup_data::*;
 pub use packet_car_status_data::*;
@@ -28,4 +33,30 @@ pub use packet_motion_ex_data::*;
 pub use packet_participants_data::*;
 pub use packet_session_data::*;
 pub use packet_session_history_data::*;
+pub use packet_time_trial_data::*;
 pub use packet_tyre_sets_data::*;
+
+// The length of the per-car arrays in the packets.
+pub(crate) const NUM_CARS: usize = 22;
+
+// The lap data of the player, or of the race leader when spectating, in which
+// case the player car index is 255.
+pub(crate) fn followed_lap_data(packet: &PacketLapData) -> Option<&LapData> {
+    match packet.lap_data.get(packet.header.player_car_index as usize) {
+        Some(lap_data) => Some(lap_data),
+        None => packet
+            .lap_data
+            .iter()
+            .find(|lap_data| lap_data.car_position == 1),
+    }
+}
+
+// Decodes a NUL-terminated byte array, such as a driver's name.
+#[cfg(any(feature = "serde", feature = "influxdb"))]
+pub(crate) fn nul_terminated(bytes: &[u8]) -> String {
+    let length: usize = bytes
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..length]).into_owned()
+}
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CarDamageData {
     pub tyres_wear: [f32; 4],        // 16 Bytes
     pub tyres_damage: [u8; 4],       // 4 Bytes
@@ -31,6 +33,7 @@ pub struct CarDamageData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketCarDamageData {
     pub header: PacketHeader,                 // 29 Bytes
     pub car_damage_data: [CarDamageData; 22], // 924 Bytes
@@ -120,7 +123,15 @@ impl CarDamageData {
 
 impl PacketCarDamageData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarDamageData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         Ok(PacketCarDamageData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
             car_damage_data: {
@@ -240,4 +251,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_car_damage_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarDamageData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarDamageData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_damage_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarDamageData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarDamageData::unserialize(&serialized_packet).is_ok());
+    }
 }
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CarSetupData {
     pub front_wing: u8,                 // 1 Byte
     pub rear_wing: u8,                  // 1 Byte
@@ -22,20 +24,23 @@ pub struct CarSetupData {
     pub rear_suspension_height: u8,     // 1 Byte
     pub brake_pressure: u8,             // 1 Byte
     pub brake_bias: u8,                 // 1 Byte
+    pub engine_braking: u8,             // 1 Byte
     pub rear_left_tyre_pressure: f32,   // 4 Bytes
     pub rear_right_tyre_pressure: f32,  // 4 Bytes
     pub front_left_tyre_pressure: f32,  // 4 Bytes
     pub front_right_tyre_pressure: f32, // 4 Bytes
     pub ballast: u8,                    // 1 Byte
     pub fuel_load: f32,                 // 4 Bytes
-} // 49 Bytes
+} // 50 Bytes
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketCarSetupData {
     pub header: PacketHeader,           // 29 Bytes
-    pub car_setups: [CarSetupData; 22], // 1078 Bytes
-} // 1107 Bytes
+    pub car_setups: [CarSetupData; 22], // 1100 Bytes
+    pub next_front_wing_value: f32,     // 4 Bytes
+} // 1133 Bytes
 
 impl CarSetupData {
     #[allow(dead_code)]
@@ -59,6 +64,7 @@ impl CarSetupData {
             rear_suspension_height: cursor.read_u8()?,
             brake_pressure: cursor.read_u8()?,
             brake_bias: cursor.read_u8()?,
+            engine_braking: cursor.read_u8()?,
             rear_left_tyre_pressure: cursor.read_f32::<LittleEndian>()?,
             rear_right_tyre_pressure: cursor.read_f32::<LittleEndian>()?,
             front_left_tyre_pressure: cursor.read_f32::<LittleEndian>()?,
@@ -89,6 +95,7 @@ impl CarSetupData {
         cursor.write_u8(self.rear_suspension_height)?;
         cursor.write_u8(self.brake_pressure)?;
         cursor.write_u8(self.brake_bias)?;
+        cursor.write_u8(self.engine_braking)?;
         cursor.write_f32::<LittleEndian>(self.rear_left_tyre_pressure)?;
         cursor.write_f32::<LittleEndian>(self.rear_right_tyre_pressure)?;
         cursor.write_f32::<LittleEndian>(self.front_left_tyre_pressure)?;
@@ -102,7 +109,17 @@ impl CarSetupData {
 
 impl PacketCarSetupData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarSetupData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
         Ok(PacketCarSetupData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
             car_setups: {
@@ -115,6 +132,11 @@ impl PacketCarSetupData {
                 }
                 car_setups
             },
+            next_front_wing_value: {
+                let pos: usize = size_of::<PacketHeader>() + size_of::<[CarSetupData; 22]>();
+                cursor.set_position(pos as u64);
+                cursor.read_f32::<LittleEndian>()?
+            },
         })
     }
 
@@ -127,6 +149,7 @@ impl PacketCarSetupData {
         for element in self.car_setups {
             cursor.write_all(&element.serialize()?)?;
         }
+        cursor.write_f32::<LittleEndian>(self.next_front_wing_value)?;
 
         Ok(bytes)
     }
@@ -158,6 +181,7 @@ mod tests {
             rear_suspension_height: rng.gen(),
             brake_pressure: rng.gen(),
             brake_bias: rng.gen(),
+            engine_braking: rng.gen(),
             rear_left_tyre_pressure: rng.gen(),
             rear_right_tyre_pressure: rng.gen(),
             front_left_tyre_pressure: rng.gen(),
@@ -208,6 +232,7 @@ mod tests {
                 rear_suspension_height: rng.gen(),
                 brake_pressure: rng.gen(),
                 brake_bias: rng.gen(),
+                engine_braking: rng.gen(),
                 rear_left_tyre_pressure: rng.gen(),
                 rear_right_tyre_pressure: rng.gen(),
                 front_left_tyre_pressure: rng.gen(),
@@ -215,6 +240,7 @@ mod tests {
                 ballast: rng.gen(),
                 fuel_load: rng.gen(),
             }; 22],
+            next_front_wing_value: rng.gen(),
         };
 
         let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
@@ -223,4 +249,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_car_setup_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarSetupData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarSetupData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_setup_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarSetupData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarSetupData::unserialize(&serialized_packet).is_ok());
+    }
 }
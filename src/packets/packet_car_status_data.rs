@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CarStatusData {
     pub traction_control: u8,             // 1 Byte
     pub anti_lock_brakes: u8,             // 1 Byte
@@ -35,6 +37,7 @@ pub struct CarStatusData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketCarStatusData {
     pub header: PacketHeader,                 // 29 Bytes
     pub car_status_data: [CarStatusData; 22], // 1210 Bytes
@@ -111,7 +114,15 @@ impl CarStatusData {
 
 impl PacketCarStatusData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarStatusData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         Ok(PacketCarStatusData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
             car_status_data: {
@@ -239,4 +250,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_car_status_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarStatusData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarStatusData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_status_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarStatusData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarStatusData::unserialize(&serialized_packet).is_ok());
+    }
 }
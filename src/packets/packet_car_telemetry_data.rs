@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CarTelemetryData {
     pub speed: u16,                         // 2 Bytes
     pub throttle: f32,                      // 4 Bytes
@@ -26,6 +28,7 @@ pub struct CarTelemetryData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketCarTelemetryData {
     pub header: PacketHeader,                       // 29 Bytes
     pub car_telemetry_data: [CarTelemetryData; 22], // 1320 Bytes
@@ -122,8 +125,16 @@ impl CarTelemetryData {
 
 impl PacketCarTelemetryData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
-        let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes);
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarTelemetryData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketCarTelemetryData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
@@ -248,4 +259,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_car_telemetry_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarTelemetryData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarTelemetryData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_telemetry_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarTelemetryData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarTelemetryData::unserialize(&serialized_packet).is_ok());
+    }
 }
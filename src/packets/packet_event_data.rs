@@ -1,28 +1,43 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::fmt;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Write};
 use std::mem::size_of;
 
-#[repr(C, packed)]
-#[derive(Clone, Copy)]
-pub union EventDataDetails {
-    pub fastest_lap: FastestLap,                                 // 5 Bytes
-    pub retirement: Retirement,                                  // 1 Byte
-    pub team_mate_in_pits: TeamMateInPits,                       // 1 Byte
-    pub race_winner: RaceWinner,                                 // 1 Byte
-    pub penalty: Penalty,                                        // 7 Bytes
-    pub speed_trap: SpeedTrap,                                   // 12 Bytes
-    pub start_lights: StartLights,                               // 1 Byte
-    pub drive_through_penalty_served: DriveThroughPenaltyServed, // 1 Byte
-    pub stop_go_penalty_served: StopGoPenaltyServed,             // 1 Byte
-    pub flashback: Flashback,                                    // 8 Bytes
-    pub buttons: Buttons,                                        // 4 Bytes
-    pub overtake: Overtake,                                      // 2 Bytes
-} // 12 Bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "details"))]
+pub enum Event {
+    SessionStarted,
+    SessionEnded,
+    FastestLap(FastestLap),
+    Retirement(Retirement),
+    DrsEnabled,
+    DrsDisabled,
+    TeamMateInPits(TeamMateInPits),
+    ChequeredFlag,
+    RaceWinner(RaceWinner),
+    Penalty(Penalty),
+    SpeedTrap(SpeedTrap),
+    StartLights(StartLights),
+    LightsOut,
+    DriveThroughPenaltyServed(DriveThroughPenaltyServed),
+    StopGoPenaltyServed(StopGoPenaltyServed),
+    Flashback(Flashback),
+    Buttons(Buttons),
+    RedFlag,
+    Overtake(Overtake),
+    SafetyCar(SafetyCar),
+    Collision(Collision),
+    Unknown(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::byte_string"))] [u8; 4],
+        [u8; 12],
+    ),
+}
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FastestLap {
     pub vehicle_idx: u8, // 1 Byte
     pub lap_time: f32,   // 4 Bytes
@@ -30,24 +45,28 @@ pub struct FastestLap {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Retirement {
     pub vehicle_idx: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TeamMateInPits {
     pub vehicle_idx: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RaceWinner {
     pub vehicle_idx: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Penalty {
     pub penalty_type: u8,      // 1 Byte
     pub infringement_type: u8, // 1 Byte
@@ -60,6 +79,7 @@ pub struct Penalty {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpeedTrap {
     pub vehicle_idx: u8,                    // 1 Byte
     pub speed: f32,                         // 4 Bytes
@@ -71,24 +91,28 @@ pub struct SpeedTrap {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StartLights {
     pub num_lights: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DriveThroughPenaltyServed {
     pub vehicle_idx: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StopGoPenaltyServed {
     pub vehicle_idx: u8, // 1 Byte
 } // 1 Byte
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Flashback {
     pub flashback_frame_identifier: u32, // 4 Bytes
     pub flashback_session_time: f32,     // 4 Bytes
@@ -96,24 +120,41 @@ pub struct Flashback {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Buttons {
     pub button_status: u32, // 4 Bytes
 } // 4 Bytes
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Overtake {
     pub overtaking_vehicle_idx: u8,      // 1 Byte
     pub being_overtaken_vehicle_idx: u8, // 1 Byte
 } // 2 Bytes
 
 #[repr(C, packed)]
-#[derive(Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SafetyCar {
+    pub safety_car_type: u8, // 1 Byte
+    pub event_type: u8,      // 1 Byte
+} // 2 Bytes
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Collision {
+    pub vehicle1_idx: u8, // 1 Byte
+    pub vehicle2_idx: u8, // 1 Byte
+} // 2 Bytes
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketEventData {
-    pub header: PacketHeader,            // 29 Bytes
-    pub event_string_code: [u8; 4],      // 4 Bytes
-    pub event_details: EventDataDetails, // 12 Bytes
-} // 45 Bytes
+    pub header: PacketHeader,
+    pub event: Event,
+}
 
 impl FastestLap {
     #[allow(dead_code)]
@@ -395,223 +436,173 @@ impl Overtake {
     }
 }
 
-impl PacketEventData {
+impl SafetyCar {
+    #[allow(dead_code)]
     pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
-        let header: PacketHeader = PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?;
-        let event_string_code: [u8; 4] = {
-            let mut event_string_code: [u8; 4] = [0u8; 4];
-            event_string_code.copy_from_slice(
-                &bytes[size_of::<PacketHeader>()..size_of::<PacketHeader>() + 4 * size_of::<u8>()],
-            );
-            event_string_code
-        };
-
-        let event_details: EventDataDetails = match &event_string_code {
-            b"FTLP" => EventDataDetails {
-                fastest_lap: FastestLap::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"RTMT" => EventDataDetails {
-                retirement: Retirement::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"TMPT" => EventDataDetails {
-                team_mate_in_pits: TeamMateInPits::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"RCWN" => EventDataDetails {
-                race_winner: RaceWinner::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"PENA" => EventDataDetails {
-                penalty: Penalty::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"SPTP" => EventDataDetails {
-                speed_trap: SpeedTrap::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"STLG" => EventDataDetails {
-                start_lights: StartLights::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"DTSV" => EventDataDetails {
-                drive_through_penalty_served: DriveThroughPenaltyServed::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"SGSV" => EventDataDetails {
-                stop_go_penalty_served: StopGoPenaltyServed::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"FLBK" => EventDataDetails {
-                flashback: Flashback::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"BUTN" => EventDataDetails {
-                buttons: Buttons::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"OVTK" => EventDataDetails {
-                overtake: Overtake::unserialize(
-                    &bytes[size_of::<PacketHeader>() + 4 * size_of::<u8>()..],
-                )?,
-            },
-            b"SSTA" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"SEND" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"DRSE" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"DRSD" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"CHQF" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"LGOT" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            b"RDFL" => EventDataDetails {
-                buttons: Buttons::default(),
-            }, // Unused Event Details
-            _ => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "Invalid event string code",
-                ))
-            }
-        };
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
-        Ok(PacketEventData {
-            header,
-            event_string_code,
-            event_details,
+        Ok(SafetyCar {
+            safety_car_type: cursor.read_u8()?,
+            event_type: cursor.read_u8()?,
         })
     }
 
+    #[allow(dead_code)]
     pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
-        let mut bytes = Vec::with_capacity(size_of::<PacketEventData>());
-        bytes.extend_from_slice(&self.header.serialize()?);
-        bytes.extend_from_slice(&self.event_string_code);
-
-        unsafe {
-            let ed: EventDataDetails = self.event_details;
-            match &self.event_string_code {
-                b"FTLP" => bytes.extend_from_slice(&ed.fastest_lap.serialize()?),
-                b"RTMT" => bytes.extend_from_slice(&ed.retirement.serialize()?),
-                b"TMPT" => bytes.extend_from_slice(&ed.team_mate_in_pits.serialize()?),
-                b"RCWN" => bytes.extend_from_slice(&ed.race_winner.serialize()?),
-                b"PENA" => bytes.extend_from_slice(&ed.penalty.serialize()?),
-                b"SPTP" => bytes.extend_from_slice(&ed.speed_trap.serialize()?),
-                b"STLG" => bytes.extend_from_slice(&ed.start_lights.serialize()?),
-                b"DTSV" => bytes.extend_from_slice(
-                    &self
-                        .event_details
-                        .drive_through_penalty_served
-                        .serialize()?,
-                ),
-                b"SGSV" => bytes.extend_from_slice(&ed.stop_go_penalty_served.serialize()?),
-                b"FLBK" => bytes.extend_from_slice(&ed.flashback.serialize()?),
-                b"BUTN" => bytes.extend_from_slice(&ed.buttons.serialize()?),
-                b"OVTK" => bytes.extend_from_slice(&ed.overtake.serialize()?),
-                b"SSTA" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"SEND" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"DRSE" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"DRSD" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"CHQF" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"LGOT" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                b"RDFL" => bytes.extend_from_slice(&ed.buttons.serialize()?), // Unused Event Details
-                _ => {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        "Invalid event string code",
-                    ))
-                }
-            }
-        };
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<SafetyCar>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_u8(self.safety_car_type)?;
+        cursor.write_u8(self.event_type)?;
 
         Ok(bytes)
     }
 }
 
-impl fmt::Debug for PacketEventData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PacketEventData")
-            .field("header", &self.header)
-            .field("event_string_code", &self.event_string_code)
-            .field("event_details", unsafe {
-                match &self.event_string_code {
-                    b"FTLP" => &self.event_details.fastest_lap,
-                    b"RTMT" => &self.event_details.retirement,
-                    b"TMPT" => &self.event_details.team_mate_in_pits,
-                    b"RCWN" => &self.event_details.race_winner,
-                    b"PENA" => &self.event_details.penalty,
-                    b"SPTP" => &self.event_details.speed_trap,
-                    b"STLG" => &self.event_details.start_lights,
-                    b"DTSV" => &self.event_details.drive_through_penalty_served,
-                    b"SGSV" => &self.event_details.stop_go_penalty_served,
-                    b"FLBK" => &self.event_details.flashback,
-                    b"BUTN" => &self.event_details.buttons,
-                    b"OVTK" => &self.event_details.overtake,
-                    b"SSTA" => &self.event_details.buttons, // Unused Event Details
-                    b"SEND" => &self.event_details.buttons, // Unused Event Details
-                    b"DRSE" => &self.event_details.buttons, // Unused Event Details
-                    b"DRSD" => &self.event_details.buttons, // Unused Event Details
-                    b"CHQF" => &self.event_details.buttons, // Unused Event Details
-                    b"LGOT" => &self.event_details.buttons, // Unused Event Details
-                    b"RDFL" => &self.event_details.buttons, // Unused Event Details
-                    _ => &self.event_details.buttons,       // Unused Event Details
-                }
-            })
-            .finish()
+impl Collision {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(Collision {
+            vehicle1_idx: cursor.read_u8()?,
+            vehicle2_idx: cursor.read_u8()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<Collision>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_u8(self.vehicle1_idx)?;
+        cursor.write_u8(self.vehicle2_idx)?;
+
+        Ok(bytes)
     }
 }
 
-impl PartialEq for PacketEventData {
-    fn eq(&self, other: &Self) -> bool {
-        let ed1: EventDataDetails = self.event_details;
-        let ed2: EventDataDetails = other.event_details;
-
-        self.header == other.header
-            && self.event_string_code == other.event_string_code
-            && unsafe {
-                match &self.event_string_code {
-                    b"FTLP" => ed1.fastest_lap == ed2.fastest_lap,
-                    b"RTMT" => ed1.retirement == ed2.retirement,
-                    b"TMPT" => ed1.team_mate_in_pits == ed2.team_mate_in_pits,
-                    b"RCWN" => ed1.race_winner == ed2.race_winner,
-                    b"PENA" => ed1.penalty == ed2.penalty,
-                    b"SPTP" => ed1.speed_trap == ed2.speed_trap,
-                    b"STLG" => ed1.start_lights == ed2.start_lights,
-                    b"DTSV" => ed1.drive_through_penalty_served == ed2.drive_through_penalty_served,
-                    b"SGSV" => ed1.stop_go_penalty_served == ed2.stop_go_penalty_served,
-                    b"FLBK" => ed1.flashback == ed2.flashback,
-                    b"BUTN" => ed1.buttons == ed2.buttons,
-                    b"OVTK" => ed1.overtake == ed2.overtake,
-                    b"SSTA" => true, // Unused Event Details
-                    b"SEND" => true, // Unused Event Details
-                    b"DRSE" => true, // Unused Event Details
-                    b"DRSD" => true, // Unused Event Details
-                    b"CHQF" => true, // Unused Event Details
-                    b"LGOT" => true, // Unused Event Details
-                    b"RDFL" => true, // Unused Event Details
-                    _ => true,       // Unused Event Details
-                }
+impl Event {
+    pub fn code(&self) -> [u8; 4] {
+        match self {
+            Event::SessionStarted => *b"SSTA",
+            Event::SessionEnded => *b"SEND",
+            Event::FastestLap(_) => *b"FTLP",
+            Event::Retirement(_) => *b"RTMT",
+            Event::DrsEnabled => *b"DRSE",
+            Event::DrsDisabled => *b"DRSD",
+            Event::TeamMateInPits(_) => *b"TMPT",
+            Event::ChequeredFlag => *b"CHQF",
+            Event::RaceWinner(_) => *b"RCWN",
+            Event::Penalty(_) => *b"PENA",
+            Event::SpeedTrap(_) => *b"SPTP",
+            Event::StartLights(_) => *b"STLG",
+            Event::LightsOut => *b"LGOT",
+            Event::DriveThroughPenaltyServed(_) => *b"DTSV",
+            Event::StopGoPenaltyServed(_) => *b"SGSV",
+            Event::Flashback(_) => *b"FLBK",
+            Event::Buttons(_) => *b"BUTN",
+            Event::RedFlag => *b"RDFL",
+            Event::Overtake(_) => *b"OVTK",
+            Event::SafetyCar(_) => *b"SCAR",
+            Event::Collision(_) => *b"COLL",
+            Event::Unknown(event_string_code, _) => *event_string_code,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        let mut event_string_code: [u8; 4] = [0u8; 4];
+        cursor.read_exact(&mut event_string_code)?;
+        let mut event_details: [u8; 12] = [0u8; 12];
+        cursor.read_exact(&mut event_details)?;
+
+        Ok(match &event_string_code {
+            b"SSTA" => Event::SessionStarted,
+            b"SEND" => Event::SessionEnded,
+            b"FTLP" => Event::FastestLap(FastestLap::unserialize(&event_details)?),
+            b"RTMT" => Event::Retirement(Retirement::unserialize(&event_details)?),
+            b"DRSE" => Event::DrsEnabled,
+            b"DRSD" => Event::DrsDisabled,
+            b"TMPT" => Event::TeamMateInPits(TeamMateInPits::unserialize(&event_details)?),
+            b"CHQF" => Event::ChequeredFlag,
+            b"RCWN" => Event::RaceWinner(RaceWinner::unserialize(&event_details)?),
+            b"PENA" => Event::Penalty(Penalty::unserialize(&event_details)?),
+            b"SPTP" => Event::SpeedTrap(SpeedTrap::unserialize(&event_details)?),
+            b"STLG" => Event::StartLights(StartLights::unserialize(&event_details)?),
+            b"LGOT" => Event::LightsOut,
+            b"DTSV" => Event::DriveThroughPenaltyServed(DriveThroughPenaltyServed::unserialize(
+                &event_details,
+            )?),
+            b"SGSV" => {
+                Event::StopGoPenaltyServed(StopGoPenaltyServed::unserialize(&event_details)?)
             }
+            b"FLBK" => Event::Flashback(Flashback::unserialize(&event_details)?),
+            b"BUTN" => Event::Buttons(Buttons::unserialize(&event_details)?),
+            b"RDFL" => Event::RedFlag,
+            b"OVTK" => Event::Overtake(Overtake::unserialize(&event_details)?),
+            b"SCAR" => Event::SafetyCar(SafetyCar::unserialize(&event_details)?),
+            b"COLL" => Event::Collision(Collision::unserialize(&event_details)?),
+            _ => Event::Unknown(event_string_code, event_details),
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(16);
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.code())?;
+        match self {
+            Event::FastestLap(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Retirement(details) => cursor.write_all(&details.serialize()?)?,
+            Event::TeamMateInPits(details) => cursor.write_all(&details.serialize()?)?,
+            Event::RaceWinner(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Penalty(details) => cursor.write_all(&details.serialize()?)?,
+            Event::SpeedTrap(details) => cursor.write_all(&details.serialize()?)?,
+            Event::StartLights(details) => cursor.write_all(&details.serialize()?)?,
+            Event::DriveThroughPenaltyServed(details) => cursor.write_all(&details.serialize()?)?,
+            Event::StopGoPenaltyServed(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Flashback(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Buttons(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Overtake(details) => cursor.write_all(&details.serialize()?)?,
+            Event::SafetyCar(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Collision(details) => cursor.write_all(&details.serialize()?)?,
+            Event::Unknown(_, details) => cursor.write_all(details)?,
+            _ => {} // Unused Event Details
+        }
+        bytes.resize(16, 0u8);
+
+        Ok(bytes)
+    }
+}
+
+impl PacketEventData {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketHeader>() + 16;
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketEventData {
+            header: PacketHeader::unserialize(bytes)?,
+            event: Event::unserialize(&bytes[size_of::<PacketHeader>()..])?,
+        })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(45);
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_all(&self.event.serialize()?)?;
+
+        Ok(bytes)
     }
 }
 
@@ -624,15 +615,6 @@ mod tests {
     fn test_packet_event_data_serialization_deserialization() {
         let mut rng = rand::thread_rng();
 
-        let original_event_string_code: [u8; 4] = *b"FTLP";
-
-        let original_event_details: EventDataDetails = EventDataDetails {
-            fastest_lap: FastestLap {
-                vehicle_idx: rng.gen(),
-                lap_time: rng.gen(),
-            },
-        };
-
         let original_packet: PacketEventData = PacketEventData {
             header: PacketHeader {
                 packet_format: rng.gen(),
@@ -648,14 +630,146 @@ mod tests {
                 player_car_index: rng.gen(),
                 secondary_player_car_index: rng.gen(),
             },
-            event_string_code: original_event_string_code,
-            event_details: original_event_details,
+            event: Event::FastestLap(FastestLap {
+                vehicle_idx: rng.gen(),
+                lap_time: rng.gen(),
+            }),
         };
 
         let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
         let deserialized_packet: PacketEventData =
             PacketEventData::unserialize(&serialized_packet).unwrap();
 
+        assert_eq!(serialized_packet.len(), 45);
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_event_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_events: [Event; 22] = [
+            Event::SessionStarted,
+            Event::SessionEnded,
+            Event::FastestLap(FastestLap {
+                vehicle_idx: rng.gen(),
+                lap_time: rng.gen(),
+            }),
+            Event::Retirement(Retirement {
+                vehicle_idx: rng.gen(),
+            }),
+            Event::DrsEnabled,
+            Event::DrsDisabled,
+            Event::TeamMateInPits(TeamMateInPits {
+                vehicle_idx: rng.gen(),
+            }),
+            Event::ChequeredFlag,
+            Event::RaceWinner(RaceWinner {
+                vehicle_idx: rng.gen(),
+            }),
+            Event::Penalty(Penalty {
+                penalty_type: rng.gen(),
+                infringement_type: rng.gen(),
+                vehicle_idx: rng.gen(),
+                other_vehicle_idx: rng.gen(),
+                time: rng.gen(),
+                lap_num: rng.gen(),
+                places_gained: rng.gen(),
+            }),
+            Event::SpeedTrap(SpeedTrap {
+                vehicle_idx: rng.gen(),
+                speed: rng.gen(),
+                is_overall_fastest_in_session: rng.gen(),
+                is_driver_fastest_in_session: rng.gen(),
+                fastest_vehicle_idx_in_session: rng.gen(),
+                fastest_speed_in_session: rng.gen(),
+            }),
+            Event::StartLights(StartLights {
+                num_lights: rng.gen(),
+            }),
+            Event::LightsOut,
+            Event::DriveThroughPenaltyServed(DriveThroughPenaltyServed {
+                vehicle_idx: rng.gen(),
+            }),
+            Event::StopGoPenaltyServed(StopGoPenaltyServed {
+                vehicle_idx: rng.gen(),
+            }),
+            Event::Flashback(Flashback {
+                flashback_frame_identifier: rng.gen(),
+                flashback_session_time: rng.gen(),
+            }),
+            Event::Buttons(Buttons {
+                button_status: rng.gen(),
+            }),
+            Event::RedFlag,
+            Event::Overtake(Overtake {
+                overtaking_vehicle_idx: rng.gen(),
+                being_overtaken_vehicle_idx: rng.gen(),
+            }),
+            Event::SafetyCar(SafetyCar {
+                safety_car_type: rng.gen(),
+                event_type: rng.gen(),
+            }),
+            Event::Collision(Collision {
+                vehicle1_idx: rng.gen(),
+                vehicle2_idx: rng.gen(),
+            }),
+            Event::Unknown(*b"XXXX", rng.gen()),
+        ];
+
+        for original_event in original_events {
+            let serialized_event: Vec<u8> = original_event.serialize().unwrap();
+            let deserialized_event: Event = Event::unserialize(&serialized_event).unwrap();
+
+            assert_eq!(serialized_event.len(), 16);
+            assert_eq!(&serialized_event[..4], &original_event.code());
+            assert_eq!(original_event, deserialized_event);
+        }
+    }
+
+    #[test]
+    fn test_event_unknown_event_string_code() {
+        let mut bytes: Vec<u8> = b"ABCD".to_vec();
+        bytes.extend(1u8..=12);
+
+        let event: Event = Event::unserialize(&bytes).unwrap();
+
+        assert_eq!(
+            event,
+            Event::Unknown(*b"ABCD", [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12])
+        );
+    }
+
+    #[test]
+    fn test_packet_event_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketEventData {
+            header: PacketHeader::default(),
+            event: Event::SessionStarted,
+        }
+        .serialize()
+        .unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketEventData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_event_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketEventData {
+            header: PacketHeader::default(),
+            event: Event::SessionStarted,
+        }
+        .serialize()
+        .unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketEventData::unserialize(&serialized_packet).is_ok());
+    }
 }
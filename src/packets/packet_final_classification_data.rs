@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FinalClassificationData {
     pub position: u8,                  // 1 Byte
     pub num_laps: u8,                  // 1 Byte
@@ -24,6 +26,7 @@ pub struct FinalClassificationData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketFinalClassificationData {
     pub header: PacketHeader,                               // 29 Bytes
     pub num_cars: u8,                                       // 1 Byte
@@ -49,22 +52,22 @@ impl FinalClassificationData {
             num_tyre_stints: cursor.read_u8()?,
             tyre_stints_actual: {
                 let mut tyre_stints_actual: [u8; 8] = [0u8; 8];
-                for i in 0..8 {
-                    tyre_stints_actual[i] = cursor.read_u8()?;
+                for element in tyre_stints_actual.iter_mut() {
+                    *element = cursor.read_u8()?;
                 }
                 tyre_stints_actual
             },
             tyre_stints_visual: {
                 let mut tyre_stints_visual: [u8; 8] = [0u8; 8];
-                for i in 0..8 {
-                    tyre_stints_visual[i] = cursor.read_u8()?;
+                for element in tyre_stints_visual.iter_mut() {
+                    *element = cursor.read_u8()?;
                 }
                 tyre_stints_visual
             },
             tyre_stints_end_laps: {
                 let mut tyre_stints_end_laps: [u8; 8] = [0u8; 8];
-                for i in 0..8 {
-                    tyre_stints_end_laps[i] = cursor.read_u8()?;
+                for element in tyre_stints_end_laps.iter_mut() {
+                    *element = cursor.read_u8()?;
                 }
                 tyre_stints_end_laps
             },
@@ -103,8 +106,16 @@ impl FinalClassificationData {
 
 impl PacketFinalClassificationData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
-        let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes);
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketFinalClassificationData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketFinalClassificationData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
@@ -119,10 +130,10 @@ impl PacketFinalClassificationData {
                 for i in 0..22 {
                     classification_data[i] = FinalClassificationData::unserialize(
                         &bytes[size_of::<PacketHeader>()
-                            + 1 * size_of::<u8>()
+                            + size_of::<u8>()
                             + i * size_of::<FinalClassificationData>()
                             ..size_of::<PacketHeader>()
-                                + 1 * size_of::<u8>()
+                                + size_of::<u8>()
                                 + (i + 1) * size_of::<FinalClassificationData>()],
                     )?;
                 }
@@ -277,4 +288,31 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_final_classification_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketFinalClassificationData::default()
+            .serialize()
+            .unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketFinalClassificationData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_final_classification_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketFinalClassificationData::default()
+            .serialize()
+            .unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketFinalClassificationData::unserialize(&serialized_packet).is_ok());
+    }
 }
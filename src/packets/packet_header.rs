@@ -4,6 +4,7 @@ use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketHeader {
     pub packet_format: u16,             // 2 Bytes
     pub game_year: u8,                  // 1 Byte
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LapData {
     pub last_lap_time_in_ms: u32,            // 4 Bytes
     pub current_lap_time_in_ms: u32,         // 4 Bytes
@@ -13,7 +15,9 @@ pub struct LapData {
     pub sector2_time_in_ms: u16,             // 2 Bytes
     pub sector2_time_minutes: u8,            // 1 Byte
     pub delta_to_car_in_front_in_ms: u16,    // 2 Bytes
+    pub delta_to_car_in_front_minutes: u8,   // 1 Byte
     pub delta_to_race_leader_in_ms: u16,     // 2 Bytes
+    pub delta_to_race_leader_minutes: u8,    // 1 Byte
     pub lap_distance: f32,                   // 4 Bytes
     pub total_distance: f32,                 // 4 Bytes
     pub safety_car_delta: f32,               // 4 Bytes
@@ -35,16 +39,19 @@ pub struct LapData {
     pub pit_lane_time_in_lane_in_ms: u16,    // 2 Bytes
     pub pit_stop_timer_in_ms: u16,           // 2 Bytes
     pub pit_stop_should_serve_pen: u8,       // 1 Byte
-} // 50 Bytes
+    pub speed_trap_fastest_speed: f32,       // 4 Bytes
+    pub speed_trap_fastest_lap: u8,          // 1 Byte
+} // 57 Bytes
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketLapData {
     pub header: PacketHeader,         // 29 Bytes
-    pub lap_data: [LapData; 22],      // 1100 Bytes
+    pub lap_data: [LapData; 22],      // 1254 Bytes
     pub time_trial_pb_car_idx: u8,    // 1 Byte
     pub time_trial_rival_car_idx: u8, // 1 Byte
-} // 1131 Bytes
+} // 1285 Bytes
 
 impl LapData {
     #[allow(dead_code)]
@@ -59,7 +66,9 @@ impl LapData {
             sector2_time_in_ms: cursor.read_u16::<LittleEndian>()?,
             sector2_time_minutes: cursor.read_u8()?,
             delta_to_car_in_front_in_ms: cursor.read_u16::<LittleEndian>()?,
+            delta_to_car_in_front_minutes: cursor.read_u8()?,
             delta_to_race_leader_in_ms: cursor.read_u16::<LittleEndian>()?,
+            delta_to_race_leader_minutes: cursor.read_u8()?,
             lap_distance: cursor.read_f32::<LittleEndian>()?,
             total_distance: cursor.read_f32::<LittleEndian>()?,
             safety_car_delta: cursor.read_f32::<LittleEndian>()?,
@@ -81,6 +90,8 @@ impl LapData {
             pit_lane_time_in_lane_in_ms: cursor.read_u16::<LittleEndian>()?,
             pit_stop_timer_in_ms: cursor.read_u16::<LittleEndian>()?,
             pit_stop_should_serve_pen: cursor.read_u8()?,
+            speed_trap_fastest_speed: cursor.read_f32::<LittleEndian>()?,
+            speed_trap_fastest_lap: cursor.read_u8()?,
         })
     }
 
@@ -96,7 +107,9 @@ impl LapData {
         cursor.write_u16::<LittleEndian>(self.sector2_time_in_ms)?;
         cursor.write_u8(self.sector2_time_minutes)?;
         cursor.write_u16::<LittleEndian>(self.delta_to_car_in_front_in_ms)?;
+        cursor.write_u8(self.delta_to_car_in_front_minutes)?;
         cursor.write_u16::<LittleEndian>(self.delta_to_race_leader_in_ms)?;
+        cursor.write_u8(self.delta_to_race_leader_minutes)?;
         cursor.write_f32::<LittleEndian>(self.lap_distance)?;
         cursor.write_f32::<LittleEndian>(self.total_distance)?;
         cursor.write_f32::<LittleEndian>(self.safety_car_delta)?;
@@ -118,6 +131,8 @@ impl LapData {
         cursor.write_u16::<LittleEndian>(self.pit_lane_time_in_lane_in_ms)?;
         cursor.write_u16::<LittleEndian>(self.pit_stop_timer_in_ms)?;
         cursor.write_u8(self.pit_stop_should_serve_pen)?;
+        cursor.write_f32::<LittleEndian>(self.speed_trap_fastest_speed)?;
+        cursor.write_u8(self.speed_trap_fastest_lap)?;
 
         Ok(bytes)
     }
@@ -125,7 +140,15 @@ impl LapData {
 
 impl PacketLapData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketLapData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketLapData {
@@ -182,7 +205,9 @@ mod tests {
             sector2_time_in_ms: rng.gen(),
             sector2_time_minutes: rng.gen(),
             delta_to_car_in_front_in_ms: rng.gen(),
+            delta_to_car_in_front_minutes: rng.gen(),
             delta_to_race_leader_in_ms: rng.gen(),
+            delta_to_race_leader_minutes: rng.gen(),
             lap_distance: rng.gen(),
             total_distance: rng.gen(),
             safety_car_delta: rng.gen(),
@@ -204,6 +229,8 @@ mod tests {
             pit_lane_time_in_lane_in_ms: rng.gen(),
             pit_stop_timer_in_ms: rng.gen(),
             pit_stop_should_serve_pen: rng.gen(),
+            speed_trap_fastest_speed: rng.gen(),
+            speed_trap_fastest_lap: rng.gen(),
         };
 
         let serialized_data: Vec<u8> = original_data.serialize().unwrap();
@@ -239,7 +266,9 @@ mod tests {
                 sector2_time_in_ms: rng.gen(),
                 sector2_time_minutes: rng.gen(),
                 delta_to_car_in_front_in_ms: rng.gen(),
+                delta_to_car_in_front_minutes: rng.gen(),
                 delta_to_race_leader_in_ms: rng.gen(),
+                delta_to_race_leader_minutes: rng.gen(),
                 lap_distance: rng.gen(),
                 total_distance: rng.gen(),
                 safety_car_delta: rng.gen(),
@@ -261,6 +290,8 @@ mod tests {
                 pit_lane_time_in_lane_in_ms: rng.gen(),
                 pit_stop_timer_in_ms: rng.gen(),
                 pit_stop_should_serve_pen: rng.gen(),
+                speed_trap_fastest_speed: rng.gen(),
+                speed_trap_fastest_lap: rng.gen(),
             }; 22],
             time_trial_pb_car_idx: rng.gen(),
             time_trial_rival_car_idx: rng.gen(),
@@ -272,4 +303,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_lap_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketLapData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketLapData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_lap_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketLapData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketLapData::unserialize(&serialized_packet).is_ok());
+    }
 }
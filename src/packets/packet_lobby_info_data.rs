@@ -1,27 +1,34 @@
 use super::packet_header::PacketHeader;
-use byteorder::{ReadBytesExt, WriteBytesExt};
+use crate::Error;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LobbyInfoData {
     pub ai_controlled: u8, // 1 Byte
     pub team_id: u8,       // 1 Byte
     pub nationality: u8,   // 1 Byte
     pub platform: u8,      // 1 Byte
-    pub name: [u8; 48],    // 48 Bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::byte_string"))]
+    pub name: [u8; 48], // 48 Bytes
     pub car_number: u8,    // 1 Byte
+    pub your_telemetry: u8, // 1 Byte
+    pub show_online_names: u8, // 1 Byte
+    pub tech_level: u16,   // 2 Bytes
     pub ready_status: u8,  // 1 Byte
-} // 54 Bytes
+} // 58 Bytes
 
 #[repr(C, packed)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketLobbyInfoData {
     pub header: PacketHeader,               // 29 Bytes
     pub num_players: u8,                    // 1 Byte
-    pub lobby_players: [LobbyInfoData; 22], // 1188 Bytes
-} // 1218 Bytes
+    pub lobby_players: [LobbyInfoData; 22], // 1276 Bytes
+} // 1306 Bytes
 
 impl Default for LobbyInfoData {
     fn default() -> Self {
@@ -32,21 +39,14 @@ impl Default for LobbyInfoData {
             platform: 0u8,
             name: [0u8; 48],
             car_number: 0u8,
+            your_telemetry: 0u8,
+            show_online_names: 0u8,
+            tech_level: 0u16,
             ready_status: 0u8,
         }
     }
 }
 
-impl Default for PacketLobbyInfoData {
-    fn default() -> Self {
-        PacketLobbyInfoData {
-            header: PacketHeader::default(),
-            num_players: 0u8,
-            lobby_players: [LobbyInfoData::default(); 22],
-        }
-    }
-}
-
 impl LobbyInfoData {
     #[allow(dead_code)]
     pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
@@ -59,12 +59,15 @@ impl LobbyInfoData {
             platform: cursor.read_u8()?,
             name: {
                 let mut name: [u8; 48] = [0u8; 48];
-                for i in 0..48 {
-                    name[i] = cursor.read_u8()?
+                for element in name.iter_mut() {
+                    *element = cursor.read_u8()?;
                 }
                 name
             },
             car_number: cursor.read_u8()?,
+            your_telemetry: cursor.read_u8()?,
+            show_online_names: cursor.read_u8()?,
+            tech_level: cursor.read_u16::<LittleEndian>()?,
             ready_status: cursor.read_u8()?,
         })
     }
@@ -82,6 +85,9 @@ impl LobbyInfoData {
             cursor.write_u8(element)?;
         }
         cursor.write_u8(self.car_number)?;
+        cursor.write_u8(self.your_telemetry)?;
+        cursor.write_u8(self.show_online_names)?;
+        cursor.write_u16::<LittleEndian>(self.tech_level)?;
         cursor.write_u8(self.ready_status)?;
 
         Ok(bytes)
@@ -90,8 +96,16 @@ impl LobbyInfoData {
 
 impl PacketLobbyInfoData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
-        let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes);
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketLobbyInfoData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketLobbyInfoData {
             header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
@@ -105,10 +119,10 @@ impl PacketLobbyInfoData {
                 for i in 0..22 {
                     lobby_players[i] = LobbyInfoData::unserialize(
                         &bytes[size_of::<PacketHeader>()
-                            + 1 * size_of::<u8>()
+                            + size_of::<u8>()
                             + i * size_of::<LobbyInfoData>()
                             ..size_of::<PacketHeader>()
-                                + 1 * size_of::<u8>()
+                                + size_of::<u8>()
                                 + (i + 1) * size_of::<LobbyInfoData>()],
                     )?;
                 }
@@ -148,6 +162,9 @@ mod tests {
             platform: rng.gen(),
             name: [rng.sample(Alphanumeric); 48],
             car_number: rng.gen(),
+            your_telemetry: rng.gen(),
+            show_online_names: rng.gen(),
+            tech_level: rng.gen(),
             ready_status: rng.gen(),
         };
 
@@ -185,6 +202,9 @@ mod tests {
                 platform: rng.gen(),
                 name: [rng.sample(Alphanumeric); 48],
                 car_number: rng.gen(),
+                your_telemetry: rng.gen(),
+                show_online_names: rng.gen(),
+                tech_level: rng.gen(),
                 ready_status: rng.gen(),
             }; 22],
         };
@@ -195,4 +215,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_lobby_info_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketLobbyInfoData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketLobbyInfoData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_lobby_info_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketLobbyInfoData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketLobbyInfoData::unserialize(&serialized_packet).is_ok());
+    }
 }
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketMotionExData {
     pub header: PacketHeader,              // 29 Bytes
     pub suspension_position: [f32; 4],     // 16 Bytes
@@ -27,11 +29,24 @@ pub struct PacketMotionExData {
     pub angular_acceleration_z: f32,       // 4 Bytes
     pub front_wheels_angle: f32,           // 4 Bytes
     pub wheel_vert_force: [f32; 4],        // 16 Bytes
-} // 217 Bytes
+    pub front_aero_height: f32,            // 4 Bytes
+    pub rear_aero_height: f32,             // 4 Bytes
+    pub front_roll_angle: f32,             // 4 Bytes
+    pub rear_roll_angle: f32,              // 4 Bytes
+    pub chassis_yaw: f32,                  // 4 Bytes
+} // 237 Bytes
 
 impl PacketMotionExData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketMotionExData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketMotionExData {
@@ -105,6 +120,11 @@ impl PacketMotionExData {
                 cursor.read_f32::<LittleEndian>()?,
                 cursor.read_f32::<LittleEndian>()?,
             ],
+            front_aero_height: cursor.read_f32::<LittleEndian>()?,
+            rear_aero_height: cursor.read_f32::<LittleEndian>()?,
+            front_roll_angle: cursor.read_f32::<LittleEndian>()?,
+            rear_roll_angle: cursor.read_f32::<LittleEndian>()?,
+            chassis_yaw: cursor.read_f32::<LittleEndian>()?,
         })
     }
 
@@ -152,6 +172,11 @@ impl PacketMotionExData {
         for element in self.wheel_vert_force {
             cursor.write_f32::<LittleEndian>(element)?;
         }
+        cursor.write_f32::<LittleEndian>(self.front_aero_height)?;
+        cursor.write_f32::<LittleEndian>(self.rear_aero_height)?;
+        cursor.write_f32::<LittleEndian>(self.front_roll_angle)?;
+        cursor.write_f32::<LittleEndian>(self.rear_roll_angle)?;
+        cursor.write_f32::<LittleEndian>(self.chassis_yaw)?;
 
         Ok(bytes)
     }
@@ -201,6 +226,11 @@ mod tests {
             angular_acceleration_z: rng.gen(),
             front_wheels_angle: rng.gen(),
             wheel_vert_force: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            front_aero_height: rng.gen(),
+            rear_aero_height: rng.gen(),
+            front_roll_angle: rng.gen(),
+            rear_roll_angle: rng.gen(),
+            chassis_yaw: rng.gen(),
         };
 
         let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
@@ -209,4 +239,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_motion_ex_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketMotionExData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketMotionExData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_motion_ex_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketMotionExData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketMotionExData::unserialize(&serialized_packet).is_ok());
+    }
 }
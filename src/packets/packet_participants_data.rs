@@ -1,31 +1,36 @@
 use super::packet_header::PacketHeader;
-use byteorder::{ReadBytesExt, WriteBytesExt};
+use crate::Error;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParticipantData {
-    pub ai_controlled: u8,     // 1 Byte
-    pub driver_id: u8,         // 1 Byte
-    pub network_id: u8,        // 1 Byte
-    pub team_id: u8,           // 1 Byte
-    pub my_team: u8,           // 1 Byte
-    pub race_number: u8,       // 1 Byte
-    pub nationality: u8,       // 1 Byte
-    pub name: [u8; 48],        // 48 Bytes
-    pub your_telemetry: u8,    // 1 Byte
+    pub ai_controlled: u8, // 1 Byte
+    pub driver_id: u8,     // 1 Byte
+    pub network_id: u8,    // 1 Byte
+    pub team_id: u8,       // 1 Byte
+    pub my_team: u8,       // 1 Byte
+    pub race_number: u8,   // 1 Byte
+    pub nationality: u8,   // 1 Byte
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::byte_string"))]
+    pub name: [u8; 48], // 48 Bytes
+    pub your_telemetry: u8, // 1 Byte
     pub show_online_names: u8, // 1 Byte
-    pub platform: u8,          // 1 Byte
-} // 58 Bytes
+    pub tech_level: u16,   // 2 Bytes
+    pub platform: u8,      // 1 Byte
+} // 60 Bytes
 
 #[repr(C, packed)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketParticipantsData {
     pub header: PacketHeader,                // 29 Bytes
     pub num_active_cars: u8,                 // 1 Byte
-    pub participants: [ParticipantData; 22], // 1276 Bytes
-} // 1306 Bytes
+    pub participants: [ParticipantData; 22], // 1320 Bytes
+} // 1350 Bytes
 
 impl Default for ParticipantData {
     fn default() -> Self {
@@ -40,21 +45,12 @@ impl Default for ParticipantData {
             name: [0u8; 48],
             your_telemetry: 0u8,
             show_online_names: 0u8,
+            tech_level: 0u16,
             platform: 0u8,
         }
     }
 }
 
-impl Default for PacketParticipantsData {
-    fn default() -> Self {
-        PacketParticipantsData {
-            header: PacketHeader::default(),
-            num_active_cars: 0u8,
-            participants: [ParticipantData::default(); 22],
-        }
-    }
-}
-
 impl ParticipantData {
     #[allow(dead_code)]
     pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
@@ -70,13 +66,14 @@ impl ParticipantData {
             nationality: cursor.read_u8()?,
             name: {
                 let mut name: [u8; 48] = [0u8; 48];
-                for i in 0..48 {
-                    name[i] = cursor.read_u8()?;
+                for element in name.iter_mut() {
+                    *element = cursor.read_u8()?;
                 }
                 name
             },
             your_telemetry: cursor.read_u8()?,
             show_online_names: cursor.read_u8()?,
+            tech_level: cursor.read_u16::<LittleEndian>()?,
             platform: cursor.read_u8()?,
         })
     }
@@ -98,6 +95,7 @@ impl ParticipantData {
         }
         cursor.write_u8(self.your_telemetry)?;
         cursor.write_u8(self.show_online_names)?;
+        cursor.write_u16::<LittleEndian>(self.tech_level)?;
         cursor.write_u8(self.platform)?;
 
         Ok(bytes)
@@ -106,7 +104,15 @@ impl ParticipantData {
 
 impl PacketParticipantsData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketParticipantsData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes[size_of::<PacketHeader>()..]);
 
         Ok(PacketParticipantsData {
@@ -117,10 +123,10 @@ impl PacketParticipantsData {
                 for i in 0..22 {
                     participants[i] = ParticipantData::unserialize(
                         &bytes[size_of::<PacketHeader>()
-                            + 1 * size_of::<u8>()
+                            + size_of::<u8>()
                             + i * size_of::<ParticipantData>()
                             ..size_of::<PacketHeader>()
-                                + 1 * size_of::<u8>()
+                                + size_of::<u8>()
                                 + (i + 1) * size_of::<ParticipantData>()],
                     )?;
                 }
@@ -164,6 +170,7 @@ mod tests {
             name: [rng.sample(Alphanumeric); 48],
             your_telemetry: rng.gen(),
             show_online_names: rng.gen(),
+            tech_level: rng.gen(),
             platform: rng.gen(),
         };
 
@@ -205,6 +212,7 @@ mod tests {
                 name: [rng.sample(Alphanumeric); 48],
                 your_telemetry: rng.gen(),
                 show_online_names: rng.gen(),
+                tech_level: rng.gen(),
                 platform: rng.gen(),
             }; 22],
         };
@@ -215,4 +223,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_participants_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketParticipantsData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketParticipantsData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_participants_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketParticipantsData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketParticipantsData::unserialize(&serialized_packet).is_ok());
+    }
 }
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MarshalZone {
     pub zone_start: f32, // 4 Bytes
     pub zone_flag: i8,   // 1 Byte
@@ -12,6 +14,7 @@ pub struct MarshalZone {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WeatherForecastSample {
     pub session_type: u8,             // 1 Byte
     pub time_offset: u8,              // 1 Byte
@@ -25,58 +28,88 @@ pub struct WeatherForecastSample {
 
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketSessionData {
-    pub header: PacketHeader,                                  // 29 Bytes
-    pub weather: u8,                                           // 1 Byte
-    pub track_temperature: i8,                                 // 1 Byte
-    pub air_temperature: i8,                                   // 1 Byte
-    pub total_laps: u8,                                        // 1 Byte
-    pub track_length: u16,                                     // 2 Bytes
-    pub session_type: u8,                                      // 1 Byte
-    pub track_id: i8,                                          // 1 Byte
-    pub formula: u8,                                           // 1 Byte
-    pub session_time_left: u16,                                // 2 Bytes
-    pub session_duration: u16,                                 // 2 Bytes
-    pub pit_speed_limit: u8,                                   // 1 Byte
-    pub game_paused: u8,                                       // 1 Byte
-    pub is_spectating: u8,                                     // 1 Byte
-    pub spectator_car_index: u8,                               // 1 Byte
-    pub sli_pro_native_support: u8,                            // 1 Byte
-    pub num_marshal_zones: u8,                                 // 1 Byte
-    pub marshal_zones: [MarshalZone; 21],                      // 105 Bytes
-    pub safety_car_status: u8,                                 // 1 Byte
-    pub network_game: u8,                                      // 1 Byte
-    pub num_weather_forecast_samples: u8,                      // 1 Byte
-    pub weather_forecast_samples: [WeatherForecastSample; 56], // 448 Bytes
-    pub forecast_accuracy: u8,                                 // 1 Byte
-    pub ai_difficulty: u8,                                     // 1 Byte
-    pub season_link_identifier: u32,                           // 4 Bytes
-    pub weekend_link_identifier: u32,                          // 4 Bytes
-    pub session_link_identifier: u32,                          // 4 Bytes
-    pub pit_stop_window_ideal_lap: u8,                         // 1 Byte
-    pub pit_stop_window_latest_lap: u8,                        // 1 Byte
-    pub pit_stop_rejoin_position: u8,                          // 1 Byte
-    pub steering_assist: u8,                                   // 1 Byte
-    pub braking_assist: u8,                                    // 1 Byte
-    pub gearbox_assist: u8,                                    // 1 Byte
-    pub pit_assist: u8,                                        // 1 Byte
-    pub pit_release_assist: u8,                                // 1 Byte
-    pub ers_assist: u8,                                        // 1 Byte
-    pub drs_assist: u8,                                        // 1 Byte
-    pub dynamic_racing_line: u8,                               // 1 Byte
-    pub dynamic_racing_line_type: u8,                          // 1 Byte
-    pub game_mode: u8,                                         // 1 Byte
-    pub rule_set: u8,                                          // 1 Byte
-    pub time_of_day: u32,                                      // 4 Bytes
-    pub session_length: u8,                                    // 1 Byte
-    pub speed_units_lead_player: u8,                           // 1 Byte
-    pub temperature_units_lead_player: u8,                     // 1 Byte
-    pub speed_units_secondary_player: u8,                      // 1 Byte
-    pub temperature_units_secondary_player: u8,                // 1 Byte
-    pub num_safety_car_periods: u8,                            // 1 Byte
-    pub num_virtual_safety_car_periods: u8,                    // 1 Byte
-    pub num_red_flag_periods: u8,                              // 1 Byte
-} // 644 Bytes
+    pub header: PacketHeader,             // 29 Bytes
+    pub weather: u8,                      // 1 Byte
+    pub track_temperature: i8,            // 1 Byte
+    pub air_temperature: i8,              // 1 Byte
+    pub total_laps: u8,                   // 1 Byte
+    pub track_length: u16,                // 2 Bytes
+    pub session_type: u8,                 // 1 Byte
+    pub track_id: i8,                     // 1 Byte
+    pub formula: u8,                      // 1 Byte
+    pub session_time_left: u16,           // 2 Bytes
+    pub session_duration: u16,            // 2 Bytes
+    pub pit_speed_limit: u8,              // 1 Byte
+    pub game_paused: u8,                  // 1 Byte
+    pub is_spectating: u8,                // 1 Byte
+    pub spectator_car_index: u8,          // 1 Byte
+    pub sli_pro_native_support: u8,       // 1 Byte
+    pub num_marshal_zones: u8,            // 1 Byte
+    pub marshal_zones: [MarshalZone; 21], // 105 Bytes
+    pub safety_car_status: u8,            // 1 Byte
+    pub network_game: u8,                 // 1 Byte
+    pub num_weather_forecast_samples: u8, // 1 Byte
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array"))]
+    pub weather_forecast_samples: [WeatherForecastSample; 64], // 512 Bytes
+    pub forecast_accuracy: u8,            // 1 Byte
+    pub ai_difficulty: u8,                // 1 Byte
+    pub season_link_identifier: u32,      // 4 Bytes
+    pub weekend_link_identifier: u32,     // 4 Bytes
+    pub session_link_identifier: u32,     // 4 Bytes
+    pub pit_stop_window_ideal_lap: u8,    // 1 Byte
+    pub pit_stop_window_latest_lap: u8,   // 1 Byte
+    pub pit_stop_rejoin_position: u8,     // 1 Byte
+    pub steering_assist: u8,              // 1 Byte
+    pub braking_assist: u8,               // 1 Byte
+    pub gearbox_assist: u8,               // 1 Byte
+    pub pit_assist: u8,                   // 1 Byte
+    pub pit_release_assist: u8,           // 1 Byte
+    pub ers_assist: u8,                   // 1 Byte
+    pub drs_assist: u8,                   // 1 Byte
+    pub dynamic_racing_line: u8,          // 1 Byte
+    pub dynamic_racing_line_type: u8,     // 1 Byte
+    pub game_mode: u8,                    // 1 Byte
+    pub rule_set: u8,                     // 1 Byte
+    pub time_of_day: u32,                 // 4 Bytes
+    pub session_length: u8,               // 1 Byte
+    pub speed_units_lead_player: u8,      // 1 Byte
+    pub temperature_units_lead_player: u8, // 1 Byte
+    pub speed_units_secondary_player: u8, // 1 Byte
+    pub temperature_units_secondary_player: u8, // 1 Byte
+    pub num_safety_car_periods: u8,       // 1 Byte
+    pub num_virtual_safety_car_periods: u8, // 1 Byte
+    pub num_red_flag_periods: u8,         // 1 Byte
+    pub equal_car_performance: u8,        // 1 Byte
+    pub recovery_mode: u8,                // 1 Byte
+    pub flashback_limit: u8,              // 1 Byte
+    pub surface_type: u8,                 // 1 Byte
+    pub low_fuel_mode: u8,                // 1 Byte
+    pub race_starts: u8,                  // 1 Byte
+    pub tyre_temperature: u8,             // 1 Byte
+    pub pit_lane_tyre_sim: u8,            // 1 Byte
+    pub car_damage: u8,                   // 1 Byte
+    pub car_damage_rate: u8,              // 1 Byte
+    pub collisions: u8,                   // 1 Byte
+    pub collisions_off_for_first_lap_only: u8, // 1 Byte
+    pub mp_unsafe_pit_release: u8,        // 1 Byte
+    pub mp_off_for_griefing: u8,          // 1 Byte
+    pub corner_cutting_stringency: u8,    // 1 Byte
+    pub parc_ferme_rules: u8,             // 1 Byte
+    pub pit_stop_experience: u8,          // 1 Byte
+    pub safety_car: u8,                   // 1 Byte
+    pub safety_car_experience: u8,        // 1 Byte
+    pub formation_lap: u8,                // 1 Byte
+    pub formation_lap_experience: u8,     // 1 Byte
+    pub red_flags: u8,                    // 1 Byte
+    pub affects_licence_level_solo: u8,   // 1 Byte
+    pub affects_licence_level_mp: u8,     // 1 Byte
+    pub num_sessions_in_weekend: u8,      // 1 Byte
+    pub weekend_structure: [u8; 12],      // 12 Bytes
+    pub sector2_lap_distance_start: f32,  // 4 Bytes
+    pub sector3_lap_distance_start: f32,  // 4 Bytes
+} // 753 Bytes
 
 impl Default for PacketSessionData {
     fn default() -> Self {
@@ -102,7 +135,7 @@ impl Default for PacketSessionData {
             safety_car_status: 0u8,
             network_game: 0u8,
             num_weather_forecast_samples: 0u8,
-            weather_forecast_samples: [WeatherForecastSample::default(); 56],
+            weather_forecast_samples: [WeatherForecastSample::default(); 64],
             forecast_accuracy: 0u8,
             ai_difficulty: 0u8,
             season_link_identifier: 0u32,
@@ -131,6 +164,34 @@ impl Default for PacketSessionData {
             num_safety_car_periods: 0u8,
             num_virtual_safety_car_periods: 0u8,
             num_red_flag_periods: 0u8,
+            equal_car_performance: 0u8,
+            recovery_mode: 0u8,
+            flashback_limit: 0u8,
+            surface_type: 0u8,
+            low_fuel_mode: 0u8,
+            race_starts: 0u8,
+            tyre_temperature: 0u8,
+            pit_lane_tyre_sim: 0u8,
+            car_damage: 0u8,
+            car_damage_rate: 0u8,
+            collisions: 0u8,
+            collisions_off_for_first_lap_only: 0u8,
+            mp_unsafe_pit_release: 0u8,
+            mp_off_for_griefing: 0u8,
+            corner_cutting_stringency: 0u8,
+            parc_ferme_rules: 0u8,
+            pit_stop_experience: 0u8,
+            safety_car: 0u8,
+            safety_car_experience: 0u8,
+            formation_lap: 0u8,
+            formation_lap_experience: 0u8,
+            red_flags: 0u8,
+            affects_licence_level_solo: 0u8,
+            affects_licence_level_mp: 0u8,
+            num_sessions_in_weekend: 0u8,
+            weekend_structure: [0u8; 12],
+            sector2_lap_distance_start: 0f32,
+            sector3_lap_distance_start: 0f32,
         }
     }
 }
@@ -195,7 +256,15 @@ impl WeatherForecastSample {
 
 impl PacketSessionData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketSessionData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketSessionData {
@@ -251,9 +320,9 @@ impl PacketSessionData {
             network_game: cursor.read_u8()?,
             num_weather_forecast_samples: cursor.read_u8()?,
             weather_forecast_samples: {
-                let mut weather_forecast_samples: [WeatherForecastSample; 56] =
-                    [WeatherForecastSample::default(); 56];
-                for i in 0..56 {
+                let mut weather_forecast_samples: [WeatherForecastSample; 64] =
+                    [WeatherForecastSample::default(); 64];
+                for i in 0..64 {
                     weather_forecast_samples[i] = WeatherForecastSample::unserialize(
                         &bytes[size_of::<PacketHeader>()
                             + 3 * size_of::<i8>()
@@ -277,7 +346,7 @@ impl PacketSessionData {
                     + 13 * size_of::<u8>()
                     + 3 * size_of::<u16>()
                     + size_of::<[MarshalZone; 21]>()
-                    + size_of::<[WeatherForecastSample; 56]>();
+                    + size_of::<[WeatherForecastSample; 64]>();
                 cursor.set_position(pos as u64);
                 cursor.read_u8()?
             },
@@ -308,6 +377,40 @@ impl PacketSessionData {
             num_safety_car_periods: cursor.read_u8()?,
             num_virtual_safety_car_periods: cursor.read_u8()?,
             num_red_flag_periods: cursor.read_u8()?,
+            equal_car_performance: cursor.read_u8()?,
+            recovery_mode: cursor.read_u8()?,
+            flashback_limit: cursor.read_u8()?,
+            surface_type: cursor.read_u8()?,
+            low_fuel_mode: cursor.read_u8()?,
+            race_starts: cursor.read_u8()?,
+            tyre_temperature: cursor.read_u8()?,
+            pit_lane_tyre_sim: cursor.read_u8()?,
+            car_damage: cursor.read_u8()?,
+            car_damage_rate: cursor.read_u8()?,
+            collisions: cursor.read_u8()?,
+            collisions_off_for_first_lap_only: cursor.read_u8()?,
+            mp_unsafe_pit_release: cursor.read_u8()?,
+            mp_off_for_griefing: cursor.read_u8()?,
+            corner_cutting_stringency: cursor.read_u8()?,
+            parc_ferme_rules: cursor.read_u8()?,
+            pit_stop_experience: cursor.read_u8()?,
+            safety_car: cursor.read_u8()?,
+            safety_car_experience: cursor.read_u8()?,
+            formation_lap: cursor.read_u8()?,
+            formation_lap_experience: cursor.read_u8()?,
+            red_flags: cursor.read_u8()?,
+            affects_licence_level_solo: cursor.read_u8()?,
+            affects_licence_level_mp: cursor.read_u8()?,
+            num_sessions_in_weekend: cursor.read_u8()?,
+            weekend_structure: {
+                let mut weekend_structure: [u8; 12] = [0u8; 12];
+                for element in weekend_structure.iter_mut() {
+                    *element = cursor.read_u8()?;
+                }
+                weekend_structure
+            },
+            sector2_lap_distance_start: cursor.read_f32::<LittleEndian>()?,
+            sector3_lap_distance_start: cursor.read_f32::<LittleEndian>()?,
         })
     }
 
@@ -370,6 +473,36 @@ impl PacketSessionData {
         cursor.write_u8(self.num_safety_car_periods)?;
         cursor.write_u8(self.num_virtual_safety_car_periods)?;
         cursor.write_u8(self.num_red_flag_periods)?;
+        cursor.write_u8(self.equal_car_performance)?;
+        cursor.write_u8(self.recovery_mode)?;
+        cursor.write_u8(self.flashback_limit)?;
+        cursor.write_u8(self.surface_type)?;
+        cursor.write_u8(self.low_fuel_mode)?;
+        cursor.write_u8(self.race_starts)?;
+        cursor.write_u8(self.tyre_temperature)?;
+        cursor.write_u8(self.pit_lane_tyre_sim)?;
+        cursor.write_u8(self.car_damage)?;
+        cursor.write_u8(self.car_damage_rate)?;
+        cursor.write_u8(self.collisions)?;
+        cursor.write_u8(self.collisions_off_for_first_lap_only)?;
+        cursor.write_u8(self.mp_unsafe_pit_release)?;
+        cursor.write_u8(self.mp_off_for_griefing)?;
+        cursor.write_u8(self.corner_cutting_stringency)?;
+        cursor.write_u8(self.parc_ferme_rules)?;
+        cursor.write_u8(self.pit_stop_experience)?;
+        cursor.write_u8(self.safety_car)?;
+        cursor.write_u8(self.safety_car_experience)?;
+        cursor.write_u8(self.formation_lap)?;
+        cursor.write_u8(self.formation_lap_experience)?;
+        cursor.write_u8(self.red_flags)?;
+        cursor.write_u8(self.affects_licence_level_solo)?;
+        cursor.write_u8(self.affects_licence_level_mp)?;
+        cursor.write_u8(self.num_sessions_in_weekend)?;
+        for element in self.weekend_structure {
+            cursor.write_u8(element)?;
+        }
+        cursor.write_f32::<LittleEndian>(self.sector2_lap_distance_start)?;
+        cursor.write_f32::<LittleEndian>(self.sector3_lap_distance_start)?;
 
         Ok(bytes)
     }
@@ -468,7 +601,7 @@ mod tests {
                 air_temperature: rng.gen(),
                 air_temperature_change: rng.gen(),
                 rain_percentage: rng.gen(),
-            }; 56],
+            }; 64],
             forecast_accuracy: rng.gen(),
             ai_difficulty: rng.gen(),
             season_link_identifier: rng.gen(),
@@ -497,6 +630,34 @@ mod tests {
             num_safety_car_periods: rng.gen(),
             num_virtual_safety_car_periods: rng.gen(),
             num_red_flag_periods: rng.gen(),
+            equal_car_performance: rng.gen(),
+            recovery_mode: rng.gen(),
+            flashback_limit: rng.gen(),
+            surface_type: rng.gen(),
+            low_fuel_mode: rng.gen(),
+            race_starts: rng.gen(),
+            tyre_temperature: rng.gen(),
+            pit_lane_tyre_sim: rng.gen(),
+            car_damage: rng.gen(),
+            car_damage_rate: rng.gen(),
+            collisions: rng.gen(),
+            collisions_off_for_first_lap_only: rng.gen(),
+            mp_unsafe_pit_release: rng.gen(),
+            mp_off_for_griefing: rng.gen(),
+            corner_cutting_stringency: rng.gen(),
+            parc_ferme_rules: rng.gen(),
+            pit_stop_experience: rng.gen(),
+            safety_car: rng.gen(),
+            safety_car_experience: rng.gen(),
+            formation_lap: rng.gen(),
+            formation_lap_experience: rng.gen(),
+            red_flags: rng.gen(),
+            affects_licence_level_solo: rng.gen(),
+            affects_licence_level_mp: rng.gen(),
+            num_sessions_in_weekend: rng.gen(),
+            weekend_structure: rng.gen(),
+            sector2_lap_distance_start: rng.gen(),
+            sector3_lap_distance_start: rng.gen(),
         };
 
         let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
@@ -505,4 +666,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_session_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketSessionData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketSessionData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_session_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketSessionData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketSessionData::unserialize(&serialized_packet).is_ok());
+    }
 }
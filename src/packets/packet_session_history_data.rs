@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LapHistoryData {
     pub lap_time_in_ms: u32,      // 4 Bytes
     pub sector1_time_in_ms: u16,  // 2 Bytes
@@ -18,6 +20,7 @@ pub struct LapHistoryData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TyreStintHistoryData {
     pub end_lap: u8,              // 1 Byte
     pub tyre_actual_compound: u8, // 1 Byte
@@ -26,16 +29,18 @@ pub struct TyreStintHistoryData {
 
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketSessionHistoryData {
-    pub header: PacketHeader,                                // 29 Bytes
-    pub car_idx: u8,                                         // 1 Byte
-    pub num_laps: u8,                                        // 1 Byte
-    pub num_tyre_stints: u8,                                 // 1 Byte
-    pub best_lap_time_lap_num: u8,                           // 1 Byte
-    pub best_sector1_lap_num: u8,                            // 1 Byte
-    pub best_sector2_lap_num: u8,                            // 1 Byte
-    pub best_sector3_lap_num: u8,                            // 1 Byte
-    pub lap_history_data: [LapHistoryData; 100],             // 1400 Bytes
+    pub header: PacketHeader,      // 29 Bytes
+    pub car_idx: u8,               // 1 Byte
+    pub num_laps: u8,              // 1 Byte
+    pub num_tyre_stints: u8,       // 1 Byte
+    pub best_lap_time_lap_num: u8, // 1 Byte
+    pub best_sector1_lap_num: u8,  // 1 Byte
+    pub best_sector2_lap_num: u8,  // 1 Byte
+    pub best_sector3_lap_num: u8,  // 1 Byte
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array"))]
+    pub lap_history_data: [LapHistoryData; 100], // 1400 Bytes
     pub tyre_stints_history_data: [TyreStintHistoryData; 8], // 24 Bytes
 } // 1460 Bytes
 
@@ -118,7 +123,15 @@ impl TyreStintHistoryData {
 
 impl PacketSessionHistoryData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketSessionHistoryData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes[size_of::<PacketHeader>()..]);
 
         Ok(PacketSessionHistoryData {
@@ -281,4 +294,28 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_session_history_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketSessionHistoryData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketSessionHistoryData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_session_history_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> =
+            PacketSessionHistoryData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketSessionHistoryData::unserialize(&serialized_packet).is_ok());
+    }
 }
@@ -0,0 +1,243 @@
+use super::packet_header::PacketHeader;
+use crate::Error;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeTrialDataSet {
+    pub car_idx: u8,               // 1 Byte
+    pub team_id: u8,               // 1 Byte
+    pub lap_time_in_ms: u32,       // 4 Bytes
+    pub sector1_time_in_ms: u32,   // 4 Bytes
+    pub sector2_time_in_ms: u32,   // 4 Bytes
+    pub sector3_time_in_ms: u32,   // 4 Bytes
+    pub traction_control: u8,      // 1 Byte
+    pub gearbox_assist: u8,        // 1 Byte
+    pub anti_lock_brakes: u8,      // 1 Byte
+    pub equal_car_performance: u8, // 1 Byte
+    pub custom_setup: u8,          // 1 Byte
+    pub valid: u8,                 // 1 Byte
+} // 24 Bytes
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PacketTimeTrialData {
+    pub header: PacketHeader,                           // 29 Bytes
+    pub player_session_best_data_set: TimeTrialDataSet, // 24 Bytes
+    pub personal_best_data_set: TimeTrialDataSet,       // 24 Bytes
+    pub rival_data_set: TimeTrialDataSet,               // 24 Bytes
+} // 101 Bytes
+
+impl TimeTrialDataSet {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(TimeTrialDataSet {
+            car_idx: cursor.read_u8()?,
+            team_id: cursor.read_u8()?,
+            lap_time_in_ms: cursor.read_u32::<LittleEndian>()?,
+            sector1_time_in_ms: cursor.read_u32::<LittleEndian>()?,
+            sector2_time_in_ms: cursor.read_u32::<LittleEndian>()?,
+            sector3_time_in_ms: cursor.read_u32::<LittleEndian>()?,
+            traction_control: cursor.read_u8()?,
+            gearbox_assist: cursor.read_u8()?,
+            anti_lock_brakes: cursor.read_u8()?,
+            equal_car_performance: cursor.read_u8()?,
+            custom_setup: cursor.read_u8()?,
+            valid: cursor.read_u8()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<TimeTrialDataSet>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_u8(self.car_idx)?;
+        cursor.write_u8(self.team_id)?;
+        cursor.write_u32::<LittleEndian>(self.lap_time_in_ms)?;
+        cursor.write_u32::<LittleEndian>(self.sector1_time_in_ms)?;
+        cursor.write_u32::<LittleEndian>(self.sector2_time_in_ms)?;
+        cursor.write_u32::<LittleEndian>(self.sector3_time_in_ms)?;
+        cursor.write_u8(self.traction_control)?;
+        cursor.write_u8(self.gearbox_assist)?;
+        cursor.write_u8(self.anti_lock_brakes)?;
+        cursor.write_u8(self.equal_car_performance)?;
+        cursor.write_u8(self.custom_setup)?;
+        cursor.write_u8(self.valid)?;
+
+        Ok(bytes)
+    }
+}
+
+impl PacketTimeTrialData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketTimeTrialData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketTimeTrialData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            player_session_best_data_set: TimeTrialDataSet::unserialize(
+                &bytes[size_of::<PacketHeader>()
+                    ..size_of::<PacketHeader>() + size_of::<TimeTrialDataSet>()],
+            )?,
+            personal_best_data_set: TimeTrialDataSet::unserialize(
+                &bytes[size_of::<PacketHeader>() + size_of::<TimeTrialDataSet>()
+                    ..size_of::<PacketHeader>() + 2 * size_of::<TimeTrialDataSet>()],
+            )?,
+            rival_data_set: TimeTrialDataSet::unserialize(
+                &bytes[size_of::<PacketHeader>() + 2 * size_of::<TimeTrialDataSet>()
+                    ..size_of::<PacketHeader>() + 3 * size_of::<TimeTrialDataSet>()],
+            )?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketTimeTrialData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_all(&self.player_session_best_data_set.serialize()?)?;
+        cursor.write_all(&self.personal_best_data_set.serialize()?)?;
+        cursor.write_all(&self.rival_data_set.serialize()?)?;
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_time_trial_data_set_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_data: TimeTrialDataSet = TimeTrialDataSet {
+            car_idx: rng.gen(),
+            team_id: rng.gen(),
+            lap_time_in_ms: rng.gen(),
+            sector1_time_in_ms: rng.gen(),
+            sector2_time_in_ms: rng.gen(),
+            sector3_time_in_ms: rng.gen(),
+            traction_control: rng.gen(),
+            gearbox_assist: rng.gen(),
+            anti_lock_brakes: rng.gen(),
+            equal_car_performance: rng.gen(),
+            custom_setup: rng.gen(),
+            valid: rng.gen(),
+        };
+
+        let serialized_data: Vec<u8> = original_data.serialize().unwrap();
+        let deserialized_data: TimeTrialDataSet =
+            TimeTrialDataSet::unserialize(&serialized_data).unwrap();
+
+        assert_eq!(original_data, deserialized_data);
+    }
+
+    #[test]
+    fn test_packet_time_trial_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketTimeTrialData = PacketTimeTrialData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_year: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                overall_frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            player_session_best_data_set: TimeTrialDataSet {
+                car_idx: rng.gen(),
+                team_id: rng.gen(),
+                lap_time_in_ms: rng.gen(),
+                sector1_time_in_ms: rng.gen(),
+                sector2_time_in_ms: rng.gen(),
+                sector3_time_in_ms: rng.gen(),
+                traction_control: rng.gen(),
+                gearbox_assist: rng.gen(),
+                anti_lock_brakes: rng.gen(),
+                equal_car_performance: rng.gen(),
+                custom_setup: rng.gen(),
+                valid: rng.gen(),
+            },
+            personal_best_data_set: TimeTrialDataSet {
+                car_idx: rng.gen(),
+                team_id: rng.gen(),
+                lap_time_in_ms: rng.gen(),
+                sector1_time_in_ms: rng.gen(),
+                sector2_time_in_ms: rng.gen(),
+                sector3_time_in_ms: rng.gen(),
+                traction_control: rng.gen(),
+                gearbox_assist: rng.gen(),
+                anti_lock_brakes: rng.gen(),
+                equal_car_performance: rng.gen(),
+                custom_setup: rng.gen(),
+                valid: rng.gen(),
+            },
+            rival_data_set: TimeTrialDataSet {
+                car_idx: rng.gen(),
+                team_id: rng.gen(),
+                lap_time_in_ms: rng.gen(),
+                sector1_time_in_ms: rng.gen(),
+                sector2_time_in_ms: rng.gen(),
+                sector3_time_in_ms: rng.gen(),
+                traction_control: rng.gen(),
+                gearbox_assist: rng.gen(),
+                anti_lock_brakes: rng.gen(),
+                equal_car_performance: rng.gen(),
+                custom_setup: rng.gen(),
+                valid: rng.gen(),
+            },
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketTimeTrialData =
+            PacketTimeTrialData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_time_trial_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketTimeTrialData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketTimeTrialData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_time_trial_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketTimeTrialData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketTimeTrialData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -1,10 +1,12 @@
 use super::packet_header::PacketHeader;
+use crate::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TyreSetData {
     pub actual_tyre_compound: u8, // 1 Byte
     pub visual_tyre_compound: u8, // 1 Byte
@@ -19,6 +21,7 @@ pub struct TyreSetData {
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketTyreSetsData {
     pub header: PacketHeader,             // 29 Bytes
     pub car_idx: u8,                      // 1 Byte
@@ -65,7 +68,15 @@ impl TyreSetData {
 
 impl PacketTyreSetsData {
     #[allow(dead_code)]
-    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketTyreSetsData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
         let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
 
         Ok(PacketTyreSetsData {
@@ -80,19 +91,18 @@ impl PacketTyreSetsData {
                 for i in 0..20 {
                     tyre_set_data[i] = TyreSetData::unserialize(
                         &bytes[size_of::<PacketHeader>()
-                            + 1 * size_of::<u8>()
+                            + size_of::<u8>()
                             + i * size_of::<TyreSetData>()
                             ..size_of::<PacketHeader>()
-                                + 1 * size_of::<u8>()
+                                + size_of::<u8>()
                                 + (i + 1) * size_of::<TyreSetData>()],
                     )?;
                 }
                 tyre_set_data
             },
             fitted_idx: {
-                let pos: usize = size_of::<PacketHeader>()
-                    + 1 * size_of::<u8>()
-                    + size_of::<[TyreSetData; 20]>();
+                let pos: usize =
+                    size_of::<PacketHeader>() + size_of::<u8>() + size_of::<[TyreSetData; 20]>();
                 cursor.set_position(pos as u64);
                 cursor.read_u8()?
             },
@@ -182,4 +192,27 @@ mod tests {
 
         assert_eq!(original_packet, deserialized_packet);
     }
+
+    #[test]
+    fn test_packet_tyre_sets_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketTyreSetsData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketTyreSetsData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_tyre_sets_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketTyreSetsData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketTyreSetsData::unserialize(&serialized_packet).is_ok());
+    }
 }
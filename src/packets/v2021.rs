@@ -0,0 +1,11 @@
+mod packet_car_damage_data;
+mod packet_event_data;
+mod packet_final_classification_data;
+mod packet_lap_data;
+mod packet_session_data;
+
+pub use packet_car_damage_data::*;
+pub use packet_event_data::*;
+pub use packet_final_classification_data::*;
+pub use packet_lap_data::*;
+pub use packet_session_data::*;
@@ -0,0 +1,153 @@
+use crate::packets;
+use crate::packets::v2022::LapData;
+use crate::packets::v2022::PacketHeader;
+use crate::Error;
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketLapData {
+    pub header: PacketHeader,    // 24 Bytes
+    pub lap_data: [LapData; 22], // 946 Bytes
+} // 970 Bytes
+
+impl PacketLapData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketLapData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketLapData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            lap_data: {
+                let mut lap_data: [LapData; 22] = [LapData::default(); 22];
+                for i in 0..22 {
+                    lap_data[i] = LapData::unserialize(
+                        &bytes[size_of::<PacketHeader>() + i * size_of::<LapData>()
+                            ..size_of::<PacketHeader>() + (i + 1) * size_of::<LapData>()],
+                    )?;
+                }
+                lap_data
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketLapData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        for element in self.lap_data {
+            cursor.write_all(&element.serialize()?)?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketLapData> for packets::PacketLapData {
+    fn from(value: PacketLapData) -> Self {
+        packets::PacketLapData {
+            header: value.header.into(),
+            lap_data: value.lap_data.map(packets::LapData::from),
+            ..packets::PacketLapData::default()
+        }
+    }
+}
+
+impl From<packets::PacketLapData> for PacketLapData {
+    fn from(value: packets::PacketLapData) -> Self {
+        PacketLapData {
+            header: value.header.into(),
+            lap_data: value.lap_data.map(LapData::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_lap_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketLapData = PacketLapData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            lap_data: [LapData {
+                last_lap_time_in_ms: rng.gen(),
+                current_lap_time_in_ms: rng.gen(),
+                sector1_time_in_ms: rng.gen(),
+                sector2_time_in_ms: rng.gen(),
+                lap_distance: rng.gen(),
+                total_distance: rng.gen(),
+                safety_car_delta: rng.gen(),
+                car_position: rng.gen(),
+                current_lap_num: rng.gen(),
+                pit_status: rng.gen(),
+                num_pit_stops: rng.gen(),
+                sector: rng.gen(),
+                current_lap_invalid: rng.gen(),
+                penalties: rng.gen(),
+                total_warnings: rng.gen(),
+                num_unserved_drive_through_pens: rng.gen(),
+                num_unserved_stop_go_pens: rng.gen(),
+                grid_position: rng.gen(),
+                driver_status: rng.gen(),
+                result_status: rng.gen(),
+                pit_lane_timer_active: rng.gen(),
+                pit_lane_time_in_lane_in_ms: rng.gen(),
+                pit_stop_timer_in_ms: rng.gen(),
+                pit_stop_should_serve_pen: rng.gen(),
+            }; 22],
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketLapData =
+            PacketLapData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_lap_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketLapData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketLapData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_lap_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketLapData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketLapData::unserialize(&serialized_packet).is_ok());
+    }
+}
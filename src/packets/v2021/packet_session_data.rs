@@ -0,0 +1,474 @@
+use crate::packets;
+use crate::packets::v2022::PacketHeader;
+use crate::packets::MarshalZone;
+use crate::packets::WeatherForecastSample;
+use crate::Error;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PacketSessionData {
+    pub header: PacketHeader,                                  // 24 Bytes
+    pub weather: u8,                                           // 1 Byte
+    pub track_temperature: i8,                                 // 1 Byte
+    pub air_temperature: i8,                                   // 1 Byte
+    pub total_laps: u8,                                        // 1 Byte
+    pub track_length: u16,                                     // 2 Bytes
+    pub session_type: u8,                                      // 1 Byte
+    pub track_id: i8,                                          // 1 Byte
+    pub formula: u8,                                           // 1 Byte
+    pub session_time_left: u16,                                // 2 Bytes
+    pub session_duration: u16,                                 // 2 Bytes
+    pub pit_speed_limit: u8,                                   // 1 Byte
+    pub game_paused: u8,                                       // 1 Byte
+    pub is_spectating: u8,                                     // 1 Byte
+    pub spectator_car_index: u8,                               // 1 Byte
+    pub sli_pro_native_support: u8,                            // 1 Byte
+    pub num_marshal_zones: u8,                                 // 1 Byte
+    pub marshal_zones: [MarshalZone; 21],                      // 105 Bytes
+    pub safety_car_status: u8,                                 // 1 Byte
+    pub network_game: u8,                                      // 1 Byte
+    pub num_weather_forecast_samples: u8,                      // 1 Byte
+    pub weather_forecast_samples: [WeatherForecastSample; 56], // 448 Bytes
+    pub forecast_accuracy: u8,                                 // 1 Byte
+    pub ai_difficulty: u8,                                     // 1 Byte
+    pub season_link_identifier: u32,                           // 4 Bytes
+    pub weekend_link_identifier: u32,                          // 4 Bytes
+    pub session_link_identifier: u32,                          // 4 Bytes
+    pub pit_stop_window_ideal_lap: u8,                         // 1 Byte
+    pub pit_stop_window_latest_lap: u8,                        // 1 Byte
+    pub pit_stop_rejoin_position: u8,                          // 1 Byte
+    pub steering_assist: u8,                                   // 1 Byte
+    pub braking_assist: u8,                                    // 1 Byte
+    pub gearbox_assist: u8,                                    // 1 Byte
+    pub pit_assist: u8,                                        // 1 Byte
+    pub pit_release_assist: u8,                                // 1 Byte
+    pub ers_assist: u8,                                        // 1 Byte
+    pub drs_assist: u8,                                        // 1 Byte
+    pub dynamic_racing_line: u8,                               // 1 Byte
+    pub dynamic_racing_line_type: u8,                          // 1 Byte
+} // 625 Bytes
+
+impl Default for PacketSessionData {
+    fn default() -> Self {
+        PacketSessionData {
+            header: PacketHeader::default(),
+            weather: 0u8,
+            track_temperature: 0i8,
+            air_temperature: 0i8,
+            total_laps: 0u8,
+            track_length: 0u16,
+            session_type: 0u8,
+            track_id: 0i8,
+            formula: 0u8,
+            session_time_left: 0u16,
+            session_duration: 0u16,
+            pit_speed_limit: 0u8,
+            game_paused: 0u8,
+            is_spectating: 0u8,
+            spectator_car_index: 0u8,
+            sli_pro_native_support: 0u8,
+            num_marshal_zones: 0u8,
+            marshal_zones: [MarshalZone::default(); 21],
+            safety_car_status: 0u8,
+            network_game: 0u8,
+            num_weather_forecast_samples: 0u8,
+            weather_forecast_samples: [WeatherForecastSample::default(); 56],
+            forecast_accuracy: 0u8,
+            ai_difficulty: 0u8,
+            season_link_identifier: 0u32,
+            weekend_link_identifier: 0u32,
+            session_link_identifier: 0u32,
+            pit_stop_window_ideal_lap: 0u8,
+            pit_stop_window_latest_lap: 0u8,
+            pit_stop_rejoin_position: 0u8,
+            steering_assist: 0u8,
+            braking_assist: 0u8,
+            gearbox_assist: 0u8,
+            pit_assist: 0u8,
+            pit_release_assist: 0u8,
+            ers_assist: 0u8,
+            drs_assist: 0u8,
+            dynamic_racing_line: 0u8,
+            dynamic_racing_line_type: 0u8,
+        }
+    }
+}
+
+impl PacketSessionData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketSessionData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(PacketSessionData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            weather: {
+                let pos: usize = size_of::<PacketHeader>();
+                cursor.set_position(pos as u64);
+                cursor.read_u8()?
+            },
+            track_temperature: cursor.read_i8()?,
+            air_temperature: cursor.read_i8()?,
+            total_laps: cursor.read_u8()?,
+            track_length: cursor.read_u16::<LittleEndian>()?,
+            session_type: cursor.read_u8()?,
+            track_id: cursor.read_i8()?,
+            formula: cursor.read_u8()?,
+            session_time_left: cursor.read_u16::<LittleEndian>()?,
+            session_duration: cursor.read_u16::<LittleEndian>()?,
+            pit_speed_limit: cursor.read_u8()?,
+            game_paused: cursor.read_u8()?,
+            is_spectating: cursor.read_u8()?,
+            spectator_car_index: cursor.read_u8()?,
+            sli_pro_native_support: cursor.read_u8()?,
+            num_marshal_zones: cursor.read_u8()?,
+            marshal_zones: {
+                let mut marshal_zones: [MarshalZone; 21] = [MarshalZone::default(); 21];
+                for i in 0..21 {
+                    marshal_zones[i] = MarshalZone::unserialize(
+                        &bytes[size_of::<PacketHeader>()
+                            + 3 * size_of::<i8>()
+                            + 10 * size_of::<u8>()
+                            + 3 * size_of::<u16>()
+                            + i * size_of::<MarshalZone>()
+                            ..size_of::<PacketHeader>()
+                                + 3 * size_of::<i8>()
+                                + 10 * size_of::<u8>()
+                                + 3 * size_of::<u16>()
+                                + (i + 1) * size_of::<MarshalZone>()],
+                    )?
+                }
+
+                marshal_zones
+            },
+            safety_car_status: {
+                let pos: usize = size_of::<PacketHeader>()
+                    + 3 * size_of::<i8>()
+                    + 10 * size_of::<u8>()
+                    + 3 * size_of::<u16>()
+                    + size_of::<[MarshalZone; 21]>();
+                cursor.set_position(pos as u64);
+                cursor.read_u8()?
+            },
+            network_game: cursor.read_u8()?,
+            num_weather_forecast_samples: cursor.read_u8()?,
+            weather_forecast_samples: {
+                let mut weather_forecast_samples: [WeatherForecastSample; 56] =
+                    [WeatherForecastSample::default(); 56];
+                for i in 0..56 {
+                    weather_forecast_samples[i] = WeatherForecastSample::unserialize(
+                        &bytes[size_of::<PacketHeader>()
+                            + 3 * size_of::<i8>()
+                            + 13 * size_of::<u8>()
+                            + 3 * size_of::<u16>()
+                            + size_of::<[MarshalZone; 21]>()
+                            + i * size_of::<WeatherForecastSample>()
+                            ..size_of::<PacketHeader>()
+                                + 3 * size_of::<i8>()
+                                + 13 * size_of::<u8>()
+                                + 3 * size_of::<u16>()
+                                + size_of::<[MarshalZone; 21]>()
+                                + (i + 1) * size_of::<WeatherForecastSample>()],
+                    )?;
+                }
+                weather_forecast_samples
+            },
+            forecast_accuracy: {
+                let pos: usize = size_of::<PacketHeader>()
+                    + 3 * size_of::<i8>()
+                    + 13 * size_of::<u8>()
+                    + 3 * size_of::<u16>()
+                    + size_of::<[MarshalZone; 21]>()
+                    + size_of::<[WeatherForecastSample; 56]>();
+                cursor.set_position(pos as u64);
+                cursor.read_u8()?
+            },
+            ai_difficulty: cursor.read_u8()?,
+            season_link_identifier: cursor.read_u32::<LittleEndian>()?,
+            weekend_link_identifier: cursor.read_u32::<LittleEndian>()?,
+            session_link_identifier: cursor.read_u32::<LittleEndian>()?,
+            pit_stop_window_ideal_lap: cursor.read_u8()?,
+            pit_stop_window_latest_lap: cursor.read_u8()?,
+            pit_stop_rejoin_position: cursor.read_u8()?,
+            steering_assist: cursor.read_u8()?,
+            braking_assist: cursor.read_u8()?,
+            gearbox_assist: cursor.read_u8()?,
+            pit_assist: cursor.read_u8()?,
+            pit_release_assist: cursor.read_u8()?,
+            ers_assist: cursor.read_u8()?,
+            drs_assist: cursor.read_u8()?,
+            dynamic_racing_line: cursor.read_u8()?,
+            dynamic_racing_line_type: cursor.read_u8()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<MarshalZone>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_u8(self.weather)?;
+        cursor.write_i8(self.track_temperature)?;
+        cursor.write_i8(self.air_temperature)?;
+        cursor.write_u8(self.total_laps)?;
+        cursor.write_u16::<LittleEndian>(self.track_length)?;
+        cursor.write_u8(self.session_type)?;
+        cursor.write_i8(self.track_id)?;
+        cursor.write_u8(self.formula)?;
+        cursor.write_u16::<LittleEndian>(self.session_time_left)?;
+        cursor.write_u16::<LittleEndian>(self.session_duration)?;
+        cursor.write_u8(self.pit_speed_limit)?;
+        cursor.write_u8(self.game_paused)?;
+        cursor.write_u8(self.is_spectating)?;
+        cursor.write_u8(self.spectator_car_index)?;
+        cursor.write_u8(self.sli_pro_native_support)?;
+        cursor.write_u8(self.num_marshal_zones)?;
+        for element in self.marshal_zones {
+            cursor.write_all(&element.serialize()?)?;
+        }
+        cursor.write_u8(self.safety_car_status)?;
+        cursor.write_u8(self.network_game)?;
+        cursor.write_u8(self.num_weather_forecast_samples)?;
+        for element in self.weather_forecast_samples {
+            cursor.write_all(&element.serialize()?)?;
+        }
+        cursor.write_u8(self.forecast_accuracy)?;
+        cursor.write_u8(self.ai_difficulty)?;
+        cursor.write_u32::<LittleEndian>(self.season_link_identifier)?;
+        cursor.write_u32::<LittleEndian>(self.weekend_link_identifier)?;
+        cursor.write_u32::<LittleEndian>(self.session_link_identifier)?;
+        cursor.write_u8(self.pit_stop_window_ideal_lap)?;
+        cursor.write_u8(self.pit_stop_window_latest_lap)?;
+        cursor.write_u8(self.pit_stop_rejoin_position)?;
+        cursor.write_u8(self.steering_assist)?;
+        cursor.write_u8(self.braking_assist)?;
+        cursor.write_u8(self.gearbox_assist)?;
+        cursor.write_u8(self.pit_assist)?;
+        cursor.write_u8(self.pit_release_assist)?;
+        cursor.write_u8(self.ers_assist)?;
+        cursor.write_u8(self.drs_assist)?;
+        cursor.write_u8(self.dynamic_racing_line)?;
+        cursor.write_u8(self.dynamic_racing_line_type)?;
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketSessionData> for packets::PacketSessionData {
+    fn from(value: PacketSessionData) -> Self {
+        packets::PacketSessionData {
+            header: value.header.into(),
+            weather: value.weather,
+            track_temperature: value.track_temperature,
+            air_temperature: value.air_temperature,
+            total_laps: value.total_laps,
+            track_length: value.track_length,
+            session_type: value.session_type,
+            track_id: value.track_id,
+            formula: value.formula,
+            session_time_left: value.session_time_left,
+            session_duration: value.session_duration,
+            pit_speed_limit: value.pit_speed_limit,
+            game_paused: value.game_paused,
+            is_spectating: value.is_spectating,
+            spectator_car_index: value.spectator_car_index,
+            sli_pro_native_support: value.sli_pro_native_support,
+            num_marshal_zones: value.num_marshal_zones,
+            marshal_zones: value.marshal_zones,
+            safety_car_status: value.safety_car_status,
+            network_game: value.network_game,
+            num_weather_forecast_samples: value.num_weather_forecast_samples,
+            weather_forecast_samples: {
+                let mut weather_forecast_samples: [WeatherForecastSample; 64] =
+                    [WeatherForecastSample::default(); 64];
+                weather_forecast_samples[..56].copy_from_slice(&value.weather_forecast_samples);
+                weather_forecast_samples
+            },
+            forecast_accuracy: value.forecast_accuracy,
+            ai_difficulty: value.ai_difficulty,
+            season_link_identifier: value.season_link_identifier,
+            weekend_link_identifier: value.weekend_link_identifier,
+            session_link_identifier: value.session_link_identifier,
+            pit_stop_window_ideal_lap: value.pit_stop_window_ideal_lap,
+            pit_stop_window_latest_lap: value.pit_stop_window_latest_lap,
+            pit_stop_rejoin_position: value.pit_stop_rejoin_position,
+            steering_assist: value.steering_assist,
+            braking_assist: value.braking_assist,
+            gearbox_assist: value.gearbox_assist,
+            pit_assist: value.pit_assist,
+            pit_release_assist: value.pit_release_assist,
+            ers_assist: value.ers_assist,
+            drs_assist: value.drs_assist,
+            dynamic_racing_line: value.dynamic_racing_line,
+            dynamic_racing_line_type: value.dynamic_racing_line_type,
+            ..packets::PacketSessionData::default()
+        }
+    }
+}
+
+impl From<packets::PacketSessionData> for PacketSessionData {
+    fn from(value: packets::PacketSessionData) -> Self {
+        PacketSessionData {
+            header: value.header.into(),
+            weather: value.weather,
+            track_temperature: value.track_temperature,
+            air_temperature: value.air_temperature,
+            total_laps: value.total_laps,
+            track_length: value.track_length,
+            session_type: value.session_type,
+            track_id: value.track_id,
+            formula: value.formula,
+            session_time_left: value.session_time_left,
+            session_duration: value.session_duration,
+            pit_speed_limit: value.pit_speed_limit,
+            game_paused: value.game_paused,
+            is_spectating: value.is_spectating,
+            spectator_car_index: value.spectator_car_index,
+            sli_pro_native_support: value.sli_pro_native_support,
+            num_marshal_zones: value.num_marshal_zones,
+            marshal_zones: value.marshal_zones,
+            safety_car_status: value.safety_car_status,
+            network_game: value.network_game,
+            num_weather_forecast_samples: value.num_weather_forecast_samples,
+            weather_forecast_samples: {
+                let mut weather_forecast_samples: [WeatherForecastSample; 56] =
+                    [WeatherForecastSample::default(); 56];
+                weather_forecast_samples.copy_from_slice(&value.weather_forecast_samples[..56]);
+                weather_forecast_samples
+            },
+            forecast_accuracy: value.forecast_accuracy,
+            ai_difficulty: value.ai_difficulty,
+            season_link_identifier: value.season_link_identifier,
+            weekend_link_identifier: value.weekend_link_identifier,
+            session_link_identifier: value.session_link_identifier,
+            pit_stop_window_ideal_lap: value.pit_stop_window_ideal_lap,
+            pit_stop_window_latest_lap: value.pit_stop_window_latest_lap,
+            pit_stop_rejoin_position: value.pit_stop_rejoin_position,
+            steering_assist: value.steering_assist,
+            braking_assist: value.braking_assist,
+            gearbox_assist: value.gearbox_assist,
+            pit_assist: value.pit_assist,
+            pit_release_assist: value.pit_release_assist,
+            ers_assist: value.ers_assist,
+            drs_assist: value.drs_assist,
+            dynamic_racing_line: value.dynamic_racing_line,
+            dynamic_racing_line_type: value.dynamic_racing_line_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_session_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketSessionData = PacketSessionData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            weather: rng.gen(),
+            track_temperature: rng.gen(),
+            air_temperature: rng.gen(),
+            total_laps: rng.gen(),
+            track_length: rng.gen(),
+            session_type: rng.gen(),
+            track_id: rng.gen(),
+            formula: rng.gen(),
+            session_time_left: rng.gen(),
+            session_duration: rng.gen(),
+            pit_speed_limit: rng.gen(),
+            game_paused: rng.gen(),
+            is_spectating: rng.gen(),
+            spectator_car_index: rng.gen(),
+            sli_pro_native_support: rng.gen(),
+            num_marshal_zones: rng.gen(),
+            marshal_zones: [MarshalZone {
+                zone_start: rng.gen(),
+                zone_flag: rng.gen(),
+            }; 21],
+            safety_car_status: rng.gen(),
+            network_game: rng.gen(),
+            num_weather_forecast_samples: rng.gen(),
+            weather_forecast_samples: [WeatherForecastSample {
+                session_type: rng.gen(),
+                time_offset: rng.gen(),
+                weather: rng.gen(),
+                track_temperature: rng.gen(),
+                track_temperature_change: rng.gen(),
+                air_temperature: rng.gen(),
+                air_temperature_change: rng.gen(),
+                rain_percentage: rng.gen(),
+            }; 56],
+            forecast_accuracy: rng.gen(),
+            ai_difficulty: rng.gen(),
+            season_link_identifier: rng.gen(),
+            weekend_link_identifier: rng.gen(),
+            session_link_identifier: rng.gen(),
+            pit_stop_window_ideal_lap: rng.gen(),
+            pit_stop_window_latest_lap: rng.gen(),
+            pit_stop_rejoin_position: rng.gen(),
+            steering_assist: rng.gen(),
+            braking_assist: rng.gen(),
+            gearbox_assist: rng.gen(),
+            pit_assist: rng.gen(),
+            pit_release_assist: rng.gen(),
+            ers_assist: rng.gen(),
+            drs_assist: rng.gen(),
+            dynamic_racing_line: rng.gen(),
+            dynamic_racing_line_type: rng.gen(),
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketSessionData =
+            PacketSessionData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_session_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketSessionData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketSessionData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_session_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketSessionData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketSessionData::unserialize(&serialized_packet).is_ok());
+    }
+}
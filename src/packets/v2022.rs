@@ -0,0 +1,27 @@
+mod packet_car_damage_data;
+mod packet_car_setup_data;
+mod packet_car_status_data;
+mod packet_car_telemetry_data;
+mod packet_event_data;
+mod packet_final_classification_data;
+mod packet_header;
+mod packet_lap_data;
+mod packet_lobby_info_data;
+mod packet_motion_data;
+mod packet_participants_data;
+mod packet_session_data;
+mod packet_session_history_data;
+
+pub use packet_car_damage_data::*;
+pub use packet_car_setup_data::*;
+pub use packet_car_status_data::*;
+pub use packet_car_telemetry_data::*;
+pub use packet_event_data::*;
+pub use packet_final_classification_data::*;
+pub use packet_header::*;
+pub use packet_lap_data::*;
+pub use packet_lobby_info_data::*;
+pub use packet_motion_data::*;
+pub use packet_participants_data::*;
+pub use packet_session_data::*;
+pub use packet_session_history_data::*;
@@ -0,0 +1,149 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::CarDamageData;
+use crate::Error;
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketCarDamageData {
+    pub header: PacketHeader,                 // 24 Bytes
+    pub car_damage_data: [CarDamageData; 22], // 924 Bytes
+} // 948 Bytes
+
+impl PacketCarDamageData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarDamageData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketCarDamageData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            car_damage_data: {
+                let mut car_damage_data: [CarDamageData; 22] = [CarDamageData::default(); 22];
+                for i in 0..22 {
+                    car_damage_data[i] = CarDamageData::unserialize(
+                        &bytes[size_of::<PacketHeader>() + i * size_of::<CarDamageData>()
+                            ..size_of::<PacketHeader>() + (i + 1) * size_of::<CarDamageData>()],
+                    )?;
+                }
+                car_damage_data
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketCarDamageData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        for element in self.car_damage_data {
+            cursor.write_all(&element.serialize()?)?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketCarDamageData> for packets::PacketCarDamageData {
+    fn from(value: PacketCarDamageData) -> Self {
+        packets::PacketCarDamageData {
+            header: value.header.into(),
+            car_damage_data: value.car_damage_data,
+        }
+    }
+}
+
+impl From<packets::PacketCarDamageData> for PacketCarDamageData {
+    fn from(value: packets::PacketCarDamageData) -> Self {
+        PacketCarDamageData {
+            header: value.header.into(),
+            car_damage_data: value.car_damage_data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_car_damage_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketCarDamageData = PacketCarDamageData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            car_damage_data: [CarDamageData {
+                tyres_wear: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                tyres_damage: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                brakes_damage: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                front_left_wing_damage: rng.gen(),
+                front_right_wing_damage: rng.gen(),
+                rear_wing_damage: rng.gen(),
+                floor_damage: rng.gen(),
+                diffuser_damage: rng.gen(),
+                sidepod_damage: rng.gen(),
+                drs_fault: rng.gen(),
+                ers_fault: rng.gen(),
+                gearbox_damage: rng.gen(),
+                engine_damage: rng.gen(),
+                engine_mguh_wear: rng.gen(),
+                engine_es_wear: rng.gen(),
+                engine_ce_wear: rng.gen(),
+                engine_ice_wear: rng.gen(),
+                engine_mguk_wear: rng.gen(),
+                engine_tc_wear: rng.gen(),
+                engine_blown: rng.gen(),
+                engine_seized: rng.gen(),
+            }; 22],
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketCarDamageData =
+            PacketCarDamageData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_car_damage_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarDamageData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarDamageData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_damage_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarDamageData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarDamageData::unserialize(&serialized_packet).is_ok());
+    }
+}
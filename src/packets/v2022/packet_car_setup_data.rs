@@ -0,0 +1,151 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::v2023::CarSetupData;
+use crate::Error;
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketCarSetupData {
+    pub header: PacketHeader,           // 24 Bytes
+    pub car_setups: [CarSetupData; 22], // 1078 Bytes
+} // 1102 Bytes
+
+impl PacketCarSetupData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarSetupData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketCarSetupData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            car_setups: {
+                let mut car_setups: [CarSetupData; 22] = [CarSetupData::default(); 22];
+                for i in 0..22 {
+                    car_setups[i] = CarSetupData::unserialize(
+                        &bytes[size_of::<PacketHeader>() + i * size_of::<CarSetupData>()
+                            ..size_of::<PacketHeader>() + (i + 1) * size_of::<CarSetupData>()],
+                    )?;
+                }
+                car_setups
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<CarSetupData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        for element in self.car_setups {
+            cursor.write_all(&element.serialize()?)?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketCarSetupData> for packets::PacketCarSetupData {
+    fn from(value: PacketCarSetupData) -> Self {
+        packets::PacketCarSetupData {
+            header: value.header.into(),
+            car_setups: value.car_setups.map(packets::CarSetupData::from),
+            ..packets::PacketCarSetupData::default()
+        }
+    }
+}
+
+impl From<packets::PacketCarSetupData> for PacketCarSetupData {
+    fn from(value: packets::PacketCarSetupData) -> Self {
+        PacketCarSetupData {
+            header: value.header.into(),
+            car_setups: value.car_setups.map(CarSetupData::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_car_setup_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketCarSetupData = PacketCarSetupData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            car_setups: [CarSetupData {
+                front_wing: rng.gen(),
+                rear_wing: rng.gen(),
+                on_throttle: rng.gen(),
+                off_throttle: rng.gen(),
+                front_camber: rng.gen(),
+                rear_camber: rng.gen(),
+                front_toe: rng.gen(),
+                rear_toe: rng.gen(),
+                front_suspension: rng.gen(),
+                rear_suspension: rng.gen(),
+                front_anti_roll_bar: rng.gen(),
+                rear_anti_roll_bar: rng.gen(),
+                front_suspension_height: rng.gen(),
+                rear_suspension_height: rng.gen(),
+                brake_pressure: rng.gen(),
+                brake_bias: rng.gen(),
+                rear_left_tyre_pressure: rng.gen(),
+                rear_right_tyre_pressure: rng.gen(),
+                front_left_tyre_pressure: rng.gen(),
+                front_right_tyre_pressure: rng.gen(),
+                ballast: rng.gen(),
+                fuel_load: rng.gen(),
+            }; 22],
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketCarSetupData =
+            PacketCarSetupData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_car_setup_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarSetupData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarSetupData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_setup_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarSetupData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarSetupData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::CarTelemetryData;
+use crate::Error;
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketCarTelemetryData {
+    pub header: PacketHeader,                       // 24 Bytes
+    pub car_telemetry_data: [CarTelemetryData; 22], // 1320 Bytes
+    pub mfd_panel_index: u8,                        // 1 Byte
+    pub mfd_panel_index_secondary_player: u8,       // 1 Byte
+    pub suggested_gear: i8,                         // 1 Byte
+} // 1347 Bytes
+
+impl PacketCarTelemetryData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketCarTelemetryData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(PacketCarTelemetryData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            car_telemetry_data: {
+                let mut car_telemetry_data: [CarTelemetryData; 22] =
+                    [CarTelemetryData::default(); 22];
+                for i in 0..22 {
+                    car_telemetry_data[i] = CarTelemetryData::unserialize(
+                        &bytes[size_of::<PacketHeader>() + i * size_of::<CarTelemetryData>()
+                            ..size_of::<PacketHeader>() + (i + 1) * size_of::<CarTelemetryData>()],
+                    )?;
+                }
+                car_telemetry_data
+            },
+            mfd_panel_index: {
+                let pos: usize = size_of::<PacketHeader>() + size_of::<[CarTelemetryData; 22]>();
+                cursor.set_position(pos as u64);
+                cursor.read_u8()?
+            },
+            mfd_panel_index_secondary_player: cursor.read_u8()?,
+            suggested_gear: cursor.read_i8()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketCarTelemetryData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        for element in self.car_telemetry_data {
+            cursor.write_all(&element.serialize()?)?;
+        }
+        cursor.write_u8(self.mfd_panel_index)?;
+        cursor.write_u8(self.mfd_panel_index_secondary_player)?;
+        cursor.write_i8(self.suggested_gear)?;
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketCarTelemetryData> for packets::PacketCarTelemetryData {
+    fn from(value: PacketCarTelemetryData) -> Self {
+        packets::PacketCarTelemetryData {
+            header: value.header.into(),
+            car_telemetry_data: value.car_telemetry_data,
+            mfd_panel_index: value.mfd_panel_index,
+            mfd_panel_index_secondary_player: value.mfd_panel_index_secondary_player,
+            suggested_gear: value.suggested_gear,
+        }
+    }
+}
+
+impl From<packets::PacketCarTelemetryData> for PacketCarTelemetryData {
+    fn from(value: packets::PacketCarTelemetryData) -> Self {
+        PacketCarTelemetryData {
+            header: value.header.into(),
+            car_telemetry_data: value.car_telemetry_data,
+            mfd_panel_index: value.mfd_panel_index,
+            mfd_panel_index_secondary_player: value.mfd_panel_index_secondary_player,
+            suggested_gear: value.suggested_gear,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_car_telemetry_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            car_telemetry_data: [CarTelemetryData {
+                speed: rng.gen(),
+                throttle: rng.gen(),
+                steer: rng.gen(),
+                brake: rng.gen(),
+                clutch: rng.gen(),
+                gear: rng.gen(),
+                engine_rpm: rng.gen(),
+                drs: rng.gen(),
+                rev_lights_percent: rng.gen(),
+                rev_lights_bit_value: rng.gen(),
+                brakes_temperature: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                tyres_surface_temperature: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                tyres_inner_temperature: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                engine_temperature: 95u16,
+                tyres_pressure: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+                surface_type: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            }; 22],
+            mfd_panel_index: rng.gen(),
+            mfd_panel_index_secondary_player: rng.gen(),
+            suggested_gear: rng.gen(),
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketCarTelemetryData =
+            PacketCarTelemetryData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_car_telemetry_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketCarTelemetryData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketCarTelemetryData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_car_telemetry_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketCarTelemetryData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketCarTelemetryData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -0,0 +1,128 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::Event;
+use crate::Error;
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PacketEventData {
+    pub header: PacketHeader,
+    pub event: Event,
+}
+
+impl PacketEventData {
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketHeader>() + 16;
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PacketEventData {
+            header: PacketHeader::unserialize(bytes)?,
+            event: Event::unserialize(&bytes[size_of::<PacketHeader>()..])?,
+        })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(40);
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_all(&self.event.serialize()?)?;
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketEventData> for packets::PacketEventData {
+    fn from(value: PacketEventData) -> Self {
+        packets::PacketEventData {
+            header: value.header.into(),
+            event: value.event,
+        }
+    }
+}
+
+impl From<packets::PacketEventData> for PacketEventData {
+    fn from(value: packets::PacketEventData) -> Self {
+        PacketEventData {
+            header: value.header.into(),
+            event: value.event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::FastestLap;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_event_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketEventData = PacketEventData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            event: Event::FastestLap(FastestLap {
+                vehicle_idx: rng.gen(),
+                lap_time: rng.gen(),
+            }),
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketEventData =
+            PacketEventData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(serialized_packet.len(), 40);
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_event_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketEventData {
+            header: PacketHeader::default(),
+            event: Event::SessionStarted,
+        }
+        .serialize()
+        .unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketEventData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_event_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketEventData {
+            header: PacketHeader::default(),
+            event: Event::SessionStarted,
+        }
+        .serialize()
+        .unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketEventData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -0,0 +1,191 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::FinalClassificationData;
+use crate::Error;
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketFinalClassificationData {
+    pub header: PacketHeader,                               // 24 Bytes
+    pub num_cars: u8,                                       // 1 Byte
+    pub classification_data: [FinalClassificationData; 22], // 990 Bytes
+} // 1015 Bytes
+
+impl PacketFinalClassificationData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketFinalClassificationData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(PacketFinalClassificationData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            num_cars: {
+                let pos: usize = size_of::<PacketHeader>();
+                cursor.set_position(pos as u64);
+                cursor.read_u8()?
+            },
+            classification_data: {
+                let mut classification_data: [FinalClassificationData; 22] =
+                    [FinalClassificationData::default(); 22];
+                for i in 0..22 {
+                    classification_data[i] = FinalClassificationData::unserialize(
+                        &bytes[size_of::<PacketHeader>()
+                            + size_of::<u8>()
+                            + i * size_of::<FinalClassificationData>()
+                            ..size_of::<PacketHeader>()
+                                + size_of::<u8>()
+                                + (i + 1) * size_of::<FinalClassificationData>()],
+                    )?;
+                }
+                classification_data
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<FinalClassificationData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_u8(self.num_cars)?;
+        for element in self.classification_data {
+            cursor.write_all(&element.serialize()?)?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketFinalClassificationData> for packets::PacketFinalClassificationData {
+    fn from(value: PacketFinalClassificationData) -> Self {
+        packets::PacketFinalClassificationData {
+            header: value.header.into(),
+            num_cars: value.num_cars,
+            classification_data: value.classification_data,
+        }
+    }
+}
+
+impl From<packets::PacketFinalClassificationData> for PacketFinalClassificationData {
+    fn from(value: packets::PacketFinalClassificationData) -> Self {
+        PacketFinalClassificationData {
+            header: value.header.into(),
+            num_cars: value.num_cars,
+            classification_data: value.classification_data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_final_classification_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketFinalClassificationData = PacketFinalClassificationData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            num_cars: rng.gen(),
+            classification_data: [FinalClassificationData {
+                position: rng.gen(),
+                num_laps: rng.gen(),
+                grid_position: rng.gen(),
+                points: rng.gen(),
+                num_pit_stops: rng.gen(),
+                result_status: rng.gen(),
+                best_lap_time_in_ms: rng.gen(),
+                total_race_time: rng.gen(),
+                penalties_time: rng.gen(),
+                num_penalties: rng.gen(),
+                num_tyre_stints: rng.gen(),
+                tyre_stints_actual: [
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                ],
+                tyre_stints_visual: [
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                ],
+                tyre_stints_end_laps: [
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                ],
+            }; 22],
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketFinalClassificationData =
+            PacketFinalClassificationData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_final_classification_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketFinalClassificationData::default()
+            .serialize()
+            .unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketFinalClassificationData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_final_classification_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketFinalClassificationData::default()
+            .serialize()
+            .unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketFinalClassificationData::unserialize(&serialized_packet).is_ok());
+    }
+}
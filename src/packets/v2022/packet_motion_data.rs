@@ -0,0 +1,278 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::packets::CarMotionData;
+use crate::Error;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketMotionData {
+    pub header: PacketHeader,                 // 24 Bytes
+    pub car_motion_data: [CarMotionData; 22], // 1320 Bytes
+    pub suspension_position: [f32; 4],        // 16 Bytes
+    pub suspension_velocity: [f32; 4],        // 16 Bytes
+    pub suspension_acceleration: [f32; 4],    // 16 Bytes
+    pub wheel_speed: [f32; 4],                // 16 Bytes
+    pub wheel_slip: [f32; 4],                 // 16 Bytes
+    pub local_velocity_x: f32,                // 4 Bytes
+    pub local_velocity_y: f32,                // 4 Bytes
+    pub local_velocity_z: f32,                // 4 Bytes
+    pub angular_velocity_x: f32,              // 4 Bytes
+    pub angular_velocity_y: f32,              // 4 Bytes
+    pub angular_velocity_z: f32,              // 4 Bytes
+    pub angular_acceleration_x: f32,          // 4 Bytes
+    pub angular_acceleration_y: f32,          // 4 Bytes
+    pub angular_acceleration_z: f32,          // 4 Bytes
+    pub front_wheels_angle: f32,              // 4 Bytes
+} // 1464 Bytes
+
+impl PacketMotionData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketMotionData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(PacketMotionData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            car_motion_data: {
+                let mut car_motion_data: [CarMotionData; 22] = [CarMotionData::default(); 22];
+                for i in 0..22 {
+                    car_motion_data[i] = CarMotionData::unserialize(
+                        &bytes[size_of::<PacketHeader>() + i * size_of::<CarMotionData>()
+                            ..size_of::<PacketHeader>() + (i + 1) * size_of::<CarMotionData>()],
+                    )?;
+                }
+                car_motion_data
+            },
+            suspension_position: {
+                let pos: usize = size_of::<PacketHeader>() + size_of::<[CarMotionData; 22]>();
+                cursor.set_position(pos as u64);
+                [
+                    cursor.read_f32::<LittleEndian>()?,
+                    cursor.read_f32::<LittleEndian>()?,
+                    cursor.read_f32::<LittleEndian>()?,
+                    cursor.read_f32::<LittleEndian>()?,
+                ]
+            },
+            suspension_velocity: [
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+            ],
+            suspension_acceleration: [
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+            ],
+            wheel_speed: [
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+            ],
+            wheel_slip: [
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+                cursor.read_f32::<LittleEndian>()?,
+            ],
+            local_velocity_x: cursor.read_f32::<LittleEndian>()?,
+            local_velocity_y: cursor.read_f32::<LittleEndian>()?,
+            local_velocity_z: cursor.read_f32::<LittleEndian>()?,
+            angular_velocity_x: cursor.read_f32::<LittleEndian>()?,
+            angular_velocity_y: cursor.read_f32::<LittleEndian>()?,
+            angular_velocity_z: cursor.read_f32::<LittleEndian>()?,
+            angular_acceleration_x: cursor.read_f32::<LittleEndian>()?,
+            angular_acceleration_y: cursor.read_f32::<LittleEndian>()?,
+            angular_acceleration_z: cursor.read_f32::<LittleEndian>()?,
+            front_wheels_angle: cursor.read_f32::<LittleEndian>()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketMotionData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        for element in self.car_motion_data {
+            cursor.write_all(&element.serialize()?)?;
+        }
+        for element in self.suspension_position {
+            cursor.write_f32::<LittleEndian>(element)?;
+        }
+        for element in self.suspension_velocity {
+            cursor.write_f32::<LittleEndian>(element)?;
+        }
+        for element in self.suspension_acceleration {
+            cursor.write_f32::<LittleEndian>(element)?;
+        }
+        for element in self.wheel_speed {
+            cursor.write_f32::<LittleEndian>(element)?;
+        }
+        for element in self.wheel_slip {
+            cursor.write_f32::<LittleEndian>(element)?;
+        }
+        cursor.write_f32::<LittleEndian>(self.local_velocity_x)?;
+        cursor.write_f32::<LittleEndian>(self.local_velocity_y)?;
+        cursor.write_f32::<LittleEndian>(self.local_velocity_z)?;
+        cursor.write_f32::<LittleEndian>(self.angular_velocity_x)?;
+        cursor.write_f32::<LittleEndian>(self.angular_velocity_y)?;
+        cursor.write_f32::<LittleEndian>(self.angular_velocity_z)?;
+        cursor.write_f32::<LittleEndian>(self.angular_acceleration_x)?;
+        cursor.write_f32::<LittleEndian>(self.angular_acceleration_y)?;
+        cursor.write_f32::<LittleEndian>(self.angular_acceleration_z)?;
+        cursor.write_f32::<LittleEndian>(self.front_wheels_angle)?;
+
+        Ok(bytes)
+    }
+}
+
+impl From<PacketMotionData> for packets::PacketMotionData {
+    fn from(value: PacketMotionData) -> Self {
+        packets::PacketMotionData {
+            header: value.header.into(),
+            car_motion_data: value.car_motion_data,
+        }
+    }
+}
+
+// The player car's extended motion data, which later formats send on its own
+// as the motion ex packet.
+impl From<PacketMotionData> for packets::PacketMotionExData {
+    fn from(value: PacketMotionData) -> Self {
+        packets::PacketMotionExData {
+            header: packets::PacketHeader {
+                packet_id: 13,
+                ..value.header.into()
+            },
+            suspension_position: value.suspension_position,
+            suspension_velocity: value.suspension_velocity,
+            suspension_acceleration: value.suspension_acceleration,
+            wheel_speed: value.wheel_speed,
+            wheel_slip_ratio: value.wheel_slip,
+            local_velocity_x: value.local_velocity_x,
+            local_velocity_y: value.local_velocity_y,
+            local_velocity_z: value.local_velocity_z,
+            angular_velocity_x: value.angular_velocity_x,
+            angular_velocity_y: value.angular_velocity_y,
+            angular_velocity_z: value.angular_velocity_z,
+            angular_acceleration_x: value.angular_acceleration_x,
+            angular_acceleration_y: value.angular_acceleration_y,
+            angular_acceleration_z: value.angular_acceleration_z,
+            front_wheels_angle: value.front_wheels_angle,
+            ..packets::PacketMotionExData::default()
+        }
+    }
+}
+
+impl From<packets::PacketMotionData> for PacketMotionData {
+    fn from(value: packets::PacketMotionData) -> Self {
+        PacketMotionData {
+            header: value.header.into(),
+            car_motion_data: value.car_motion_data,
+            ..PacketMotionData::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_packet_motion_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketMotionData = PacketMotionData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            car_motion_data: [CarMotionData {
+                world_position_x: rng.gen(),
+                world_position_y: rng.gen(),
+                world_position_z: rng.gen(),
+                world_velocity_x: rng.gen(),
+                world_velocity_y: rng.gen(),
+                world_velocity_z: rng.gen(),
+                world_forward_dir_x: rng.gen(),
+                world_forward_dir_y: rng.gen(),
+                world_forward_dir_z: rng.gen(),
+                world_right_dir_x: rng.gen(),
+                world_right_dir_y: rng.gen(),
+                world_right_dir_z: rng.gen(),
+                g_force_lateral: rng.gen(),
+                g_force_longitudinal: rng.gen(),
+                g_force_vertical: rng.gen(),
+                yaw: rng.gen(),
+                pitch: rng.gen(),
+                roll: rng.gen(),
+            }; 22],
+            suspension_position: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            suspension_velocity: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            suspension_acceleration: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            wheel_speed: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            wheel_slip: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            local_velocity_x: rng.gen(),
+            local_velocity_y: rng.gen(),
+            local_velocity_z: rng.gen(),
+            angular_velocity_x: rng.gen(),
+            angular_velocity_y: rng.gen(),
+            angular_velocity_z: rng.gen(),
+            angular_acceleration_x: rng.gen(),
+            angular_acceleration_y: rng.gen(),
+            angular_acceleration_z: rng.gen(),
+            front_wheels_angle: rng.gen(),
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketMotionData =
+            PacketMotionData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_motion_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketMotionData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketMotionData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_motion_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketMotionData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketMotionData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -0,0 +1,280 @@
+use super::packet_header::PacketHeader;
+use crate::packets;
+use crate::Error;
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticipantData {
+    pub ai_controlled: u8,  // 1 Byte
+    pub driver_id: u8,      // 1 Byte
+    pub network_id: u8,     // 1 Byte
+    pub team_id: u8,        // 1 Byte
+    pub my_team: u8,        // 1 Byte
+    pub race_number: u8,    // 1 Byte
+    pub nationality: u8,    // 1 Byte
+    pub name: [u8; 48],     // 48 Bytes
+    pub your_telemetry: u8, // 1 Byte
+} // 56 Bytes
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PacketParticipantsData {
+    pub header: PacketHeader,                // 24 Bytes
+    pub num_active_cars: u8,                 // 1 Byte
+    pub participants: [ParticipantData; 22], // 1232 Bytes
+} // 1257 Bytes
+
+impl Default for ParticipantData {
+    fn default() -> Self {
+        ParticipantData {
+            ai_controlled: 0u8,
+            driver_id: 0u8,
+            network_id: 0u8,
+            team_id: 0u8,
+            my_team: 0u8,
+            race_number: 0u8,
+            nationality: 0u8,
+            name: [0u8; 48],
+            your_telemetry: 0u8,
+        }
+    }
+}
+
+impl ParticipantData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        let mut cursor: Cursor<&[u8]> = Cursor::new(bytes);
+
+        Ok(ParticipantData {
+            ai_controlled: cursor.read_u8()?,
+            driver_id: cursor.read_u8()?,
+            network_id: cursor.read_u8()?,
+            team_id: cursor.read_u8()?,
+            my_team: cursor.read_u8()?,
+            race_number: cursor.read_u8()?,
+            nationality: cursor.read_u8()?,
+            name: {
+                let mut name: [u8; 48] = [0u8; 48];
+                for element in name.iter_mut() {
+                    *element = cursor.read_u8()?;
+                }
+                name
+            },
+            your_telemetry: cursor.read_u8()?,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<ParticipantData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_u8(self.ai_controlled)?;
+        cursor.write_u8(self.driver_id)?;
+        cursor.write_u8(self.network_id)?;
+        cursor.write_u8(self.team_id)?;
+        cursor.write_u8(self.my_team)?;
+        cursor.write_u8(self.race_number)?;
+        cursor.write_u8(self.nationality)?;
+        for element in self.name {
+            cursor.write_u8(element)?;
+        }
+        cursor.write_u8(self.your_telemetry)?;
+
+        Ok(bytes)
+    }
+}
+
+impl PacketParticipantsData {
+    #[allow(dead_code)]
+    pub fn unserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let expected: usize = size_of::<PacketParticipantsData>();
+        if bytes.len() < expected {
+            return Err(Error::TruncatedPacket {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut cursor: Cursor<&[u8]> = Cursor::new(&bytes[size_of::<PacketHeader>()..]);
+
+        Ok(PacketParticipantsData {
+            header: PacketHeader::unserialize(&bytes[..size_of::<PacketHeader>()])?,
+            num_active_cars: cursor.read_u8()?,
+            participants: {
+                let mut participants: [ParticipantData; 22] = [ParticipantData::default(); 22];
+                for i in 0..22 {
+                    participants[i] = ParticipantData::unserialize(
+                        &bytes[size_of::<PacketHeader>()
+                            + size_of::<u8>()
+                            + i * size_of::<ParticipantData>()
+                            ..size_of::<PacketHeader>()
+                                + size_of::<u8>()
+                                + (i + 1) * size_of::<ParticipantData>()],
+                    )?;
+                }
+                participants
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn serialize(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<PacketParticipantsData>());
+        let mut cursor: Cursor<&mut Vec<u8>> = Cursor::new(&mut bytes);
+
+        cursor.write_all(&self.header.serialize()?)?;
+        cursor.write_u8(self.num_active_cars)?;
+        for element in self.participants {
+            cursor.write_all(&element.serialize()?)?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl From<ParticipantData> for packets::ParticipantData {
+    fn from(value: ParticipantData) -> Self {
+        packets::ParticipantData {
+            ai_controlled: value.ai_controlled,
+            driver_id: value.driver_id,
+            network_id: value.network_id,
+            team_id: value.team_id,
+            my_team: value.my_team,
+            race_number: value.race_number,
+            nationality: value.nationality,
+            name: value.name,
+            your_telemetry: value.your_telemetry,
+            ..packets::ParticipantData::default()
+        }
+    }
+}
+
+impl From<packets::ParticipantData> for ParticipantData {
+    fn from(value: packets::ParticipantData) -> Self {
+        ParticipantData {
+            ai_controlled: value.ai_controlled,
+            driver_id: value.driver_id,
+            network_id: value.network_id,
+            team_id: value.team_id,
+            my_team: value.my_team,
+            race_number: value.race_number,
+            nationality: value.nationality,
+            name: value.name,
+            your_telemetry: value.your_telemetry,
+        }
+    }
+}
+
+impl From<PacketParticipantsData> for packets::PacketParticipantsData {
+    fn from(value: PacketParticipantsData) -> Self {
+        packets::PacketParticipantsData {
+            header: value.header.into(),
+            num_active_cars: value.num_active_cars,
+            participants: value.participants.map(packets::ParticipantData::from),
+        }
+    }
+}
+
+impl From<packets::PacketParticipantsData> for PacketParticipantsData {
+    fn from(value: packets::PacketParticipantsData) -> Self {
+        PacketParticipantsData {
+            header: value.header.into(),
+            num_active_cars: value.num_active_cars,
+            participants: value.participants.map(ParticipantData::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{distributions::Alphanumeric, Rng};
+
+    #[test]
+    fn test_participant_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_data: ParticipantData = ParticipantData {
+            ai_controlled: rng.gen(),
+            driver_id: rng.gen(),
+            network_id: rng.gen(),
+            team_id: rng.gen(),
+            my_team: rng.gen(),
+            race_number: rng.gen(),
+            nationality: rng.gen(),
+            name: [rng.sample(Alphanumeric); 48],
+            your_telemetry: rng.gen(),
+        };
+
+        let serialized_data: Vec<u8> = original_data.serialize().unwrap();
+        let deserialized_data: ParticipantData =
+            ParticipantData::unserialize(&serialized_data).unwrap();
+
+        assert_eq!(original_data, deserialized_data);
+    }
+
+    #[test]
+    fn test_packet_participants_data_serialization_deserialization() {
+        let mut rng = rand::thread_rng();
+
+        let original_packet: PacketParticipantsData = PacketParticipantsData {
+            header: PacketHeader {
+                packet_format: rng.gen(),
+                game_major_version: rng.gen(),
+                game_minor_version: rng.gen(),
+                packet_version: rng.gen(),
+                packet_id: rng.gen(),
+                session_uid: rng.gen(),
+                session_time: rng.gen(),
+                frame_identifier: rng.gen(),
+                player_car_index: rng.gen(),
+                secondary_player_car_index: rng.gen(),
+            },
+            num_active_cars: rng.gen(),
+            participants: [ParticipantData {
+                ai_controlled: rng.gen(),
+                driver_id: rng.gen(),
+                network_id: rng.gen(),
+                team_id: rng.gen(),
+                my_team: rng.gen(),
+                race_number: rng.gen(),
+                nationality: rng.gen(),
+                name: [rng.sample(Alphanumeric); 48],
+                your_telemetry: rng.gen(),
+            }; 22],
+        };
+
+        let serialized_packet: Vec<u8> = original_packet.serialize().unwrap();
+        let deserialized_packet: PacketParticipantsData =
+            PacketParticipantsData::unserialize(&serialized_packet).unwrap();
+
+        assert_eq!(original_packet, deserialized_packet);
+    }
+
+    #[test]
+    fn test_packet_participants_data_truncated() {
+        let serialized_packet: Vec<u8> = PacketParticipantsData::default().serialize().unwrap();
+
+        for len in 0..serialized_packet.len() {
+            assert!(matches!(
+                PacketParticipantsData::unserialize(&serialized_packet[..len]),
+                Err(Error::TruncatedPacket { expected, actual })
+                    if expected == serialized_packet.len() && actual == len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_packet_participants_data_oversized() {
+        let mut rng = rand::thread_rng();
+
+        let mut serialized_packet: Vec<u8> = PacketParticipantsData::default().serialize().unwrap();
+        serialized_packet.extend((0..2048).map(|_| rng.gen::<u8>()));
+
+        assert!(PacketParticipantsData::unserialize(&serialized_packet).is_ok());
+    }
+}
@@ -0,0 +1,13 @@
+mod packet_car_setup_data;
+mod packet_lap_data;
+mod packet_lobby_info_data;
+mod packet_motion_ex_data;
+mod packet_participants_data;
+mod packet_session_data;
+
+pub use packet_car_setup_data::*;
+pub use packet_lap_data::*;
+pub use packet_lobby_info_data::*;
+pub use packet_motion_ex_data::*;
+pub use packet_participants_data::*;
+pub use packet_session_data::*;
@@ -0,0 +1,408 @@
+use crate::columns::{Columns, Value};
+use crate::packets::{
+    followed_lap_data, CarDamageData, CarMotionData, CarStatusData, CarTelemetryData, LapData,
+    PacketHeader,
+};
+use crate::{Error, Packet, PacketSink};
+use arrow_array::{
+    ArrayRef, Float32Array, Int16Array, Int8Array, RecordBatch, UInt16Array, UInt32Array,
+    UInt64Array, UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+const BATCH_SIZE: usize = 4096;
+// Row groups are cut on lap changes. The cap only bounds the rows held in
+// memory when no lap change comes, e.g. in a long practice session.
+const MAX_ROW_GROUP_SIZE: usize = 256 * 1024;
+
+struct Table<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    rows: Vec<Vec<Value>>,
+}
+
+impl<W: Write + Send> Table<W> {
+    fn new<T: Columns>(writer: W) -> Result<Self, Error> {
+        let mut names: Vec<String> = vec![
+            "session_uid".to_string(),
+            "session_time".to_string(),
+            "frame_identifier".to_string(),
+            "car_index".to_string(),
+        ];
+        T::names(&mut names);
+        let mut values: Vec<Value> = row(&PacketHeader::default(), 0);
+        T::default().values(&mut values);
+        let fields: Vec<Field> = names
+            .into_iter()
+            .zip(values)
+            .map(|(name, value)| Field::new(name, data_type(value), false))
+            .collect();
+        let schema: SchemaRef = Arc::new(Schema::new(fields));
+        let properties: WriterProperties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_max_row_group_size(MAX_ROW_GROUP_SIZE)
+            .build();
+
+        Ok(Table {
+            writer: ArrowWriter::try_new(writer, Arc::clone(&schema), Some(properties))?,
+            schema,
+            rows: Vec::new(),
+        })
+    }
+
+    fn push<T: Columns>(&mut self, header: &PacketHeader, cars: &[T]) -> Result<(), Error> {
+        for (car_index, car) in cars.iter().enumerate() {
+            let mut values: Vec<Value> = row(header, car_index as u8);
+            car.values(&mut values);
+            self.rows.push(values);
+        }
+        if self.rows.len() >= BATCH_SIZE {
+            self.write_batch()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> Result<(), Error> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let columns: Vec<ArrayRef> = (0..self.schema.fields().len())
+            .map(|index| column(&self.rows, index))
+            .collect();
+        self.writer
+            .write(&RecordBatch::try_new(Arc::clone(&self.schema), columns)?)?;
+        self.rows.clear();
+
+        Ok(())
+    }
+
+    fn end_row_group(&mut self) -> Result<(), Error> {
+        self.write_batch()?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<W, Error> {
+        self.write_batch()?;
+
+        Ok(self.writer.into_inner()?)
+    }
+}
+
+fn row(header: &PacketHeader, car_index: u8) -> Vec<Value> {
+    vec![
+        Value::from(header.session_uid),
+        Value::from(header.session_time),
+        Value::from(header.frame_identifier),
+        Value::from(car_index),
+    ]
+}
+
+fn data_type(value: Value) -> DataType {
+    match value {
+        Value::U8(_) => DataType::UInt8,
+        Value::I8(_) => DataType::Int8,
+        Value::U16(_) => DataType::UInt16,
+        Value::I16(_) => DataType::Int16,
+        Value::U32(_) => DataType::UInt32,
+        Value::U64(_) => DataType::UInt64,
+        Value::F32(_) => DataType::Float32,
+    }
+}
+
+// Every row of a table is built by the same `Columns` impl, so a column's
+// values all share the variant of its first row.
+fn column(rows: &[Vec<Value>], index: usize) -> ArrayRef {
+    macro_rules! array {
+        ($array:ident, $variant:ident) => {
+            Arc::new($array::from_iter_values(rows.iter().map(|row| {
+                match row[index] {
+                    Value::$variant(value) => value,
+                    value => unreachable!("mixed column types: {value:?}"),
+                }
+            })))
+        };
+    }
+
+    match rows[0][index] {
+        Value::U8(_) => array!(UInt8Array, U8),
+        Value::I8(_) => array!(Int8Array, I8),
+        Value::U16(_) => array!(UInt16Array, U16),
+        Value::I16(_) => array!(Int16Array, I16),
+        Value::U32(_) => array!(UInt32Array, U32),
+        Value::U64(_) => array!(UInt64Array, U64),
+        Value::F32(_) => array!(Float32Array, F32),
+    }
+}
+
+// Writes one table per packet type, with one row per packet and car. A new
+// row group is started whenever a lap packet shows the player, or the race
+// leader when spectating, on another lap, and whenever the session changes.
+pub struct ParquetExporter<W: Write + Send = File> {
+    car_telemetry: Table<W>,
+    car_status: Table<W>,
+    car_motion: Table<W>,
+    lap: Table<W>,
+    car_damage: Table<W>,
+    row_group: Option<(u64, Option<u8>)>,
+}
+
+impl ParquetExporter {
+    // Writes car_telemetry.parquet, car_status.parquet, car_motion.parquet,
+    // lap.parquet and car_damage.parquet into the directory.
+    pub fn create(directory: impl AsRef<Path>) -> Result<Self, Error> {
+        fs::create_dir_all(&directory)?;
+        let file = |table: &str| File::create(directory.as_ref().join(format!("{table}.parquet")));
+        ParquetExporter::new([
+            file("car_telemetry")?,
+            file("car_status")?,
+            file("car_motion")?,
+            file("lap")?,
+            file("car_damage")?,
+        ])
+    }
+}
+
+impl<W: Write + Send> ParquetExporter<W> {
+    // Writers for the car telemetry, car status, car motion, lap and car damage
+    // tables, in that order.
+    pub fn new(writers: [W; 5]) -> Result<Self, Error> {
+        let [car_telemetry, car_status, car_motion, lap, car_damage] = writers;
+
+        Ok(ParquetExporter {
+            car_telemetry: Table::new::<CarTelemetryData>(car_telemetry)?,
+            car_status: Table::new::<CarStatusData>(car_status)?,
+            car_motion: Table::new::<CarMotionData>(car_motion)?,
+            lap: Table::new::<LapData>(lap)?,
+            car_damage: Table::new::<CarDamageData>(car_damage)?,
+            row_group: None,
+        })
+    }
+
+    // Writes the remaining rows and the file footers.
+    pub fn finish(self) -> Result<[W; 5], Error> {
+        Ok([
+            self.car_telemetry.finish()?,
+            self.car_status.finish()?,
+            self.car_motion.finish()?,
+            self.lap.finish()?,
+            self.car_damage.finish()?,
+        ])
+    }
+
+    fn end_row_group(&mut self) -> Result<(), Error> {
+        self.car_telemetry.end_row_group()?;
+        self.car_status.end_row_group()?;
+        self.car_motion.end_row_group()?;
+        self.lap.end_row_group()?;
+        self.car_damage.end_row_group()
+    }
+}
+
+impl<W: Write + Send> PacketSink for ParquetExporter<W> {
+    fn push(&mut self, packet: &Packet) -> Result<(), Error> {
+        if !matches!(
+            packet,
+            Packet::CarTelemetry(_)
+                | Packet::CarStatus(_)
+                | Packet::Motion(_)
+                | Packet::Lap(_)
+                | Packet::CarDamage(_)
+        ) {
+            return Ok(());
+        }
+        let header: PacketHeader = packet.header();
+        let lap_num: Option<u8> = match packet {
+            Packet::Lap(packet) => {
+                followed_lap_data(packet).map(|lap_data| lap_data.current_lap_num)
+            }
+            _ => None,
+        };
+        match self.row_group {
+            Some((session_uid, _)) if session_uid != header.session_uid => {
+                self.end_row_group()?;
+                self.row_group = Some((header.session_uid, lap_num));
+            }
+            Some((_, Some(current))) if lap_num.is_some_and(|lap_num| lap_num != current) => {
+                self.end_row_group()?;
+                self.row_group = Some((header.session_uid, lap_num));
+            }
+            Some((_, None)) | None => self.row_group = Some((header.session_uid, lap_num)),
+            _ => {}
+        }
+        match packet {
+            Packet::CarTelemetry(packet) => self
+                .car_telemetry
+                .push(&header, &packet.car_telemetry_data)?,
+            Packet::CarStatus(packet) => self.car_status.push(&header, &packet.car_status_data)?,
+            Packet::Motion(packet) => self.car_motion.push(&header, &packet.car_motion_data)?,
+            Packet::Lap(packet) => self.lap.push(&header, &packet.lap_data)?,
+            Packet::CarDamage(packet) => self.car_damage.push(&header, &packet.car_damage_data)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{PacketCarTelemetryData, PacketLapData};
+    use crate::test_support::header;
+    use crate::{CaptureReader, Recorder};
+    use arrow_array::cast::as_primitive_array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::path::PathBuf;
+
+    fn telemetry(session_uid: u64, frame_identifier: u32, speed: u16) -> Packet {
+        let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: header(6, session_uid, frame_identifier),
+            ..PacketCarTelemetryData::default()
+        };
+        packet.car_telemetry_data[1].speed = speed;
+        packet.car_telemetry_data[1].tyres_pressure = [21.0, 21.5, 23.0, 23.5];
+        Packet::CarTelemetry(packet)
+    }
+
+    fn lap(session_uid: u64, frame_identifier: u32, current_lap_num: u8) -> Packet {
+        let mut packet: PacketLapData = PacketLapData {
+            header: header(2, session_uid, frame_identifier),
+            ..PacketLapData::default()
+        };
+        packet.lap_data[1].current_lap_num = current_lap_num;
+        Packet::Lap(packet)
+    }
+
+    fn directory(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("f1-telemetry-client-{name}-{}", std::process::id()))
+    }
+
+    // Returns the number of row groups and the batches of a table.
+    fn read(directory: &Path, table: &str) -> (usize, Vec<RecordBatch>) {
+        let file: File = File::open(directory.join(format!("{table}.parquet"))).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let row_groups: usize = builder.metadata().num_row_groups();
+        let batches: Vec<RecordBatch> = builder.build().unwrap().map(Result::unwrap).collect();
+        (row_groups, batches)
+    }
+
+    fn speeds(batches: &[RecordBatch]) -> Vec<u16> {
+        let mut speeds: Vec<u16> = Vec::new();
+        for batch in batches {
+            let car_index: &UInt8Array =
+                as_primitive_array(batch.column_by_name("car_index").unwrap());
+            let speed: &UInt16Array = as_primitive_array(batch.column_by_name("speed").unwrap());
+            for row in 0..batch.num_rows() {
+                if car_index.value(row) == 1 {
+                    speeds.push(speed.value(row));
+                }
+            }
+        }
+        speeds
+    }
+
+    #[test]
+    fn test_parquet_exporter_row_group_per_lap() {
+        let directory: PathBuf = directory("parquet");
+        let mut exporter: ParquetExporter = ParquetExporter::create(&directory).unwrap();
+        for packet in [
+            telemetry(7, 0, 100),
+            lap(7, 0, 1),
+            telemetry(7, 1, 110),
+            lap(7, 1, 1),
+            lap(7, 2, 2),
+            telemetry(7, 2, 120),
+            telemetry(8, 3, 50),
+        ] {
+            exporter.push(&packet).unwrap();
+        }
+        exporter.finish().unwrap();
+
+        let (row_groups, batches) = read(&directory, "car_telemetry");
+        assert_eq!(row_groups, 3);
+        assert_eq!(
+            batches.iter().map(RecordBatch::num_rows).sum::<usize>(),
+            4 * 22
+        );
+        let schema: SchemaRef = batches[0].schema();
+        assert_eq!(schema.field(0).name(), "session_uid");
+        assert_eq!(schema.field(2).name(), "frame_identifier");
+        assert_eq!(schema.field(3).name(), "car_index");
+        assert_eq!(
+            schema
+                .field_with_name("tyres_pressure_fl")
+                .unwrap()
+                .data_type(),
+            &DataType::Float32
+        );
+        assert_eq!(speeds(&batches), vec![100, 110, 120, 50]);
+        let (row_groups, batches) = read(&directory, "lap");
+        assert_eq!(row_groups, 2);
+        assert_eq!(
+            batches.iter().map(RecordBatch::num_rows).sum::<usize>(),
+            3 * 22
+        );
+        let (row_groups, batches) = read(&directory, "car_damage");
+        assert_eq!(row_groups, 0);
+        assert!(batches.is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_parquet_exporter_row_group_per_leader_lap_when_spectating() {
+        let directory: PathBuf = directory("parquet-spectating");
+        let mut exporter: ParquetExporter = ParquetExporter::create(&directory).unwrap();
+        for (frame_identifier, current_lap_num) in [(0, 1), (1, 1), (2, 2), (3, 3)] {
+            let mut packet: PacketLapData = PacketLapData {
+                header: PacketHeader {
+                    player_car_index: 255,
+                    ..header(2, 7, frame_identifier)
+                },
+                ..PacketLapData::default()
+            };
+            packet.lap_data[4].car_position = 1;
+            packet.lap_data[4].current_lap_num = current_lap_num;
+            exporter.push(&Packet::Lap(packet)).unwrap();
+        }
+        exporter.finish().unwrap();
+
+        let (row_groups, _) = read(&directory, "lap");
+        assert_eq!(row_groups, 3);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_parquet_exporter_reads_capture() {
+        let mut recorder: Recorder<Vec<u8>> = Recorder::new(Vec::new()).unwrap();
+        for packet in [telemetry(7, 0, 100), lap(7, 0, 1), telemetry(7, 1, 110)] {
+            recorder.record(&packet.encode().unwrap()).unwrap();
+        }
+        recorder.record(&[0; 10]).unwrap();
+        let bytes: Vec<u8> = recorder.into_inner();
+        let directory: PathBuf = directory("parquet-capture");
+        let mut exporter: ParquetExporter = ParquetExporter::create(&directory).unwrap();
+
+        exporter
+            .push_capture(CaptureReader::new(bytes.as_slice()).unwrap())
+            .unwrap();
+        exporter.finish().unwrap();
+
+        let (row_groups, batches) = read(&directory, "car_telemetry");
+        assert_eq!(row_groups, 1);
+        assert_eq!(speeds(&batches), vec![100, 110]);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,790 @@
+use crate::{Error, Packet};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LOOP: u32 = 108;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const PCAPNG_SECTION_HEADER_BLOCK: [u8; 4] = [0x0a, 0x0d, 0x0d, 0x0a];
+const PCAPNG_INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 6;
+const PCAPNG_OPTION_IF_TSRESOL: u16 = 9;
+
+// Frames larger than this can't come from a sane capture and are treated as corruption.
+const MAX_FRAME_SIZE: usize = 256 * 1024;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_QINQ: u16 = 0x88a8;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcapDatagram {
+    pub timestamp: SystemTime,
+    pub source: SocketAddr,
+    pub destination: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
+impl PcapDatagram {
+    pub fn decode(&self) -> Result<Packet, Error> {
+        Packet::decode(&self.payload)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u32,
+    ticks_per_second: u64,
+}
+
+enum Format {
+    Pcap {
+        big_endian: bool,
+        interface: Interface,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+pub struct PcapReader<R: Read = BufReader<File>> {
+    reader: R,
+    format: Format,
+    port: Option<u16>,
+}
+
+impl PcapReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        PcapReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> PcapReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut magic: [u8; 4] = [0; 4];
+        reader.read_exact(&mut magic)?;
+        let format: Format = match magic {
+            [0xd4, 0xc3, 0xb2, 0xa1] => pcap_format(&mut reader, false, 1_000_000)?,
+            [0xa1, 0xb2, 0xc3, 0xd4] => pcap_format(&mut reader, true, 1_000_000)?,
+            [0x4d, 0x3c, 0xb2, 0xa1] => pcap_format(&mut reader, false, 1_000_000_000)?,
+            [0xa1, 0xb2, 0x3c, 0x4d] => pcap_format(&mut reader, true, 1_000_000_000)?,
+            PCAPNG_SECTION_HEADER_BLOCK => Format::PcapNg {
+                big_endian: read_section_header(&mut reader)?,
+                interfaces: Vec::new(),
+            },
+            _ => return Err(Error::InvalidPcapMagic(magic)),
+        };
+
+        Ok(PcapReader {
+            reader,
+            format,
+            port: None,
+        })
+    }
+
+    pub fn set_port_filter(&mut self, port: Option<u16>) {
+        self.port = port;
+    }
+
+    pub fn read_datagram(&mut self) -> Result<Option<PcapDatagram>, Error> {
+        // A frame cut short at the end of the file marks the end of the capture.
+        loop {
+            let frame: Option<(SystemTime, Interface, Vec<u8>)> = match self.read_frame() {
+                Ok(frame) => frame,
+                Err(Error::Io(e)) if e.kind() == ErrorKind::UnexpectedEof => None,
+                Err(e) => return Err(e),
+            };
+            let Some((timestamp, interface, frame)) = frame else {
+                return Ok(None);
+            };
+            let Some((source, destination, payload)) = udp_datagram(interface.link_type, &frame)
+            else {
+                continue;
+            };
+            if let Some(port) = self.port {
+                if source.port() != port && destination.port() != port {
+                    continue;
+                }
+            }
+
+            return Ok(Some(PcapDatagram {
+                timestamp,
+                source,
+                destination,
+                payload: payload.to_vec(),
+            }));
+        }
+    }
+
+    fn read_frame(&mut self) -> Result<Option<(SystemTime, Interface, Vec<u8>)>, Error> {
+        match &mut self.format {
+            Format::Pcap {
+                big_endian,
+                interface,
+            } => {
+                let mut header: [u8; 16] = [0; 16];
+                if !read_exact_or_eof(&mut self.reader, &mut header)? {
+                    return Ok(None);
+                }
+                let seconds: u64 = u32_at(&header, 0, *big_endian) as u64;
+                let fraction: u64 = u32_at(&header, 4, *big_endian) as u64;
+                let length: usize = u32_at(&header, 8, *big_endian) as usize;
+                let frame: Vec<u8> = read_vec(&mut self.reader, length)?;
+                let timestamp: SystemTime = timestamp(
+                    seconds * interface.ticks_per_second + fraction,
+                    interface.ticks_per_second,
+                );
+
+                Ok(Some((timestamp, *interface, frame)))
+            }
+            Format::PcapNg {
+                big_endian,
+                interfaces,
+            } => loop {
+                let mut head: [u8; 8] = [0; 8];
+                if !read_exact_or_eof(&mut self.reader, &mut head)? {
+                    return Ok(None);
+                }
+                if head[..4] == PCAPNG_SECTION_HEADER_BLOCK {
+                    // A new section may switch byte order and always resets the interfaces.
+                    let mut rest: [u8; 4] = [0; 4];
+                    self.reader.read_exact(&mut rest)?;
+                    let mut section_header: Vec<u8> = head[4..].to_vec();
+                    section_header.extend_from_slice(&rest);
+                    *big_endian = read_section_header(
+                        &mut section_header.as_slice().chain(&mut self.reader),
+                    )?;
+                    interfaces.clear();
+                    continue;
+                }
+                let block_type: u32 = u32_at(&head, 0, *big_endian);
+                let block_length: usize = u32_at(&head, 4, *big_endian) as usize;
+                if block_length < 12
+                    || !block_length.is_multiple_of(4)
+                    || block_length > MAX_FRAME_SIZE
+                {
+                    return Err(invalid_data("bad pcapng block length"));
+                }
+                let block: Vec<u8> = read_vec(&mut self.reader, block_length - 8)?;
+                let body: &[u8] = &block[..block_length - 12];
+                match block_type {
+                    PCAPNG_INTERFACE_DESCRIPTION_BLOCK => {
+                        interfaces.push(interface_description(body, *big_endian)?);
+                    }
+                    PCAPNG_ENHANCED_PACKET_BLOCK => {
+                        if body.len() < 20 {
+                            return Err(invalid_data("short pcapng enhanced packet block"));
+                        }
+                        let interface: Interface = *interfaces
+                            .get(u32_at(body, 0, *big_endian) as usize)
+                            .ok_or_else(|| invalid_data("unknown pcapng interface"))?;
+                        let ticks: u64 = (u32_at(body, 4, *big_endian) as u64) << 32
+                            | u32_at(body, 8, *big_endian) as u64;
+                        let length: usize = u32_at(body, 12, *big_endian) as usize;
+                        let frame: &[u8] = body
+                            .get(20..20 + length)
+                            .ok_or_else(|| invalid_data("short pcapng enhanced packet block"))?;
+                        let timestamp: SystemTime = timestamp(ticks, interface.ticks_per_second);
+
+                        return Ok(Some((timestamp, interface, frame.to_vec())));
+                    }
+                    _ => {}
+                }
+            },
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<PcapDatagram, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_datagram().transpose()
+    }
+}
+
+pub struct PcapWriter<W: Write = File> {
+    writer: W,
+}
+
+impl PcapWriter {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        PcapWriter::new(File::create(path)?)
+    }
+}
+
+impl<W: Write> PcapWriter<W> {
+    pub fn new(mut writer: W) -> Result<Self, Error> {
+        let mut header: Vec<u8> = Vec::with_capacity(24);
+        header.extend_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+        header.extend_from_slice(&2u16.to_le_bytes());
+        header.extend_from_slice(&4u16.to_le_bytes());
+        header.extend_from_slice(&0i32.to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(&65535u32.to_le_bytes());
+        header.extend_from_slice(&LINKTYPE_RAW.to_le_bytes());
+        writer.write_all(&header)?;
+        writer.flush()?;
+
+        Ok(PcapWriter { writer })
+    }
+
+    pub fn write_datagram(
+        &mut self,
+        timestamp: SystemTime,
+        source: SocketAddr,
+        destination: SocketAddr,
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        let frame: Vec<u8> = ip_udp_frame(source, destination, payload)?;
+        let since_epoch: Duration = timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let mut record: Vec<u8> = Vec::with_capacity(16 + frame.len());
+        record.extend_from_slice(&(since_epoch.as_secs() as u32).to_le_bytes());
+        record.extend_from_slice(&since_epoch.subsec_micros().to_le_bytes());
+        record.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        record.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        record.extend_from_slice(&frame);
+        // Like the recorder, write each record in one go and flush it straight away.
+        self.writer.write_all(&record)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+
+    // Flushes the writer and returns it.
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn pcap_format(
+    reader: &mut impl Read,
+    big_endian: bool,
+    ticks_per_second: u64,
+) -> Result<Format, Error> {
+    let mut header: [u8; 20] = [0; 20];
+    reader.read_exact(&mut header)?;
+    let link_type: u32 = u32_at(&header, 16, big_endian) & 0xffff;
+    supported_link_type(link_type)?;
+
+    Ok(Format::Pcap {
+        big_endian,
+        interface: Interface {
+            link_type,
+            ticks_per_second,
+        },
+    })
+}
+
+// Reads the rest of a section header block after its block type and returns its byte order.
+fn read_section_header(reader: &mut impl Read) -> Result<bool, Error> {
+    let mut head: [u8; 8] = [0; 8];
+    reader.read_exact(&mut head)?;
+    let big_endian: bool = match head[4..] {
+        [0x1a, 0x2b, 0x3c, 0x4d] => true,
+        [0x4d, 0x3c, 0x2b, 0x1a] => false,
+        _ => return Err(invalid_data("bad pcapng byte order magic")),
+    };
+    let block_length: usize = u32_at(&head, 0, big_endian) as usize;
+    if block_length < 28 || !block_length.is_multiple_of(4) || block_length > MAX_FRAME_SIZE {
+        return Err(invalid_data("bad pcapng block length"));
+    }
+    read_vec(reader, block_length - 12)?;
+
+    Ok(big_endian)
+}
+
+fn interface_description(body: &[u8], big_endian: bool) -> Result<Interface, Error> {
+    if body.len() < 8 {
+        return Err(invalid_data("short pcapng interface description block"));
+    }
+    let link_type: u32 = u16_at(body, 0, big_endian) as u32;
+    supported_link_type(link_type)?;
+    let mut ticks_per_second: u64 = 1_000_000;
+    let mut options: &[u8] = &body[8..];
+    while options.len() >= 4 {
+        let code: u16 = u16_at(options, 0, big_endian);
+        let length: usize = u16_at(options, 2, big_endian) as usize;
+        let Some(value) = options.get(4..4 + length) else {
+            break;
+        };
+        if code == PCAPNG_OPTION_IF_TSRESOL && length == 1 {
+            let resolution: u32 = (value[0] & 0x7f) as u32;
+            ticks_per_second = if value[0] & 0x80 == 0 {
+                10u64.checked_pow(resolution)
+            } else {
+                2u64.checked_pow(resolution)
+            }
+            .ok_or_else(|| invalid_data("bad pcapng timestamp resolution"))?;
+        }
+        if code == 0 {
+            break;
+        }
+        options = options
+            .get(4 + length.next_multiple_of(4)..)
+            .unwrap_or_default();
+    }
+
+    Ok(Interface {
+        link_type,
+        ticks_per_second,
+    })
+}
+
+fn supported_link_type(link_type: u32) -> Result<(), Error> {
+    match link_type {
+        LINKTYPE_NULL | LINKTYPE_ETHERNET | LINKTYPE_RAW | LINKTYPE_LOOP | LINKTYPE_LINUX_SLL
+        | LINKTYPE_IPV4 | LINKTYPE_IPV6 | LINKTYPE_LINUX_SLL2 => Ok(()),
+        _ => Err(Error::UnsupportedLinkType(link_type)),
+    }
+}
+
+fn udp_datagram(link_type: u32, frame: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let ip: &[u8] = match link_type {
+        LINKTYPE_NULL | LINKTYPE_LOOP => frame.get(4..)?,
+        LINKTYPE_ETHERNET => {
+            let mut offset: usize = 12;
+            let mut ethertype: u16 = be_u16(frame, offset)?;
+            while matches!(ethertype, ETHERTYPE_VLAN | ETHERTYPE_QINQ) {
+                offset += 4;
+                ethertype = be_u16(frame, offset)?;
+            }
+            if !matches!(ethertype, ETHERTYPE_IPV4 | ETHERTYPE_IPV6) {
+                return None;
+            }
+            frame.get(offset + 2..)?
+        }
+        LINKTYPE_LINUX_SLL => frame.get(16..)?,
+        LINKTYPE_LINUX_SLL2 => frame.get(20..)?,
+        _ => frame,
+    };
+    match ip.first()? >> 4 {
+        4 => udp_over_ipv4(ip),
+        6 => udp_over_ipv6(ip),
+        _ => None,
+    }
+}
+
+fn udp_over_ipv4(ip: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let header_length: usize = ((ip.first()? & 0x0f) as usize) * 4;
+    let total_length: usize = be_u16(ip, 2)? as usize;
+    let fragment: u16 = be_u16(ip, 6)?;
+    // Fragmented datagrams are skipped; telemetry packets fit in a single frame.
+    if fragment & 0x3fff != 0 || *ip.get(9)? != IP_PROTOCOL_UDP {
+        return None;
+    }
+    let source: IpAddr = IpAddr::from(<[u8; 4]>::try_from(ip.get(12..16)?).ok()?);
+    let destination: IpAddr = IpAddr::from(<[u8; 4]>::try_from(ip.get(16..20)?).ok()?);
+    let udp: &[u8] = ip.get(header_length..total_length.min(ip.len()))?;
+    udp_payload(source, destination, udp)
+}
+
+fn udp_over_ipv6(ip: &[u8]) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let payload_length: usize = be_u16(ip, 4)? as usize;
+    let source: IpAddr = IpAddr::from(<[u8; 16]>::try_from(ip.get(8..24)?).ok()?);
+    let destination: IpAddr = IpAddr::from(<[u8; 16]>::try_from(ip.get(24..40)?).ok()?);
+    let mut next_header: u8 = *ip.get(6)?;
+    let mut payload: &[u8] = ip.get(40..(40 + payload_length).min(ip.len()))?;
+    loop {
+        match next_header {
+            IP_PROTOCOL_UDP => return udp_payload(source, destination, payload),
+            // Hop-by-hop, routing and destination options extension headers.
+            0 | 43 | 60 => {
+                next_header = *payload.first()?;
+                payload = payload.get((*payload.get(1)? as usize + 1) * 8..)?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn udp_payload(
+    source: IpAddr,
+    destination: IpAddr,
+    udp: &[u8],
+) -> Option<(SocketAddr, SocketAddr, &[u8])> {
+    let source_port: u16 = be_u16(udp, 0)?;
+    let destination_port: u16 = be_u16(udp, 2)?;
+    let length: usize = be_u16(udp, 4)? as usize;
+    let payload: &[u8] = udp.get(8..length.max(8).min(udp.len()))?;
+
+    Some((
+        SocketAddr::new(source, source_port),
+        SocketAddr::new(destination, destination_port),
+        payload,
+    ))
+}
+
+fn ip_udp_frame(
+    source: SocketAddr,
+    destination: SocketAddr,
+    payload: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let udp_length: u16 = u16::try_from(8 + payload.len())
+        .ok()
+        .filter(|length| *length <= u16::MAX - 40)
+        .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidInput, "datagram too large"))?;
+    let (source_ip, destination_ip) = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source_ip), IpAddr::V4(destination_ip)) => {
+            (IpAddr::V4(source_ip), IpAddr::V4(destination_ip))
+        }
+        (source_ip, destination_ip) => (
+            IpAddr::V6(to_ipv6(source_ip)),
+            IpAddr::V6(to_ipv6(destination_ip)),
+        ),
+    };
+
+    let mut udp: Vec<u8> = Vec::with_capacity(udp_length as usize);
+    udp.extend_from_slice(&source.port().to_be_bytes());
+    udp.extend_from_slice(&destination.port().to_be_bytes());
+    udp.extend_from_slice(&udp_length.to_be_bytes());
+    udp.extend_from_slice(&[0, 0]);
+    udp.extend_from_slice(payload);
+
+    let mut pseudo_header: Vec<u8> = Vec::with_capacity(40);
+    let mut frame: Vec<u8> = Vec::with_capacity(40 + udp.len());
+    match (source_ip, destination_ip) {
+        (IpAddr::V4(source_ip), IpAddr::V4(destination_ip)) => {
+            pseudo_header.extend_from_slice(&source_ip.octets());
+            pseudo_header.extend_from_slice(&destination_ip.octets());
+            pseudo_header.extend_from_slice(&[0, IP_PROTOCOL_UDP]);
+            pseudo_header.extend_from_slice(&udp_length.to_be_bytes());
+
+            frame.extend_from_slice(&[0x45, 0]);
+            frame.extend_from_slice(&(20 + udp_length).to_be_bytes());
+            frame.extend_from_slice(&[0, 0, 0x40, 0, 64, IP_PROTOCOL_UDP, 0, 0]);
+            frame.extend_from_slice(&source_ip.octets());
+            frame.extend_from_slice(&destination_ip.octets());
+            let checksum: u16 = checksum(&[&frame]);
+            frame[10..12].copy_from_slice(&checksum.to_be_bytes());
+        }
+        (IpAddr::V6(source_ip), IpAddr::V6(destination_ip)) => {
+            pseudo_header.extend_from_slice(&source_ip.octets());
+            pseudo_header.extend_from_slice(&destination_ip.octets());
+            pseudo_header.extend_from_slice(&(udp_length as u32).to_be_bytes());
+            pseudo_header.extend_from_slice(&[0, 0, 0, IP_PROTOCOL_UDP]);
+
+            frame.extend_from_slice(&[0x60, 0, 0, 0]);
+            frame.extend_from_slice(&udp_length.to_be_bytes());
+            frame.extend_from_slice(&[IP_PROTOCOL_UDP, 64]);
+            frame.extend_from_slice(&source_ip.octets());
+            frame.extend_from_slice(&destination_ip.octets());
+        }
+        _ => unreachable!("addresses were converted to the same family above"),
+    }
+    let udp_checksum: u16 = match checksum(&[&pseudo_header, &udp]) {
+        0 => 0xffff,
+        udp_checksum => udp_checksum,
+    };
+    udp[6..8].copy_from_slice(&udp_checksum.to_be_bytes());
+    frame.extend_from_slice(&udp);
+
+    Ok(frame)
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+fn checksum(parts: &[&[u8]]) -> u16 {
+    let mut sum: u32 = 0;
+    for part in parts {
+        for chunk in part.chunks(2) {
+            let word: u16 = u16::from_be_bytes([chunk[0], *chunk.get(1).unwrap_or(&0)]);
+            sum += word as u32;
+        }
+    }
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+fn timestamp(ticks: u64, ticks_per_second: u64) -> SystemTime {
+    let seconds: u64 = ticks / ticks_per_second;
+    let nanos: u64 =
+        ((ticks % ticks_per_second) as u128 * 1_000_000_000 / ticks_per_second as u128) as u64;
+    UNIX_EPOCH + Duration::from_secs(seconds) + Duration::from_nanos(nanos)
+}
+
+fn u16_at(bytes: &[u8], offset: usize, big_endian: bool) -> u16 {
+    let value: [u8; 2] = [bytes[offset], bytes[offset + 1]];
+    if big_endian {
+        u16::from_be_bytes(value)
+    } else {
+        u16::from_le_bytes(value)
+    }
+}
+
+fn u32_at(bytes: &[u8], offset: usize, big_endian: bool) -> u32 {
+    let value: [u8; 4] = [
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ];
+    if big_endian {
+        u32::from_be_bytes(value)
+    } else {
+        u32::from_le_bytes(value)
+    }
+}
+
+fn be_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_exact_or_eof(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool, Error> {
+    let mut filled: usize = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(Error::Io(ErrorKind::UnexpectedEof.into())),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+    Ok(true)
+}
+
+fn read_vec(reader: &mut impl Read, length: usize) -> Result<Vec<u8>, Error> {
+    if length > MAX_FRAME_SIZE {
+        return Err(invalid_data("frame too large"));
+    }
+    let mut bytes: Vec<u8> = vec![0; length];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::Io(std::io::Error::new(ErrorKind::InvalidData, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{PacketHeader, PacketLapData};
+    use rand::Rng;
+
+    fn lap_packet(frame_identifier: u32) -> Vec<u8> {
+        Packet::Lap(PacketLapData {
+            header: PacketHeader {
+                packet_format: 2024,
+                packet_id: 2,
+                frame_identifier,
+                ..PacketHeader::default()
+            },
+            ..PacketLapData::default()
+        })
+        .encode()
+        .unwrap()
+    }
+
+    fn ethernet_frame(source: SocketAddr, destination: SocketAddr, payload: &[u8]) -> Vec<u8> {
+        let ip: Vec<u8> = ip_udp_frame(source, destination, payload).unwrap();
+        let mut frame: Vec<u8> = vec![0; 12];
+        frame.extend_from_slice(&ETHERTYPE_VLAN.to_be_bytes());
+        frame.extend_from_slice(&[0, 1]);
+        frame.extend_from_slice(
+            &(if source.is_ipv4() {
+                ETHERTYPE_IPV4
+            } else {
+                ETHERTYPE_IPV6
+            })
+            .to_be_bytes(),
+        );
+        frame.extend_from_slice(&ip);
+        frame
+    }
+
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let length: u32 = (12 + body.len().next_multiple_of(4)) as u32;
+        let mut block: Vec<u8> = Vec::new();
+        block.extend_from_slice(&block_type.to_be_bytes());
+        block.extend_from_slice(&length.to_be_bytes());
+        block.extend_from_slice(body);
+        block.resize(length as usize - 4, 0);
+        block.extend_from_slice(&length.to_be_bytes());
+        block
+    }
+
+    fn pcapng(frames: &[(u64, Vec<u8>)]) -> Vec<u8> {
+        let mut section_header: Vec<u8> = vec![0x1a, 0x2b, 0x3c, 0x4d, 0, 1, 0, 0];
+        section_header.extend_from_slice(&u64::MAX.to_be_bytes());
+        let mut bytes: Vec<u8> = pcapng_block(
+            u32::from_be_bytes(PCAPNG_SECTION_HEADER_BLOCK),
+            &section_header,
+        );
+        let mut interface: Vec<u8> = Vec::new();
+        interface.extend_from_slice(&(LINKTYPE_ETHERNET as u16).to_be_bytes());
+        interface.extend_from_slice(&[0, 0, 0, 0, 0xff, 0xff]);
+        interface.extend_from_slice(&PCAPNG_OPTION_IF_TSRESOL.to_be_bytes());
+        interface.extend_from_slice(&[0, 1, 9, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend(pcapng_block(PCAPNG_INTERFACE_DESCRIPTION_BLOCK, &interface));
+        for (nanos, frame) in frames {
+            let mut body: Vec<u8> = Vec::new();
+            body.extend_from_slice(&0u32.to_be_bytes());
+            body.extend_from_slice(&((nanos >> 32) as u32).to_be_bytes());
+            body.extend_from_slice(&(*nanos as u32).to_be_bytes());
+            body.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+            body.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+            body.extend_from_slice(frame);
+            bytes.extend(pcapng_block(PCAPNG_ENHANCED_PACKET_BLOCK, &body));
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_pcap_writer_reader_round_trip() {
+        let source: SocketAddr = "192.168.1.20:51234".parse().unwrap();
+        let destination: SocketAddr = "192.168.1.10:20777".parse().unwrap();
+        let timestamp: SystemTime = UNIX_EPOCH + Duration::from_micros(1_700_000_000_250_000);
+        let mut writer: PcapWriter<Vec<u8>> = PcapWriter::new(Vec::new()).unwrap();
+        for frame_identifier in 0..3 {
+            writer
+                .write_datagram(
+                    timestamp,
+                    source,
+                    destination,
+                    &lap_packet(frame_identifier),
+                )
+                .unwrap();
+        }
+        let bytes: Vec<u8> = writer.into_inner();
+
+        let datagrams: Vec<PcapDatagram> = PcapReader::new(bytes.as_slice())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(datagrams.len(), 3);
+        for (frame_identifier, datagram) in datagrams.iter().enumerate() {
+            assert_eq!(datagram.timestamp, timestamp);
+            assert_eq!(datagram.source, source);
+            assert_eq!(datagram.destination, destination);
+            assert_eq!(
+                { datagram.decode().unwrap().header().frame_identifier },
+                frame_identifier as u32
+            );
+        }
+    }
+
+    #[test]
+    fn test_pcap_writer_produces_valid_checksums() {
+        let source: SocketAddr = "10.0.0.2:40000".parse().unwrap();
+        let destination: SocketAddr = "10.0.0.1:20777".parse().unwrap();
+
+        let frame: Vec<u8> = ip_udp_frame(source, destination, &[1, 2, 3]).unwrap();
+
+        assert_eq!(checksum(&[&frame[..20]]), 0);
+        let mut pseudo_header: Vec<u8> = frame[12..20].to_vec();
+        pseudo_header.extend_from_slice(&[0, IP_PROTOCOL_UDP, 0, 11]);
+        assert_eq!(checksum(&[&pseudo_header, &frame[20..]]), 0);
+    }
+
+    #[test]
+    fn test_pcapng_reader_with_port_filter() {
+        let game: SocketAddr = "[fe80::1]:51234".parse().unwrap();
+        let client: SocketAddr = "[fe80::2]:20777".parse().unwrap();
+        let other: SocketAddr = "[fe80::2]:5353".parse().unwrap();
+        let bytes: Vec<u8> = pcapng(&[
+            (1_000_000_000, ethernet_frame(game, client, &lap_packet(1))),
+            (1_500_000_000, ethernet_frame(game, other, &[0; 10])),
+            (2_000_000_000, ethernet_frame(game, client, &lap_packet(2))),
+        ]);
+        let mut reader: PcapReader<&[u8]> = PcapReader::new(bytes.as_slice()).unwrap();
+        reader.set_port_filter(Some(20777));
+
+        let datagrams: Vec<PcapDatagram> = reader.collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(datagrams.len(), 2);
+        assert_eq!(datagrams[0].timestamp, UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(datagrams[1].timestamp, UNIX_EPOCH + Duration::from_secs(2));
+        assert_eq!(datagrams[0].source, game);
+        assert_eq!(datagrams[1].destination, client);
+        assert_eq!(datagrams[1].payload, lap_packet(2));
+    }
+
+    #[test]
+    fn test_pcap_reader_stops_at_truncated_frame() {
+        let source: SocketAddr = "10.0.0.2:40000".parse().unwrap();
+        let destination: SocketAddr = "10.0.0.1:20777".parse().unwrap();
+        let mut writer: PcapWriter<Vec<u8>> = PcapWriter::new(Vec::new()).unwrap();
+        writer
+            .write_datagram(UNIX_EPOCH, source, destination, &[1, 2, 3])
+            .unwrap();
+        let complete: usize = writer.writer.len();
+        writer
+            .write_datagram(UNIX_EPOCH, source, destination, &[4, 5, 6])
+            .unwrap();
+        let bytes: Vec<u8> = writer.into_inner();
+
+        for len in complete..bytes.len() {
+            let datagrams: Vec<PcapDatagram> = PcapReader::new(&bytes[..len])
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            assert_eq!(datagrams.len(), 1);
+            assert_eq!(datagrams[0].payload, vec![1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn test_pcap_reader_skips_malformed_frames() {
+        let mut rng = rand::thread_rng();
+
+        for link_type in [LINKTYPE_ETHERNET, LINKTYPE_RAW, LINKTYPE_LINUX_SLL] {
+            for _ in 0..100 {
+                let frame: Vec<u8> = (0..rng.gen_range(0..64)).map(|_| rng.gen()).collect();
+                udp_datagram(link_type, &frame);
+            }
+            for len in 0..14 {
+                udp_datagram(link_type, &[0x45; 14][..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcap_reader_rejects_bad_magic() {
+        assert!(matches!(
+            PcapReader::new(&b"F1TC\x01\x00"[..]),
+            Err(Error::InvalidPcapMagic(magic)) if magic == *b"F1TC"
+        ));
+    }
+
+    #[test]
+    fn test_pcap_reader_rejects_unsupported_link_type() {
+        let mut bytes: Vec<u8> = PcapWriter::new(Vec::new()).unwrap().into_inner();
+        bytes[20..24].copy_from_slice(&147u32.to_le_bytes());
+
+        assert!(matches!(
+            PcapReader::new(bytes.as_slice()),
+            Err(Error::UnsupportedLinkType(147))
+        ));
+    }
+}
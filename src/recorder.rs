@@ -0,0 +1,202 @@
+use crate::capture::{CaptureHeader, CaptureRecord, BLOCK_SIZE, CAPTURE_VERSION};
+use crate::index::Indexer;
+use crate::{Compression, Error};
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+pub struct Recorder<W: Write = File> {
+    writer: W,
+    started: Instant,
+    position: u64,
+    indexer: Indexer,
+    compression: Compression,
+    block: Vec<u8>,
+}
+
+impl Recorder {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Recorder::new(File::create(path)?)
+    }
+
+    pub fn create_with_compression(
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<Self, Error> {
+        Recorder::with_compression(File::create(path)?, compression)
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(writer: W) -> Result<Self, Error> {
+        Recorder::with_compression(writer, Compression::None)
+    }
+
+    pub fn with_compression(mut writer: W, compression: Compression) -> Result<Self, Error> {
+        let header: CaptureHeader = CaptureHeader {
+            version: CAPTURE_VERSION,
+            started_at: SystemTime::now(),
+            compression,
+        };
+        let started: Instant = Instant::now();
+        writer.write_all(&header.serialize()?)?;
+        writer.flush()?;
+
+        Ok(Recorder {
+            writer,
+            started,
+            position: header.size(),
+            indexer: Indexer::default(),
+            compression,
+            block: Vec::new(),
+        })
+    }
+
+    pub fn record(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.record_at(self.started.elapsed(), bytes)
+    }
+
+    pub fn record_at(&mut self, time: Duration, bytes: &[u8]) -> Result<(), Error> {
+        let record: CaptureRecord = CaptureRecord {
+            time,
+            bytes: bytes.to_vec(),
+        };
+        let bytes: Vec<u8> = record.serialize()?;
+        if self.compression != Compression::None {
+            // Records are buffered until a block is full, so a crash loses the
+            // records of at most one block.
+            if !self.block.is_empty() && self.block.len() + bytes.len() > BLOCK_SIZE {
+                self.flush()?;
+            }
+            self.indexer
+                .push(self.position << 16 | self.block.len() as u64, &record.bytes);
+            self.block.extend_from_slice(&bytes);
+            if self.block.len() >= BLOCK_SIZE {
+                self.flush()?;
+            }
+            return Ok(());
+        }
+        // Each record goes out in a single write followed by a flush, so a crash
+        // can leave at most one partial record at the end of the file.
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        self.indexer.push(self.position, &record.bytes);
+        self.position += bytes.len() as u64;
+
+        Ok(())
+    }
+
+    // Writes out the records buffered for the current compressed block.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if self.block.is_empty() {
+            return Ok(());
+        }
+        let compressed: Vec<u8> = self.compression.compress(&self.block)?;
+        let mut bytes: Vec<u8> = Vec::with_capacity(8 + compressed.len());
+        bytes.write_u32::<LittleEndian>(compressed.len() as u32)?;
+        bytes.write_u32::<LittleEndian>(self.block.len() as u32)?;
+        bytes.write_all(&compressed)?;
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        self.position += bytes.len() as u64;
+        self.block.clear();
+
+        Ok(())
+    }
+
+    // Appends the index block and returns the writer. Nothing may be recorded afterwards.
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.flush()?;
+        let index: Vec<u8> = self.indexer.into_index().serialize(self.position)?;
+        self.writer.write_all(&index)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    // Returns the writer without flushing the current compressed block or appending the index.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::CaptureReader;
+    #[cfg(any(feature = "zstd", feature = "lz4"))]
+    use crate::CaptureIndex;
+
+    #[test]
+    fn test_recorder_writes_readable_capture() {
+        let before: SystemTime = SystemTime::now();
+        let mut recorder: Recorder<Vec<u8>> = Recorder::new(Vec::new()).unwrap();
+        recorder.record(&[1, 2, 3]).unwrap();
+        recorder.record(&[]).unwrap();
+        recorder.record(&[4; 1500]).unwrap();
+        let bytes: Vec<u8> = recorder.into_inner();
+
+        let reader: CaptureReader<&[u8]> = CaptureReader::new(bytes.as_slice()).unwrap();
+        assert!(reader.header().started_at >= before);
+        let records: Vec<CaptureRecord> = reader.collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].bytes, vec![1, 2, 3]);
+        assert_eq!(records[1].bytes, Vec::<u8>::new());
+        assert_eq!(records[2].bytes, vec![4; 1500]);
+        assert!(records.windows(2).all(|pair| pair[0].time <= pair[1].time));
+    }
+
+    #[cfg(any(feature = "zstd", feature = "lz4"))]
+    fn assert_compressed_recorder_writes_readable_capture(compression: Compression) {
+        let records: Vec<Vec<u8>> = (0..200).map(|i| vec![i as u8; 1000 + i]).collect();
+        let mut recorder: Recorder<Vec<u8>> =
+            Recorder::with_compression(Vec::new(), compression).unwrap();
+        for bytes in &records {
+            recorder.record(bytes).unwrap();
+        }
+        let unfinished: Vec<u8> = {
+            let mut recorder: Recorder<Vec<u8>> =
+                Recorder::with_compression(Vec::new(), compression).unwrap();
+            for bytes in &records {
+                recorder.record(bytes).unwrap();
+            }
+            recorder.flush().unwrap();
+            recorder.into_inner()
+        };
+        let bytes: Vec<u8> = recorder.finish().unwrap();
+        assert!(unfinished.len() < 200 * 1100 / 10);
+
+        for bytes in [bytes, unfinished] {
+            let mut reader: CaptureReader<std::io::Cursor<Vec<u8>>> =
+                CaptureReader::new(std::io::Cursor::new(bytes)).unwrap();
+            assert_eq!(reader.header().compression, compression);
+            let index: CaptureIndex = reader.index().unwrap();
+            let read: Vec<Vec<u8>> = reader
+                .by_ref()
+                .map(|record| record.unwrap().bytes)
+                .collect();
+            assert_eq!(read, records);
+
+            assert_eq!(index.entries().len(), 200);
+            for i in [0, 59, 60, 150, 199] {
+                reader.seek_to(&index.entries()[i]).unwrap();
+                assert_eq!(reader.read_record().unwrap().unwrap().bytes, records[i]);
+            }
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_recorder_writes_readable_capture() {
+        assert_compressed_recorder_writes_readable_capture(Compression::Zstd);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_lz4_recorder_writes_readable_capture() {
+        assert_compressed_recorder_writes_readable_capture(Compression::Lz4);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::capture::{CaptureReader, CaptureRecord};
+use crate::packets::v2022;
+use crate::packets::PacketHeader;
+use crate::{Error, F1TelemetryClient, ShutdownHandle};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Seek};
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SLEEP_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    Multiplier(f64),
+    AsFastAsPossible,
+}
+
+impl Default for ReplaySpeed {
+    fn default() -> Self {
+        ReplaySpeed::Multiplier(1.0)
+    }
+}
+
+pub struct Replayer<R: Read + Seek = BufReader<File>> {
+    reader: CaptureReader<R>,
+    speed: ReplaySpeed,
+    looping: bool,
+    start_session_time: Option<f32>,
+    stop_session_time: Option<f32>,
+    shutdown_handle: ShutdownHandle,
+}
+
+impl Replayer {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Replayer::new(CaptureReader::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Replayer<R> {
+    pub fn new(reader: CaptureReader<R>) -> Self {
+        Replayer {
+            reader,
+            speed: ReplaySpeed::default(),
+            looping: false,
+            start_session_time: None,
+            stop_session_time: None,
+            shutdown_handle: ShutdownHandle::default(),
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: ReplaySpeed) -> Result<(), Error> {
+        if let ReplaySpeed::Multiplier(multiplier) = speed {
+            if !(multiplier > 0.0 && multiplier.is_finite()) {
+                return Err(Error::InvalidReplaySpeed(multiplier));
+            }
+        }
+        self.speed = speed;
+
+        Ok(())
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    pub fn set_start_session_time(&mut self, session_time: Option<f32>) {
+        self.start_session_time = session_time;
+    }
+
+    pub fn set_stop_session_time(&mut self, session_time: Option<f32>) {
+        self.stop_session_time = session_time;
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown_handle.clone()
+    }
+
+    pub fn replay_to(&mut self, target: impl ToSocketAddrs) -> Result<(), Error> {
+        let target: SocketAddr = target.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(ErrorKind::InvalidInput, "no address to replay to")
+        })?;
+        let bind_address: &str = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket: UdpSocket = UdpSocket::bind(bind_address).map_err(Error::Bind)?;
+        socket.connect(target)?;
+        self.replay(|bytes| {
+            socket.send(bytes)?;
+            Ok(())
+        })
+    }
+
+    pub fn feed(&mut self, client: &mut F1TelemetryClient) -> Result<(), Error> {
+        self.replay(|bytes| {
+            if let Err(e) = client.process_datagram(bytes) {
+                client.error_handlers.call(&e);
+            }
+            Ok(())
+        })
+    }
+
+    pub fn replay(
+        &mut self,
+        mut emit: impl FnMut(&[u8]) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        loop {
+            self.reader.rewind()?;
+            let emitted: usize = self.replay_once(&mut emit)?;
+            if !self.looping || emitted == 0 || self.shutdown_handle.is_shutdown() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn replay_once(
+        &mut self,
+        emit: &mut impl FnMut(&[u8]) -> Result<(), Error>,
+    ) -> Result<usize, Error> {
+        let mut emitted: usize = 0;
+        let mut started: bool = self.start_session_time.is_none();
+        let mut origin: Option<(Duration, Instant)> = None;
+        while let Some(record) = self.reader.read_record()? {
+            if self.shutdown_handle.is_shutdown() {
+                break;
+            }
+            let session_time: Option<f32> = session_time(&record.bytes);
+            if !started {
+                match (session_time, self.start_session_time) {
+                    (Some(session_time), Some(start)) if session_time >= start => started = true,
+                    _ => continue,
+                }
+            }
+            if let (Some(session_time), Some(stop)) = (session_time, self.stop_session_time) {
+                if session_time > stop {
+                    break;
+                }
+            }
+            let (record_origin, instant_origin) =
+                *origin.get_or_insert((record.time, Instant::now()));
+            self.wait(&record, record_origin, instant_origin);
+            if self.shutdown_handle.is_shutdown() {
+                break;
+            }
+            emit(&record.bytes)?;
+            emitted += 1;
+        }
+
+        Ok(emitted)
+    }
+
+    fn wait(&self, record: &CaptureRecord, record_origin: Duration, instant_origin: Instant) {
+        let ReplaySpeed::Multiplier(multiplier) = self.speed else {
+            return;
+        };
+        let elapsed: f64 = record.time.saturating_sub(record_origin).as_secs_f64() / multiplier;
+        let Ok(elapsed) = Duration::try_from_secs_f64(elapsed) else {
+            return;
+        };
+        let deadline: Instant = instant_origin + elapsed;
+        // Sleep in short steps so that a shutdown request is honoured promptly.
+        while !self.shutdown_handle.is_shutdown() {
+            let remaining: Duration = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(SLEEP_INTERVAL));
+        }
+    }
+}
+
+fn session_time(bytes: &[u8]) -> Option<f32> {
+    let header: PacketHeader = PacketHeader::unserialize(bytes).ok()?;
+    match header.packet_format {
+        2021 | 2022 => Some(v2022::PacketHeader::unserialize(bytes).ok()?.session_time),
+        _ => Some(header.session_time),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::PacketLapData;
+    use crate::{Packet, Recorder};
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    fn lap_packet(session_time: f32) -> Vec<u8> {
+        Packet::Lap(PacketLapData {
+            header: PacketHeader {
+                packet_format: 2024,
+                packet_id: 2,
+                session_time,
+                ..PacketHeader::default()
+            },
+            ..PacketLapData::default()
+        })
+        .encode()
+        .unwrap()
+    }
+
+    fn capture(records: &[(u64, f32)]) -> CaptureReader<Cursor<Vec<u8>>> {
+        let mut recorder: Recorder<Vec<u8>> = Recorder::new(Vec::new()).unwrap();
+        for (time, session_time) in records {
+            recorder
+                .record_at(Duration::from_millis(*time), &lap_packet(*session_time))
+                .unwrap();
+        }
+
+        CaptureReader::new(Cursor::new(recorder.into_inner())).unwrap()
+    }
+
+    fn replayed_session_times(replayer: &mut Replayer<Cursor<Vec<u8>>>) -> Vec<f32> {
+        let mut session_times: Vec<f32> = Vec::new();
+        replayer
+            .replay(|bytes| {
+                session_times.push(session_time(bytes).unwrap());
+                Ok(())
+            })
+            .unwrap();
+        session_times
+    }
+
+    #[test]
+    fn test_replay_keeps_original_pacing() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> =
+            Replayer::new(capture(&[(1000, 0.0), (1100, 0.1), (1200, 0.2)]));
+        let started: Instant = Instant::now();
+
+        assert_eq!(replayed_session_times(&mut replayer), vec![0.0, 0.1, 0.2]);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_replay_speed_multiplier() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> =
+            Replayer::new(capture(&[(0, 0.0), (200, 0.2), (400, 0.4)]));
+        replayer.set_speed(ReplaySpeed::Multiplier(4.0)).unwrap();
+        let started: Instant = Instant::now();
+
+        replayed_session_times(&mut replayer);
+
+        let elapsed: Duration = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(100));
+        assert!(elapsed < Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_replay_as_fast_as_possible() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> =
+            Replayer::new(capture(&[(0, 0.0), (60_000, 60.0)]));
+        replayer.set_speed(ReplaySpeed::AsFastAsPossible).unwrap();
+        let started: Instant = Instant::now();
+
+        assert_eq!(replayed_session_times(&mut replayer), vec![0.0, 60.0]);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_replay_session_time_offsets() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> =
+            Replayer::new(capture(&[(0, 0.0), (0, 1.0), (0, 2.0), (0, 3.0), (0, 4.0)]));
+        replayer.set_start_session_time(Some(1.0));
+        replayer.set_stop_session_time(Some(3.0));
+
+        assert_eq!(replayed_session_times(&mut replayer), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_replay_looping_until_shutdown() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> = Replayer::new(capture(&[(0, 0.0), (0, 1.0)]));
+        replayer.set_looping(true);
+        let shutdown_handle: ShutdownHandle = replayer.shutdown_handle();
+        let mut session_times: Vec<f32> = Vec::new();
+
+        replayer
+            .replay(|bytes| {
+                session_times.push(session_time(bytes).unwrap());
+                if session_times.len() == 5 {
+                    shutdown_handle.shutdown();
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(session_times, vec![0.0, 1.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_replay_looping_stops_when_nothing_matches() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> = Replayer::new(capture(&[(0, 0.0), (0, 1.0)]));
+        replayer.set_looping(true);
+        replayer.set_start_session_time(Some(5.0));
+
+        assert!(replayed_session_times(&mut replayer).is_empty());
+    }
+
+    #[test]
+    fn test_replay_feeds_client() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> = Replayer::new(capture(&[(0, 0.0), (0, 1.0)]));
+        replayer.set_speed(ReplaySpeed::AsFastAsPossible).unwrap();
+        let mut client: F1TelemetryClient = F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        let session_times: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+        let shared: Arc<Mutex<Vec<f32>>> = Arc::clone(&session_times);
+        client.add_packet_lap_data_handler(Box::new(move |packet| {
+            shared.lock().unwrap().push(packet.header.session_time);
+        }));
+
+        replayer.feed(&mut client).unwrap();
+
+        assert_eq!(*session_times.lock().unwrap(), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_replay_rejects_invalid_speed() {
+        let mut replayer: Replayer<Cursor<Vec<u8>>> = Replayer::new(capture(&[(0, 0.0)]));
+
+        for multiplier in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                replayer.set_speed(ReplaySpeed::Multiplier(multiplier)),
+                Err(Error::InvalidReplaySpeed(_))
+            ));
+        }
+        assert_eq!(replayer.speed, ReplaySpeed::default());
+    }
+
+    #[test]
+    fn test_replay_to_socket() {
+        for address in ["127.0.0.1:0", "[::1]:0"] {
+            let mut replayer: Replayer<Cursor<Vec<u8>>> =
+                Replayer::new(capture(&[(0, 0.0), (0, 1.0)]));
+            let receiver: UdpSocket = UdpSocket::bind(address).unwrap();
+            receiver
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+
+            replayer.replay_to(receiver.local_addr().unwrap()).unwrap();
+
+            let mut buf: [u8; 2048] = [0; 2048];
+            for session_time in [0.0, 1.0] {
+                let received: usize = receiver.recv(&mut buf).unwrap();
+                assert_eq!(buf[..received], lap_packet(session_time));
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::packets::nul_terminated;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// serde only implements its traits for arrays of up to 32 elements.
+pub(crate) mod array {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        array: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(array)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[T; N], D::Error> {
+        let elements: Vec<T> = Vec::deserialize(deserializer)?;
+        let length: usize = elements.len();
+        elements
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(length, &format!("{N} elements").as_str()))
+    }
+}
+
+// Null-terminated byte arrays such as driver names, as strings.
+pub(crate) mod byte_string {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, const N: usize>(
+        bytes: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&nul_terminated(bytes))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        let string: String = String::deserialize(deserializer)?;
+        if string.len() > N {
+            return Err(D::Error::invalid_length(
+                string.len(),
+                &format!("at most {N} bytes").as_str(),
+            ));
+        }
+        let mut bytes: [u8; N] = [0; N];
+        bytes[..string.len()].copy_from_slice(string.as_bytes());
+        Ok(bytes)
+    }
+}
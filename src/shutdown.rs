@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ShutdownHandle {
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutdown_handle_is_shared_between_clones() {
+        let handle: ShutdownHandle = ShutdownHandle::default();
+        let clone: ShutdownHandle = handle.clone();
+        assert!(!handle.is_shutdown());
+
+        clone.shutdown();
+
+        assert!(handle.is_shutdown());
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{CaptureReader, Error, Packet};
+use std::io::Read;
+
+// Consumes decoded packets, e.g. to export or store them. The same sink takes
+// live traffic through `push` and recorded traffic through `push_capture`.
+pub trait PacketSink {
+    fn push(&mut self, packet: &Packet) -> Result<(), Error>;
+
+    fn push_capture<R: Read>(&mut self, reader: CaptureReader<R>) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        for record in reader {
+            // Datagrams that don't decode are skipped, as the client does.
+            if let Ok(packet) = Packet::decode(&record?.bytes) {
+                self.push(&packet)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,647 @@
+use crate::packets::{
+    nul_terminated, Event, PacketEventData, PacketFinalClassificationData, PacketHeader,
+    PacketParticipantsData, PacketSessionData, PacketSessionHistoryData,
+};
+use crate::{Error, Packet, PacketSink};
+use rusqlite::{params, Connection, Transaction};
+use std::path::Path;
+
+// Applied in order; `PRAGMA user_version` holds the number of migrations that
+// have been applied to a database. Only ever append to this list.
+const MIGRATIONS: &[&str] = &["
+CREATE TABLE sessions (
+    session_uid TEXT PRIMARY KEY,
+    season_link_identifier INTEGER,
+    weekend_link_identifier INTEGER,
+    session_link_identifier INTEGER,
+    packet_format INTEGER,
+    game_year INTEGER,
+    session_type INTEGER,
+    track_id INTEGER,
+    formula INTEGER,
+    weather INTEGER,
+    track_temperature INTEGER,
+    air_temperature INTEGER,
+    total_laps INTEGER,
+    track_length INTEGER,
+    session_duration INTEGER,
+    ai_difficulty INTEGER,
+    game_mode INTEGER,
+    rule_set INTEGER
+);
+CREATE INDEX sessions_link_identifiers
+    ON sessions (season_link_identifier, weekend_link_identifier, session_link_identifier);
+
+CREATE TABLE participants (
+    session_uid TEXT NOT NULL REFERENCES sessions,
+    car_index INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    ai_controlled INTEGER NOT NULL,
+    driver_id INTEGER NOT NULL,
+    network_id INTEGER NOT NULL,
+    team_id INTEGER NOT NULL,
+    race_number INTEGER NOT NULL,
+    nationality INTEGER NOT NULL,
+    platform INTEGER NOT NULL,
+    PRIMARY KEY (session_uid, car_index)
+);
+
+CREATE TABLE laps (
+    session_uid TEXT NOT NULL REFERENCES sessions,
+    car_index INTEGER NOT NULL,
+    lap_num INTEGER NOT NULL,
+    lap_time_in_ms INTEGER NOT NULL,
+    valid INTEGER NOT NULL,
+    PRIMARY KEY (session_uid, car_index, lap_num)
+);
+
+CREATE TABLE sectors (
+    session_uid TEXT NOT NULL,
+    car_index INTEGER NOT NULL,
+    lap_num INTEGER NOT NULL,
+    sector INTEGER NOT NULL,
+    time_in_ms INTEGER NOT NULL,
+    valid INTEGER NOT NULL,
+    PRIMARY KEY (session_uid, car_index, lap_num, sector),
+    FOREIGN KEY (session_uid, car_index, lap_num) REFERENCES laps ON DELETE CASCADE
+);
+
+CREATE TABLE stints (
+    session_uid TEXT NOT NULL REFERENCES sessions,
+    car_index INTEGER NOT NULL,
+    stint_num INTEGER NOT NULL,
+    end_lap INTEGER NOT NULL,
+    tyre_actual_compound INTEGER NOT NULL,
+    tyre_visual_compound INTEGER NOT NULL,
+    PRIMARY KEY (session_uid, car_index, stint_num)
+);
+
+CREATE TABLE events (
+    id INTEGER PRIMARY KEY,
+    session_uid TEXT NOT NULL REFERENCES sessions,
+    session_time REAL NOT NULL,
+    frame_identifier INTEGER NOT NULL,
+    code TEXT NOT NULL,
+    vehicle_idx INTEGER,
+    details TEXT NOT NULL
+);
+CREATE INDEX events_session_uid ON events (session_uid, code);
+
+CREATE TABLE classifications (
+    session_uid TEXT NOT NULL REFERENCES sessions,
+    car_index INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    num_laps INTEGER NOT NULL,
+    grid_position INTEGER NOT NULL,
+    points INTEGER NOT NULL,
+    num_pit_stops INTEGER NOT NULL,
+    result_status INTEGER NOT NULL,
+    best_lap_time_in_ms INTEGER NOT NULL,
+    total_race_time REAL NOT NULL,
+    penalties_time INTEGER NOT NULL,
+    num_penalties INTEGER NOT NULL,
+    num_tyre_stints INTEGER NOT NULL,
+    PRIMARY KEY (session_uid, car_index)
+);
+"];
+
+// Stores sessions, participants, laps with their sector times, tyre stints,
+// events and final classifications in a SQLite database. Rows are keyed by
+// the session UID, stored as text so that it reads the same as in the other
+// outputs, and are updated as newer packets arrive.
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        SqliteStore::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        SqliteStore::new(Connection::open_in_memory()?)
+    }
+
+    fn new(mut connection: Connection) -> Result<Self, Error> {
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        connection.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut connection)?;
+
+        Ok(SqliteStore { connection })
+    }
+
+    // For queries against the stored data.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    // Closes the database, which checkpoints the write-ahead log into it.
+    pub fn finish(self) -> Result<(), Error> {
+        self.connection.close().map_err(|(_, e)| Error::Sqlite(e))
+    }
+}
+
+impl PacketSink for SqliteStore {
+    fn push(&mut self, packet: &Packet) -> Result<(), Error> {
+        let transaction: Transaction = self.connection.transaction()?;
+        match packet {
+            Packet::Session(packet) => insert_session(&transaction, packet)?,
+            Packet::Participants(packet) => insert_participants(&transaction, packet)?,
+            Packet::SessionHistory(packet) => insert_session_history(&transaction, packet)?,
+            Packet::Event(packet) => insert_event(&transaction, packet)?,
+            Packet::FinalClassification(packet) => insert_classification(&transaction, packet)?,
+            _ => return Ok(()),
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), Error> {
+    let version: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let transaction: Transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", index + 1)?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+fn session_uid(header: &PacketHeader) -> String {
+    { header.session_uid }.to_string()
+}
+
+// Adds the session row that the other tables refer to, if the session packet
+// hasn't been seen yet.
+fn insert_session_uid(transaction: &Transaction, header: &PacketHeader) -> Result<(), Error> {
+    transaction.execute(
+        "INSERT OR IGNORE INTO sessions (session_uid) VALUES (?1)",
+        params![session_uid(header)],
+    )?;
+
+    Ok(())
+}
+
+fn insert_session(transaction: &Transaction, packet: &PacketSessionData) -> Result<(), Error> {
+    let header: PacketHeader = packet.header;
+    transaction.execute(
+        "INSERT INTO sessions (
+            session_uid, season_link_identifier, weekend_link_identifier,
+            session_link_identifier, packet_format, game_year, session_type, track_id, formula,
+            weather, track_temperature, air_temperature, total_laps, track_length,
+            session_duration, ai_difficulty, game_mode, rule_set
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+        ON CONFLICT (session_uid) DO UPDATE SET
+            season_link_identifier = excluded.season_link_identifier,
+            weekend_link_identifier = excluded.weekend_link_identifier,
+            session_link_identifier = excluded.session_link_identifier,
+            packet_format = excluded.packet_format,
+            game_year = excluded.game_year,
+            session_type = excluded.session_type,
+            track_id = excluded.track_id,
+            formula = excluded.formula,
+            weather = excluded.weather,
+            track_temperature = excluded.track_temperature,
+            air_temperature = excluded.air_temperature,
+            total_laps = excluded.total_laps,
+            track_length = excluded.track_length,
+            session_duration = excluded.session_duration,
+            ai_difficulty = excluded.ai_difficulty,
+            game_mode = excluded.game_mode,
+            rule_set = excluded.rule_set",
+        params![
+            session_uid(&header),
+            { packet.season_link_identifier },
+            { packet.weekend_link_identifier },
+            { packet.session_link_identifier },
+            { header.packet_format },
+            { header.game_year },
+            { packet.session_type },
+            { packet.track_id },
+            { packet.formula },
+            { packet.weather },
+            { packet.track_temperature },
+            { packet.air_temperature },
+            { packet.total_laps },
+            { packet.track_length },
+            { packet.session_duration },
+            { packet.ai_difficulty },
+            { packet.game_mode },
+            { packet.rule_set },
+        ],
+    )?;
+
+    Ok(())
+}
+
+fn insert_participants(
+    transaction: &Transaction,
+    packet: &PacketParticipantsData,
+) -> Result<(), Error> {
+    let header: PacketHeader = packet.header;
+    insert_session_uid(transaction, &header)?;
+    let num_active_cars: usize = packet.num_active_cars as usize;
+    for (car_index, participant) in packet.participants.iter().enumerate().take(num_active_cars) {
+        transaction.execute(
+            "INSERT OR REPLACE INTO participants (
+                session_uid, car_index, name, ai_controlled, driver_id, network_id, team_id,
+                race_number, nationality, platform
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                session_uid(&header),
+                car_index,
+                nul_terminated(&participant.name),
+                { participant.ai_controlled },
+                { participant.driver_id },
+                { participant.network_id },
+                { participant.team_id },
+                { participant.race_number },
+                { participant.nationality },
+                { participant.platform },
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Laps are stored once they are complete, that is once they have a lap time.
+fn insert_session_history(
+    transaction: &Transaction,
+    packet: &PacketSessionHistoryData,
+) -> Result<(), Error> {
+    let header: PacketHeader = packet.header;
+    insert_session_uid(transaction, &header)?;
+    let num_laps: usize = packet.num_laps as usize;
+    for (index, lap) in packet.lap_history_data.iter().enumerate().take(num_laps) {
+        let lap_time_in_ms: u32 = lap.lap_time_in_ms;
+        if lap_time_in_ms == 0 {
+            continue;
+        }
+        let flags: u8 = lap.lap_valid_bit_flags;
+        // `INSERT OR REPLACE` would delete the lap's sectors through the cascade.
+        transaction.execute(
+            "INSERT INTO laps (session_uid, car_index, lap_num, lap_time_in_ms, valid)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (session_uid, car_index, lap_num) DO UPDATE SET
+                lap_time_in_ms = excluded.lap_time_in_ms,
+                valid = excluded.valid",
+            params![
+                session_uid(&header),
+                { packet.car_idx },
+                index + 1,
+                lap_time_in_ms,
+                flags & 0x01 != 0,
+            ],
+        )?;
+        let sectors: [(u8, u16); 3] = [
+            (lap.sector1_time_minutes, lap.sector1_time_in_ms),
+            (lap.sector2_time_minutes, lap.sector2_time_in_ms),
+            (lap.sector3_time_minutes, lap.sector3_time_in_ms),
+        ];
+        for (sector, (minutes, ms)) in sectors.into_iter().enumerate() {
+            transaction.execute(
+                "INSERT OR REPLACE INTO sectors (
+                    session_uid, car_index, lap_num, sector, time_in_ms, valid
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    session_uid(&header),
+                    { packet.car_idx },
+                    index + 1,
+                    sector + 1,
+                    minutes as u32 * 60_000 + ms as u32,
+                    flags & (0x02 << sector) != 0,
+                ],
+            )?;
+        }
+    }
+    let num_tyre_stints: usize = packet.num_tyre_stints as usize;
+    for (index, stint) in packet
+        .tyre_stints_history_data
+        .iter()
+        .enumerate()
+        .take(num_tyre_stints)
+    {
+        insert_stint(
+            transaction,
+            &header,
+            packet.car_idx,
+            index,
+            [
+                stint.end_lap,
+                stint.tyre_actual_compound,
+                stint.tyre_visual_compound,
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn insert_stint(
+    transaction: &Transaction,
+    header: &PacketHeader,
+    car_index: u8,
+    index: usize,
+    [end_lap, tyre_actual_compound, tyre_visual_compound]: [u8; 3],
+) -> Result<(), Error> {
+    transaction.execute(
+        "INSERT OR REPLACE INTO stints (
+            session_uid, car_index, stint_num, end_lap, tyre_actual_compound, tyre_visual_compound
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            session_uid(header),
+            car_index,
+            index + 1,
+            end_lap,
+            tyre_actual_compound,
+            tyre_visual_compound,
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Button presses are sent as events too, but aren't worth storing.
+fn insert_event(transaction: &Transaction, packet: &PacketEventData) -> Result<(), Error> {
+    if matches!(packet.event, Event::Buttons(_)) {
+        return Ok(());
+    }
+    let header: PacketHeader = packet.header;
+    insert_session_uid(transaction, &header)?;
+    let details: serde_json::Value =
+        serde_json::to_value(packet.event).map_err(std::io::Error::from)?;
+    let vehicle_idx: Option<u64> = details["details"]["vehicle_idx"].as_u64();
+    transaction.execute(
+        "INSERT INTO events (session_uid, session_time, frame_identifier, code, vehicle_idx, details)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            session_uid(&header),
+            { header.session_time },
+            { header.frame_identifier },
+            String::from_utf8_lossy(&packet.event.code()),
+            vehicle_idx,
+            details.to_string(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+fn insert_classification(
+    transaction: &Transaction,
+    packet: &PacketFinalClassificationData,
+) -> Result<(), Error> {
+    let header: PacketHeader = packet.header;
+    insert_session_uid(transaction, &header)?;
+    let num_cars: usize = packet.num_cars as usize;
+    for (car_index, classification) in packet.classification_data.iter().enumerate().take(num_cars)
+    {
+        transaction.execute(
+            "INSERT OR REPLACE INTO classifications (
+                session_uid, car_index, position, num_laps, grid_position, points, num_pit_stops,
+                result_status, best_lap_time_in_ms, total_race_time, penalties_time,
+                num_penalties, num_tyre_stints
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                session_uid(&header),
+                car_index,
+                { classification.position },
+                { classification.num_laps },
+                { classification.grid_position },
+                { classification.points },
+                { classification.num_pit_stops },
+                { classification.result_status },
+                { classification.best_lap_time_in_ms },
+                { classification.total_race_time },
+                { classification.penalties_time },
+                { classification.num_penalties },
+                { classification.num_tyre_stints },
+            ],
+        )?;
+        let num_tyre_stints: usize = classification.num_tyre_stints as usize;
+        for index in 0..num_tyre_stints.min(classification.tyre_stints_end_laps.len()) {
+            insert_stint(
+                transaction,
+                &header,
+                car_index as u8,
+                index,
+                [
+                    classification.tyre_stints_end_laps[index],
+                    classification.tyre_stints_actual[index],
+                    classification.tyre_stints_visual[index],
+                ],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{FastestLap, LapHistoryData};
+    use crate::test_support::header;
+    use crate::{CaptureReader, Recorder};
+
+    fn session_history(lap_time_in_ms: u32) -> Packet {
+        let mut packet: PacketSessionHistoryData = PacketSessionHistoryData {
+            header: header(11, u64::MAX, 3),
+            car_idx: 1,
+            num_laps: 2,
+            num_tyre_stints: 1,
+            ..PacketSessionHistoryData::default()
+        };
+        packet.lap_history_data[0] = LapHistoryData {
+            lap_time_in_ms,
+            sector1_time_in_ms: 30_000,
+            sector1_time_minutes: 0,
+            sector2_time_in_ms: 5_000,
+            sector2_time_minutes: 1,
+            sector3_time_in_ms: 20_000,
+            sector3_time_minutes: 0,
+            lap_valid_bit_flags: 0b1011,
+        };
+        packet.tyre_stints_history_data[0].end_lap = 255;
+        packet.tyre_stints_history_data[0].tyre_visual_compound = 16;
+        Packet::SessionHistory(packet)
+    }
+
+    fn query(store: &SqliteStore, sql: &str) -> Vec<String> {
+        let mut statement = store.connection().prepare(sql).unwrap();
+        let rows = statement
+            .query_map([], |row| {
+                let value: rusqlite::types::Value = row.get(0)?;
+                Ok(format!("{value:?}"))
+            })
+            .unwrap();
+        rows.map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn test_sqlite_store_migrations() {
+        let path =
+            std::env::temp_dir().join(format!("f1-telemetry-client-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut store: SqliteStore = SqliteStore::open(&path).unwrap();
+            store.push(&session_history(115_000)).unwrap();
+        }
+        let store: SqliteStore = SqliteStore::open(&path).unwrap();
+
+        assert_eq!(
+            query(&store, "PRAGMA user_version"),
+            vec![format!("Integer({})", MIGRATIONS.len())]
+        );
+        assert_eq!(
+            query(&store, "SELECT lap_time_in_ms FROM laps"),
+            vec!["Integer(115000)"]
+        );
+        drop(store);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[test]
+    fn test_sqlite_store_writes_session_data() {
+        let mut store: SqliteStore = SqliteStore::open_in_memory().unwrap();
+        let mut participants: PacketParticipantsData = PacketParticipantsData {
+            header: header(4, u64::MAX, 3),
+            num_active_cars: 2,
+            ..PacketParticipantsData::default()
+        };
+        participants.participants[1].name[..3].copy_from_slice(b"Max");
+        participants.participants[1].team_id = 2;
+        let mut classification: PacketFinalClassificationData = PacketFinalClassificationData {
+            header: header(8, u64::MAX, 3),
+            num_cars: 2,
+            ..PacketFinalClassificationData::default()
+        };
+        classification.classification_data[1].position = 1;
+        classification.classification_data[1].num_tyre_stints = 2;
+        classification.classification_data[1].tyre_stints_end_laps[..2].copy_from_slice(&[20, 50]);
+
+        for packet in [
+            session_history(0),
+            Packet::Session(PacketSessionData {
+                header: header(1, u64::MAX, 3),
+                season_link_identifier: 1,
+                weekend_link_identifier: 2,
+                session_link_identifier: 3,
+                track_id: 10,
+                ..PacketSessionData::default()
+            }),
+            Packet::Participants(participants),
+            session_history(115_000),
+            session_history(114_000),
+            Packet::Event(PacketEventData {
+                header: header(3, u64::MAX, 3),
+                event: Event::FastestLap(FastestLap {
+                    vehicle_idx: 1,
+                    lap_time: 114.0,
+                }),
+            }),
+            Packet::Event(PacketEventData {
+                header: header(3, u64::MAX, 3),
+                event: Event::SessionEnded,
+            }),
+            Packet::FinalClassification(classification),
+        ] {
+            store.push(&packet).unwrap();
+        }
+
+        assert_eq!(
+            query(
+                &store,
+                "SELECT session_uid || ' ' || season_link_identifier || ' '
+                    || weekend_link_identifier || ' ' || session_link_identifier || ' ' || track_id
+                FROM sessions"
+            ),
+            vec!["Text(\"18446744073709551615 1 2 3 10\")"]
+        );
+        assert_eq!(
+            query(&store, "SELECT name FROM participants ORDER BY car_index"),
+            vec!["Text(\"\")", "Text(\"Max\")"]
+        );
+        assert_eq!(
+            query(&store, "SELECT lap_time_in_ms || ' ' || valid FROM laps"),
+            vec!["Text(\"114000 1\")"]
+        );
+        assert_eq!(
+            query(
+                &store,
+                "SELECT time_in_ms || ' ' || valid FROM sectors ORDER BY sector"
+            ),
+            vec![
+                "Text(\"30000 1\")",
+                "Text(\"65000 0\")",
+                "Text(\"20000 1\")"
+            ]
+        );
+        assert_eq!(
+            query(&store, "SELECT end_lap FROM stints ORDER BY stint_num"),
+            vec!["Integer(20)", "Integer(50)"]
+        );
+        assert_eq!(
+            query(
+                &store,
+                "SELECT code || ' ' || ifnull(vehicle_idx, '-') FROM events ORDER BY id"
+            ),
+            vec!["Text(\"FTLP 1\")", "Text(\"SEND -\")"]
+        );
+        assert_eq!(
+            query(
+                &store,
+                "SELECT position FROM classifications ORDER BY car_index"
+            ),
+            vec!["Integer(0)", "Integer(1)"]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_store_reads_capture() {
+        let mut recorder: Recorder<Vec<u8>> = Recorder::new(Vec::new()).unwrap();
+        recorder
+            .record(&session_history(115_000).encode().unwrap())
+            .unwrap();
+        recorder.record(&[0; 10]).unwrap();
+        let bytes: Vec<u8> = recorder.into_inner();
+        let mut store: SqliteStore = SqliteStore::open_in_memory().unwrap();
+
+        store
+            .push_capture(CaptureReader::new(bytes.as_slice()).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            query(&store, "SELECT count(*) FROM laps"),
+            vec!["Integer(1)"]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_store_finish_checkpoints() {
+        let path = std::env::temp_dir().join(format!(
+            "f1-telemetry-client-finish-{}.sqlite",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let mut store: SqliteStore = SqliteStore::open(&path).unwrap();
+        store.push(&session_history(115_000)).unwrap();
+        store.finish().unwrap();
+
+        assert!(!path.with_extension("sqlite-wal").exists());
+        let store: SqliteStore = SqliteStore::open(&path).unwrap();
+        assert_eq!(
+            query(&store, "SELECT count(*) FROM laps"),
+            vec!["Integer(1)"]
+        );
+        store.finish().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,15 @@
+use crate::packets::PacketHeader;
+
+// The header shared by the test packets: an F1 24 packet whose session time
+// advances half a second per frame, with the player in car 1.
+pub(crate) fn header(packet_id: u8, session_uid: u64, frame_identifier: u32) -> PacketHeader {
+    PacketHeader {
+        packet_format: 2024,
+        packet_id,
+        session_uid,
+        session_time: frame_identifier as f32 / 2.0,
+        frame_identifier,
+        player_car_index: 1,
+        ..PacketHeader::default()
+    }
+}
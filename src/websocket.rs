@@ -0,0 +1,404 @@
+use crate::packets::NUM_CARS;
+use crate::{Error, F1TelemetryClient, Packet, ShutdownHandle, SubscriptionId};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tungstenite::{Message, WebSocket};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+// Packets waiting to be sent to a client. Packets for a client that falls
+// further behind are dropped, so a slow browser doesn't hold back the others.
+const CLIENT_QUEUE_CAPACITY: usize = 64;
+
+// Sent by a client to choose what it receives. Every field is optional and a
+// new subscription replaces the previous one.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Subscription {
+    packet_ids: Option<Vec<u8>>,
+    car_indices: Option<Vec<usize>>,
+    // Messages per second and packet id.
+    max_rate: Option<f64>,
+}
+
+type Broadcast = Arc<(u8, Value)>;
+
+#[derive(Default)]
+struct Broadcaster {
+    clients: Mutex<Vec<SyncSender<Broadcast>>>,
+}
+
+impl Broadcaster {
+    fn broadcast(&self, packet: &Packet) {
+        let mut clients = self.clients.lock().unwrap();
+        if clients.is_empty() {
+            return;
+        }
+        let Ok(value) = serde_json::to_value(packet) else {
+            return;
+        };
+        let broadcast: Broadcast = Arc::new((packet.header().packet_id, value));
+        clients.retain(|client| match client.try_send(Arc::clone(&broadcast)) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+}
+
+// Broadcasts decoded packets as JSON, in the same representation as the JSON
+// Lines output, to the WebSocket clients connected to it. Clients send a
+// subscription message such as
+// `{"packet_ids": [6], "car_indices": [0, 1], "max_rate": 10}` to receive a
+// subset; per-car arrays keep their length, with `null` for the other cars.
+pub struct WebSocketServer {
+    broadcaster: Arc<Broadcaster>,
+    local_addr: SocketAddr,
+    shutdown_handle: ShutdownHandle,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WebSocketServer {
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, Error> {
+        let listener: TcpListener = TcpListener::bind(address).map_err(Error::Bind)?;
+        // Accept without blocking so that a shutdown request is noticed.
+        listener.set_nonblocking(true)?;
+        let local_addr: SocketAddr = listener.local_addr()?;
+        let broadcaster: Arc<Broadcaster> = Arc::new(Broadcaster::default());
+        let shutdown_handle: ShutdownHandle = ShutdownHandle::default();
+        let thread: JoinHandle<()> = {
+            let broadcaster: Arc<Broadcaster> = Arc::clone(&broadcaster);
+            let shutdown_handle: ShutdownHandle = shutdown_handle.clone();
+            thread::spawn(move || {
+                while !shutdown_handle.is_shutdown() {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let broadcaster: Arc<Broadcaster> = Arc::clone(&broadcaster);
+                            thread::spawn(move || serve(stream, &broadcaster));
+                        }
+                        // Other errors, such as running out of file descriptors,
+                        // would otherwise repeat straight away.
+                        Err(_) => thread::sleep(POLL_INTERVAL),
+                    }
+                }
+                broadcaster.clients.lock().unwrap().clear();
+            })
+        };
+
+        Ok(WebSocketServer {
+            broadcaster,
+            local_addr,
+            shutdown_handle,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.broadcaster.clients.lock().unwrap().len()
+    }
+
+    pub fn broadcast(&self, packet: &Packet) {
+        self.broadcaster.broadcast(packet);
+    }
+
+    // Registers handlers on the client that broadcast the packets with the
+    // given ids, and returns their subscription ids.
+    pub fn attach(&self, client: &mut F1TelemetryClient, packet_ids: &[u8]) -> Vec<SubscriptionId> {
+        macro_rules! attach {
+            ($packet_id:expr, $($id:literal => $add:ident($variant:ident)),* $(,)?) => {
+                match $packet_id {
+                    $($id => {
+                        let broadcaster: Arc<Broadcaster> = Arc::clone(&self.broadcaster);
+                        Some(client.$add(Box::new(move |packet| {
+                            broadcaster.broadcast(&Packet::$variant(*packet))
+                        })))
+                    })*
+                    _ => None,
+                }
+            };
+        }
+
+        packet_ids
+            .iter()
+            .filter_map(|packet_id| {
+                attach!(
+                    packet_id,
+                    0 => add_packet_motion_data_handler(Motion),
+                    1 => add_packet_session_data_handler(Session),
+                    2 => add_packet_lap_data_handler(Lap),
+                    3 => add_packet_event_data_handler(Event),
+                    4 => add_packet_participants_data_handler(Participants),
+                    5 => add_packet_car_setup_data_handler(CarSetup),
+                    6 => add_packet_car_telemetry_data_handler(CarTelemetry),
+                    7 => add_packet_car_status_data_handler(CarStatus),
+                    8 => add_packet_final_classification_data_handler(FinalClassification),
+                    9 => add_packet_lobby_info_data_handler(LobbyInfo),
+                    10 => add_packet_car_damage_data_handler(CarDamage),
+                    11 => add_packet_session_history_data_handler(SessionHistory),
+                    12 => add_packet_tyre_sets_data_handler(TyreSets),
+                    13 => add_packet_motion_ex_data_handler(MotionEx),
+                    14 => add_packet_time_trial_data_handler(TimeTrial),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Drop for WebSocketServer {
+    fn drop(&mut self) {
+        self.shutdown_handle.shutdown();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(stream: TcpStream, broadcaster: &Broadcaster) {
+    if stream.set_nonblocking(false).is_err() {
+        return;
+    }
+    let Ok(mut websocket) = tungstenite::accept(stream) else {
+        return;
+    };
+    // Reads only wait briefly, so that packets are forwarded promptly.
+    if websocket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_millis(1)))
+        .is_err()
+    {
+        return;
+    }
+    let (sender, receiver) = mpsc::sync_channel::<Broadcast>(CLIENT_QUEUE_CAPACITY);
+    broadcaster.clients.lock().unwrap().push(sender);
+    let _ = forward(&mut websocket, &receiver);
+    let _ = websocket.close(None);
+    let _ = websocket.flush();
+}
+
+fn forward(
+    websocket: &mut WebSocket<TcpStream>,
+    receiver: &Receiver<Broadcast>,
+) -> Result<(), Box<tungstenite::Error>> {
+    let mut subscription: Subscription = Subscription::default();
+    let mut sent: HashMap<u8, Instant> = HashMap::new();
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(broadcast) => {
+                for broadcast in std::iter::once(broadcast).chain(receiver.try_iter()) {
+                    if let Some(text) = filter(&subscription, &mut sent, &broadcast) {
+                        websocket.send(Message::text(text)).map_err(Box::new)?;
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        match websocket.read() {
+            Ok(Message::Text(text)) => {
+                let reply: Value = match parse(&text) {
+                    Ok(new_subscription) => {
+                        subscription = new_subscription;
+                        sent.clear();
+                        let mut reply: Value = json!(subscription);
+                        reply["type"] = json!("Subscribed");
+                        reply
+                    }
+                    Err(message) => json!({ "type": "Error", "message": message }),
+                };
+                websocket
+                    .send(Message::text(reply.to_string()))
+                    .map_err(Box::new)?;
+            }
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
+}
+
+fn parse(text: &str) -> Result<Subscription, String> {
+    let subscription: Subscription = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if let Some(max_rate) = subscription.max_rate {
+        if !max_rate.is_finite() || max_rate <= 0.0 {
+            return Err(format!(
+                "max_rate must be a positive number, got {max_rate}"
+            ));
+        }
+    }
+
+    Ok(subscription)
+}
+
+fn filter(
+    subscription: &Subscription,
+    sent: &mut HashMap<u8, Instant>,
+    broadcast: &Broadcast,
+) -> Option<String> {
+    let (packet_id, value) = broadcast.as_ref();
+    if let Some(packet_ids) = &subscription.packet_ids {
+        if !packet_ids.contains(packet_id) {
+            return None;
+        }
+    }
+    if let Some(max_rate) = subscription.max_rate {
+        let now: Instant = Instant::now();
+        if let Some(last) = sent.get(packet_id) {
+            if now.duration_since(*last).as_secs_f64() * max_rate < 1.0 {
+                return None;
+            }
+        }
+        sent.insert(*packet_id, now);
+    }
+    let Some(car_indices) = &subscription.car_indices else {
+        return Some(value.to_string());
+    };
+    let mut value: Value = value.clone();
+    if let Value::Object(fields) = &mut value {
+        for field in fields.values_mut() {
+            match field {
+                Value::Array(cars)
+                    if cars.len() == NUM_CARS && cars.iter().all(Value::is_object) =>
+                {
+                    for (car_index, car) in cars.iter_mut().enumerate() {
+                        if !car_indices.contains(&car_index) {
+                            *car = Value::Null;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::{PacketCarTelemetryData, PacketLapData};
+    use crate::test_support::header;
+    use tungstenite::stream::MaybeTlsStream;
+
+    type Client = WebSocket<MaybeTlsStream<TcpStream>>;
+
+    fn telemetry(frame_identifier: u32) -> Packet {
+        let mut packet: PacketCarTelemetryData = PacketCarTelemetryData {
+            header: header(6, 0, frame_identifier),
+            ..PacketCarTelemetryData::default()
+        };
+        packet.car_telemetry_data[1].speed = 300;
+        Packet::CarTelemetry(packet)
+    }
+
+    fn lap(frame_identifier: u32) -> Packet {
+        Packet::Lap(PacketLapData {
+            header: header(2, 0, frame_identifier),
+            ..PacketLapData::default()
+        })
+    }
+
+    fn connect(server: &WebSocketServer, clients: usize) -> Client {
+        let (client, _) = tungstenite::connect(format!("ws://{}", server.local_addr())).unwrap();
+        let started: Instant = Instant::now();
+        while server.client_count() < clients {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(1));
+        }
+        client
+    }
+
+    fn receive(client: &mut Client) -> Value {
+        loop {
+            if let Message::Text(text) = client.read().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    fn subscribe(client: &mut Client, subscription: Value) -> Value {
+        client
+            .send(Message::text(subscription.to_string()))
+            .unwrap();
+        receive(client)
+    }
+
+    #[test]
+    fn test_websocket_server_broadcasts_attached_packets() {
+        let server: WebSocketServer = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        let mut telemetry_client: F1TelemetryClient =
+            F1TelemetryClient::new("127.0.0.1:0").unwrap();
+        assert_eq!(server.attach(&mut telemetry_client, &[6, 99]).len(), 1);
+        let mut first: Client = connect(&server, 1);
+        let mut second: Client = connect(&server, 2);
+
+        telemetry_client
+            .process_datagram(&lap(1).encode().unwrap())
+            .unwrap();
+        telemetry_client
+            .process_datagram(&telemetry(1).encode().unwrap())
+            .unwrap();
+
+        for client in [&mut first, &mut second] {
+            let value: Value = receive(client);
+            assert_eq!(value["type"], "CarTelemetry");
+            assert_eq!(value["header"]["frame_identifier"], 1);
+            assert_eq!(value["car_telemetry_data"][1]["speed"], 300);
+        }
+        drop(first);
+        telemetry_client
+            .process_datagram(&telemetry(2).encode().unwrap())
+            .unwrap();
+        assert_eq!(receive(&mut second)["header"]["frame_identifier"], 2);
+    }
+
+    #[test]
+    fn test_websocket_server_subscriptions() {
+        let server: WebSocketServer = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        let mut client: Client = connect(&server, 1);
+
+        let reply: Value = subscribe(
+            &mut client,
+            json!({ "packet_ids": [6], "car_indices": [1] }),
+        );
+        assert_eq!(reply["type"], "Subscribed");
+        server.broadcast(&lap(1));
+        server.broadcast(&telemetry(1));
+        let value: Value = receive(&mut client);
+        assert_eq!(value["type"], "CarTelemetry");
+        assert_eq!(value["car_telemetry_data"][0], Value::Null);
+        assert_eq!(value["car_telemetry_data"][1]["speed"], 300);
+        assert_eq!(
+            value["car_telemetry_data"].as_array().unwrap().len(),
+            NUM_CARS
+        );
+
+        subscribe(&mut client, json!({ "max_rate": 1.0 }));
+        server.broadcast(&telemetry(2));
+        server.broadcast(&telemetry(3));
+        server.broadcast(&lap(3));
+        assert_eq!(receive(&mut client)["header"]["frame_identifier"], 2);
+        assert_eq!(receive(&mut client)["type"], "Lap");
+
+        let reply: Value = subscribe(&mut client, json!({ "packet_ids": "all" }));
+        assert_eq!(reply["type"], "Error");
+        subscribe(&mut client, json!({ "packet_ids": [2] }));
+        for max_rate in [0.0, -1.0] {
+            let reply: Value = subscribe(&mut client, json!({ "max_rate": max_rate }));
+            assert_eq!(reply["type"], "Error");
+        }
+        // A rejected subscription leaves the previous one in place.
+        server.broadcast(&telemetry(4));
+        server.broadcast(&lap(4));
+        assert_eq!(receive(&mut client)["type"], "Lap");
+    }
+}